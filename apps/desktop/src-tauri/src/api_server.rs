@@ -0,0 +1,307 @@
+//! Local REST API server
+//!
+//! An optional authenticated HTTP API on localhost exposing log queries,
+//! function stats, and deployment status, so scripts and dashboards
+//! (e.g. a Grafana JSON datasource) can consume the panel's local data
+//! without speaking the MCP protocol. Off by default; started with
+//! `start_api_server`.
+
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Json};
+use axum::routing::get;
+use axum::Router;
+use once_cell::sync::Lazy;
+use rusqlite::params;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager};
+
+use crate::log_store::DbConnection;
+
+const API_TOKEN_SECRET: &str = "local-api-token";
+const DEFAULT_PORT: u16 = 14310;
+
+/// Running server: bound port plus the shutdown handle
+static SERVER: Lazy<Mutex<Option<(u16, tokio::sync::oneshot::Sender<()>)>>> =
+    Lazy::new(|| Mutex::new(None));
+
+/// Bearer token all requests must present
+static API_TOKEN: Lazy<Mutex<Option<String>>> = Lazy::new(|| Mutex::new(None));
+
+/// The API token, generating and persisting one on first use
+fn ensure_api_token() -> Result<String, String> {
+    if let Some(token) = crate::secure_store::read_secret(API_TOKEN_SECRET)? {
+        return Ok(token);
+    }
+
+    // Random 32-byte hex token from the OS RNG (via aes-gcm's rand_core)
+    use aes_gcm::aead::{rand_core::RngCore, OsRng};
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+    let token = hex::encode(bytes);
+
+    crate::secure_store::store_secret(API_TOKEN_SECRET, &token)?;
+    Ok(token)
+}
+
+fn authorized(headers: &axum::http::HeaderMap) -> bool {
+    let expected = match API_TOKEN.lock().unwrap().clone() {
+        Some(token) => token,
+        None => return false,
+    };
+
+    headers
+        .get("authorization")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .map(|token| token == expected)
+        .unwrap_or(false)
+}
+
+async fn auth_middleware(
+    request: axum::extract::Request,
+    next: axum::middleware::Next,
+) -> axum::response::Response {
+    if !authorized(request.headers()) {
+        return (StatusCode::UNAUTHORIZED, "invalid or missing token").into_response();
+    }
+    next.run(request).await
+}
+
+fn db_error(e: impl std::fmt::Display) -> (StatusCode, String) {
+    (StatusCode::INTERNAL_SERVER_ERROR, format!("{}", e))
+}
+
+/// GET /api/status — app, connectivity, and per-deployment network status
+async fn status_handler(State(app): State<AppHandle>) -> Json<serde_json::Value> {
+    Json(serde_json::json!({
+        "version": app.package_info().version.to_string(),
+        "online": crate::is_online(),
+        "network": crate::get_network_status(),
+        "deployments": crate::deployments::list_deployments(),
+    }))
+}
+
+/// GET /api/logs — recent logs with simple filters
+async fn logs_handler(
+    State(app): State<AppHandle>,
+    Query(query): Query<HashMap<String, String>>,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    let db = app.state::<DbConnection>();
+    let conn = db.lock().map_err(db_error)?;
+
+    let limit: i64 = query
+        .get("limit")
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(100)
+        .min(1000);
+    let since: i64 = query
+        .get("since_ms")
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(0);
+
+    // Params are pushed in lockstep with the placeholders; rusqlite rejects
+    // any mismatch between the two counts
+    let mut sql = String::from(
+        "SELECT ts, deployment, level, function_path, success, duration_ms, message
+         FROM logs WHERE ts >= ?",
+    );
+    let mut params_vec: Vec<Box<dyn rusqlite::ToSql>> = vec![Box::new(since)];
+    if let Some(deployment) = query.get("deployment") {
+        sql.push_str(" AND deployment = ?");
+        params_vec.push(Box::new(deployment.clone()));
+    }
+    if let Some(level) = query.get("level") {
+        sql.push_str(" AND level = ?");
+        params_vec.push(Box::new(level.clone()));
+    }
+    sql.push_str(" ORDER BY ts DESC LIMIT ?");
+    params_vec.push(Box::new(limit));
+
+    let mut stmt = conn.prepare(&sql).map_err(db_error)?;
+    let params_refs: Vec<&dyn rusqlite::ToSql> = params_vec.iter().map(|b| b.as_ref()).collect();
+    let rows = stmt
+        .query_map(params_refs.as_slice(), |row| {
+            Ok(serde_json::json!({
+                "ts": row.get::<_, i64>(0)?,
+                "deployment": row.get::<_, String>(1)?,
+                "level": row.get::<_, Option<String>>(2)?,
+                "functionPath": row.get::<_, Option<String>>(3)?,
+                "success": row.get::<_, Option<bool>>(4)?,
+                "durationMs": row.get::<_, Option<i64>>(5)?,
+                "message": row.get::<_, String>(6)?,
+            }))
+        })
+        .map_err(db_error)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(db_error)?;
+
+    Ok(Json(serde_json::json!({ "logs": rows })))
+}
+
+/// GET /api/functions — per-function metric summaries over a range
+async fn functions_handler(
+    State(app): State<AppHandle>,
+    Query(query): Query<HashMap<String, String>>,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    let deployment = query
+        .get("deployment")
+        .cloned()
+        .ok_or((StatusCode::BAD_REQUEST, "deployment is required".to_string()))?;
+
+    let now = chrono::Utc::now().timestamp_millis();
+    let start: i64 = query
+        .get("start_ms")
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(now - 60 * 60 * 1000);
+    let end: i64 = query.get("end_ms").and_then(|v| v.parse().ok()).unwrap_or(now);
+
+    let db = app.state::<DbConnection>();
+    let conn = db.lock().map_err(db_error)?;
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT function_path, SUM(invocations), SUM(errors),
+                    SUM(total_duration_ms), MAX(max_duration_ms)
+             FROM function_metrics
+             WHERE deployment = ?1 AND minute_ts >= ?2 AND minute_ts <= ?3
+             GROUP BY function_path ORDER BY SUM(invocations) DESC LIMIT 200",
+        )
+        .map_err(db_error)?;
+
+    let rows = stmt
+        .query_map(params![deployment, start, end], |row| {
+            let invocations: i64 = row.get(1)?;
+            let total_duration: i64 = row.get(3)?;
+            Ok(serde_json::json!({
+                "functionPath": row.get::<_, String>(0)?,
+                "invocations": invocations,
+                "errors": row.get::<_, i64>(2)?,
+                "avgDurationMs": if invocations > 0 {
+                    total_duration as f64 / invocations as f64
+                } else {
+                    0.0
+                },
+                "maxDurationMs": row.get::<_, i64>(4)?,
+            }))
+        })
+        .map_err(db_error)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(db_error)?;
+
+    Ok(Json(serde_json::json!({ "functions": rows })))
+}
+
+/// GET /api/network/history — connection-quality samples
+async fn network_history_handler(
+    State(app): State<AppHandle>,
+    Query(query): Query<HashMap<String, String>>,
+) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
+    let range_ms: i64 = query
+        .get("range_ms")
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(24 * 60 * 60 * 1000);
+    let cutoff = chrono::Utc::now().timestamp_millis() - range_ms;
+
+    let db = app.state::<DbConnection>();
+    let conn = db.lock().map_err(db_error)?;
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT ts, check_name, success, latency_ms FROM network_history
+             WHERE ts >= ?1 ORDER BY ts ASC",
+        )
+        .map_err(db_error)?;
+
+    let rows = stmt
+        .query_map(params![cutoff], |row| {
+            Ok(serde_json::json!({
+                "ts": row.get::<_, i64>(0)?,
+                "checkName": row.get::<_, String>(1)?,
+                "success": row.get::<_, bool>(2)?,
+                "latencyMs": row.get::<_, Option<i64>>(3)?,
+            }))
+        })
+        .map_err(db_error)?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(db_error)?;
+
+    Ok(Json(serde_json::json!({ "samples": rows })))
+}
+
+fn router(app: AppHandle) -> Router {
+    Router::new()
+        .route("/api/status", get(status_handler))
+        .route("/api/logs", get(logs_handler))
+        .route("/api/functions", get(functions_handler))
+        .route("/api/network/history", get(network_history_handler))
+        .layer(axum::middleware::from_fn(auth_middleware))
+        .with_state(app)
+}
+
+/// Start the local API server on localhost. Returns the bound port; the
+/// token to use as a Bearer header comes from `get_api_token`.
+#[tauri::command]
+pub async fn start_api_server(app: AppHandle, port: Option<u16>) -> Result<u16, String> {
+    {
+        let server = SERVER.lock().unwrap();
+        if let Some((port, _)) = server.as_ref() {
+            return Ok(*port);
+        }
+    }
+
+    let token = ensure_api_token()?;
+    *API_TOKEN.lock().unwrap() = Some(token);
+
+    let port = port.unwrap_or(DEFAULT_PORT);
+    let listener = tokio::net::TcpListener::bind(("127.0.0.1", port))
+        .await
+        .map_err(|e| format!("Failed to bind API server: {}", e))?;
+    let bound_port = listener
+        .local_addr()
+        .map_err(|e| format!("Failed to bind API server: {}", e))?
+        .port();
+
+    let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel();
+    *SERVER.lock().unwrap() = Some((bound_port, shutdown_tx));
+
+    let service = router(app);
+    tauri::async_runtime::spawn(async move {
+        let serve = axum::serve(listener, service).with_graceful_shutdown(async {
+            let _ = shutdown_rx.await;
+        });
+        if let Err(e) = serve.await {
+            eprintln!("[api_server] Server error: {}", e);
+        }
+        SERVER.lock().unwrap().take();
+    });
+
+    Ok(bound_port)
+}
+
+/// Stop the local API server
+#[tauri::command]
+pub fn stop_api_server() -> Result<bool, String> {
+    match SERVER.lock().unwrap().take() {
+        Some((_, shutdown_tx)) => {
+            let _ = shutdown_tx.send(());
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+/// The bound port when the API server is running
+#[tauri::command]
+pub fn get_api_server_status() -> Option<u16> {
+    SERVER.lock().unwrap().as_ref().map(|(port, _)| *port)
+}
+
+/// The Bearer token clients must send, generating one on first use
+#[tauri::command]
+pub async fn get_api_token() -> Result<String, String> {
+    tauri::async_runtime::spawn_blocking(ensure_api_token)
+        .await
+        .map_err(|e| format!("Task failed: {}", e))?
+}
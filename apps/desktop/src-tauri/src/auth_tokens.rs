@@ -0,0 +1,219 @@
+//! Native OAuth token lifecycle
+//!
+//! Exchanges authorization codes for tokens and manages the refresh-token
+//! lifecycle entirely in Rust. Refresh tokens live in the secure store and
+//! access tokens are refreshed in the background shortly before expiry, so
+//! tokens never transit the webview or local storage.
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+use crate::secure_store;
+
+/// Secure-store key holding the current refresh token
+const REFRESH_TOKEN_KEY: &str = "oauth-refresh-token";
+
+/// Refresh this many seconds before the access token expires
+const REFRESH_MARGIN_SECS: u64 = 60;
+
+/// Parameters of the active refresh loop, if one is running
+static REFRESH_CONTEXT: Lazy<Mutex<Option<RefreshContext>>> = Lazy::new(|| Mutex::new(None));
+
+#[derive(Clone)]
+struct RefreshContext {
+    token_endpoint: String,
+    client_id: String,
+    /// Incremented to invalidate stale refresh loops after sign-out/re-login
+    generation: u64,
+}
+
+/// Token payload emitted to the frontend. The refresh token is deliberately
+/// omitted - it never leaves the Rust side.
+#[derive(Clone, Serialize)]
+pub struct AuthTokens {
+    pub access_token: String,
+    pub expires_in: u64,
+    pub token_type: String,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    access_token: String,
+    refresh_token: Option<String>,
+    expires_in: Option<u64>,
+    token_type: Option<String>,
+}
+
+fn http_client() -> Result<reqwest::Client, String> {
+    reqwest::Client::builder()
+        .connect_timeout(Duration::from_secs(10))
+        .timeout(Duration::from_secs(15))
+        .build()
+        .map_err(|e| format!("Failed to create HTTP client: {e}"))
+}
+
+async fn request_tokens(
+    token_endpoint: &str,
+    form: &[(&str, &str)],
+) -> Result<TokenResponse, String> {
+    let client = http_client()?;
+    let response = client
+        .post(token_endpoint)
+        .form(form)
+        .send()
+        .await
+        .map_err(|e| format!("Token request failed: {e}"))?;
+
+    if !response.status().is_success() {
+        let status = response.status().as_u16();
+        let text = response
+            .text()
+            .await
+            .unwrap_or_else(|_| "Unknown error".to_string());
+        return Err(format!("Token request failed: {} {}", status, text));
+    }
+
+    response
+        .json::<TokenResponse>()
+        .await
+        .map_err(|e| format!("Failed to parse token response: {e}"))
+}
+
+/// Persist the rotated refresh token and schedule the next refresh
+fn handle_token_response(
+    app_handle: &AppHandle,
+    token_endpoint: &str,
+    client_id: &str,
+    response: TokenResponse,
+) -> Result<AuthTokens, String> {
+    if let Some(ref refresh_token) = response.refresh_token {
+        secure_store::store_secret(REFRESH_TOKEN_KEY, refresh_token)?;
+    }
+
+    let expires_in = response.expires_in.unwrap_or(3600);
+    let tokens = AuthTokens {
+        access_token: response.access_token,
+        expires_in,
+        token_type: response.token_type.unwrap_or_else(|| "Bearer".to_string()),
+    };
+
+    let generation = {
+        let mut context = REFRESH_CONTEXT.lock().unwrap();
+        let generation = context.as_ref().map(|c| c.generation + 1).unwrap_or(1);
+        *context = Some(RefreshContext {
+            token_endpoint: token_endpoint.to_string(),
+            client_id: client_id.to_string(),
+            generation,
+        });
+        generation
+    };
+
+    schedule_refresh(app_handle.clone(), generation, expires_in);
+
+    Ok(tokens)
+}
+
+/// Sleep until shortly before expiry, then refresh and emit the new tokens
+fn schedule_refresh(app_handle: AppHandle, generation: u64, expires_in: u64) {
+    let delay = expires_in.saturating_sub(REFRESH_MARGIN_SECS).max(5);
+
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(Duration::from_secs(delay)).await;
+
+        let context = {
+            let guard = REFRESH_CONTEXT.lock().unwrap();
+            match guard.as_ref() {
+                // A newer login or a sign-out superseded this loop
+                Some(c) if c.generation == generation => c.clone(),
+                _ => return,
+            }
+        };
+
+        match refresh_access_token(&app_handle, &context).await {
+            Ok(tokens) => {
+                let _ = app_handle.emit("auth-token-refreshed", tokens);
+            }
+            Err(e) => {
+                eprintln!("[auth_tokens] Token refresh failed: {}", e);
+                let _ = app_handle.emit("auth-token-refresh-failed", e);
+            }
+        }
+    });
+}
+
+async fn refresh_access_token(
+    app_handle: &AppHandle,
+    context: &RefreshContext,
+) -> Result<AuthTokens, String> {
+    let refresh_token = secure_store::read_secret(REFRESH_TOKEN_KEY)?
+        .ok_or_else(|| "No refresh token stored".to_string())?;
+
+    let response = request_tokens(
+        &context.token_endpoint,
+        &[
+            ("grant_type", "refresh_token"),
+            ("client_id", &context.client_id),
+            ("refresh_token", &refresh_token),
+        ],
+    )
+    .await?;
+
+    handle_token_response(app_handle, &context.token_endpoint, &context.client_id, response)
+}
+
+/// Exchange an authorization code for tokens and start the refresh lifecycle
+#[tauri::command]
+pub async fn auth_exchange_code(
+    app_handle: AppHandle,
+    token_endpoint: String,
+    client_id: String,
+    code: String,
+    redirect_uri: String,
+) -> Result<AuthTokens, String> {
+    let response = request_tokens(
+        &token_endpoint,
+        &[
+            ("grant_type", "authorization_code"),
+            ("client_id", &client_id),
+            ("code", &code),
+            ("redirect_uri", &redirect_uri),
+        ],
+    )
+    .await?;
+
+    handle_token_response(&app_handle, &token_endpoint, &client_id, response)
+}
+
+/// Refresh immediately using the stored refresh token (e.g. on app launch)
+#[tauri::command]
+pub async fn auth_refresh_now(
+    app_handle: AppHandle,
+    token_endpoint: String,
+    client_id: String,
+) -> Result<AuthTokens, String> {
+    let context = RefreshContext {
+        token_endpoint,
+        client_id,
+        generation: 0,
+    };
+
+    let tokens = refresh_access_token(&app_handle, &context).await?;
+    let _ = app_handle.emit("auth-token-refreshed", tokens.clone());
+    Ok(tokens)
+}
+
+/// Drop the stored refresh token and cancel any pending refresh
+#[tauri::command]
+pub async fn auth_sign_out() -> Result<(), String> {
+    {
+        let mut context = REFRESH_CONTEXT.lock().unwrap();
+        *context = None;
+    }
+
+    tauri::async_runtime::spawn_blocking(|| secure_store::remove_secret(REFRESH_TOKEN_KEY))
+        .await
+        .map_err(|e| format!("Task failed: {}", e))?
+}
@@ -0,0 +1,98 @@
+//! Background job supervisor
+//!
+//! A registry the long-running background loops (log streaming, metrics
+//! rollups, cron monitoring, network tests) report into, so they stop being
+//! invisible: each job has an enable flag its loop consults, and records
+//! its last run and last error for `get_background_jobs`. Pausing a job
+//! flips the flag — the loop skips its work until resumed.
+
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+/// Supervised state of one background job
+#[derive(Debug, Clone, Serialize)]
+pub struct BackgroundJob {
+    pub name: String,
+    pub enabled: bool,
+    /// Whether the job's loop is currently alive (not the same as enabled —
+    /// a paused job's loop keeps ticking, it just skips the work)
+    pub running: bool,
+    pub last_run_ms: Option<i64>,
+    pub last_error: Option<String>,
+    pub last_error_ms: Option<i64>,
+}
+
+static JOBS: Lazy<Mutex<HashMap<String, BackgroundJob>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Global suspension, separate from per-job flags so the power monitor can
+/// pause everything without clobbering the user's choices
+static SUSPENDED: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+/// Suspend or resume all background work at once (sleep, battery saver)
+pub fn set_suspended(suspended: bool) {
+    SUSPENDED.store(suspended, std::sync::atomic::Ordering::Relaxed);
+}
+
+pub fn suspended() -> bool {
+    SUSPENDED.load(std::sync::atomic::Ordering::Relaxed)
+}
+
+fn with_job<R>(name: &str, f: impl FnOnce(&mut BackgroundJob) -> R) -> R {
+    let mut jobs = JOBS.lock().unwrap();
+    let job = jobs
+        .entry(name.to_string())
+        .or_insert_with(|| BackgroundJob {
+            name: name.to_string(),
+            enabled: true,
+            running: false,
+            last_run_ms: None,
+            last_error: None,
+            last_error_ms: None,
+        });
+    f(job)
+}
+
+/// Whether a job's loop should do its work this tick. Registers the job on
+/// first call so even never-run jobs show up in the overview.
+pub fn should_run(name: &str) -> bool {
+    with_job(name, |job| job.enabled) && !suspended()
+}
+
+/// Mark a job's loop as alive or exited
+pub fn set_running(name: &str, running: bool) {
+    with_job(name, |job| job.running = running);
+}
+
+/// Record the outcome of one iteration of a job's work
+pub fn report(name: &str, result: &Result<(), String>) {
+    let now = chrono::Utc::now().timestamp_millis();
+    with_job(name, |job| match result {
+        Ok(()) => {
+            job.last_run_ms = Some(now);
+            job.last_error = None;
+        }
+        Err(e) => {
+            job.last_error = Some(e.clone());
+            job.last_error_ms = Some(now);
+        }
+    });
+}
+
+/// Supervised jobs sorted by name, for the background jobs view
+#[tauri::command]
+pub fn get_background_jobs() -> Vec<BackgroundJob> {
+    let jobs = JOBS.lock().unwrap();
+    let mut list: Vec<BackgroundJob> = jobs.values().cloned().collect();
+    list.sort_by(|a, b| a.name.cmp(&b.name));
+    list
+}
+
+/// Pause (false) or resume (true) a job. Unknown names are registered so a
+/// job can be pre-paused before its loop first starts.
+#[tauri::command]
+pub fn set_background_job_enabled(name: String, enabled: bool) {
+    with_job(&name, |job| job.enabled = enabled);
+}
@@ -0,0 +1,232 @@
+//! Function benchmarking
+//!
+//! Drives a deployment function repeatedly through the Convex client with
+//! configurable concurrency, collecting a latency distribution and error
+//! counts, with live progress events and a stored history so before/after
+//! optimization runs can be compared.
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::convex_client::ConvexClient;
+
+const HISTORY_FILE: &str = "benchmarks.json";
+const MAX_HISTORY: usize = 100;
+
+/// One completed benchmark run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchmarkResult {
+    pub id: String,
+    pub deployment: String,
+    pub function_path: String,
+    pub udf_type: String,
+    pub concurrency: usize,
+    pub iterations: usize,
+    pub started_ms: i64,
+    pub wall_time_ms: i64,
+    pub successes: usize,
+    pub errors: usize,
+    pub avg_ms: f64,
+    pub min_ms: f64,
+    pub max_ms: f64,
+    pub p50_ms: f64,
+    pub p90_ms: f64,
+    pub p99_ms: f64,
+    pub per_second: f64,
+}
+
+fn history_path() -> Result<PathBuf, String> {
+    let home = std::env::var("HOME")
+        .or_else(|_| std::env::var("USERPROFILE"))
+        .map_err(|_| "Failed to get home directory")?;
+
+    let app_data = PathBuf::from(home).join(".convex-panel");
+    std::fs::create_dir_all(&app_data)
+        .map_err(|e| format!("Failed to create app data directory: {}", e))?;
+
+    Ok(app_data.join(HISTORY_FILE))
+}
+
+fn load_history() -> Vec<BenchmarkResult> {
+    history_path()
+        .ok()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_history(history: &[BenchmarkResult]) -> Result<(), String> {
+    let path = history_path()?;
+    let json = serde_json::to_string_pretty(history)
+        .map_err(|e| format!("Failed to serialize benchmark history: {}", e))?;
+    std::fs::write(&path, json).map_err(|e| format!("Failed to write benchmark history: {}", e))
+}
+
+fn percentile(sorted: &[f64], fraction: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let index = ((sorted.len() - 1) as f64 * fraction).round() as usize;
+    sorted[index.min(sorted.len() - 1)]
+}
+
+fn emit_progress(app: &AppHandle, function_path: &str, completed: usize, total: usize) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.emit(
+            "benchmark-progress",
+            serde_json::json!({
+                "function": function_path,
+                "completed": completed,
+                "total": total,
+            }),
+        );
+    }
+}
+
+/// Run a benchmark: `concurrency` workers share `iterations` total calls.
+/// Mutations and actions write to the deployment — the frontend warns
+/// before running those.
+#[tauri::command]
+pub async fn benchmark_function(
+    app: AppHandle,
+    deployment_url: String,
+    function_path: String,
+    args: serde_json::Value,
+    udf_type: Option<String>,
+    concurrency: Option<usize>,
+    iterations: Option<usize>,
+    admin_key: Option<String>,
+) -> Result<BenchmarkResult, String> {
+    let udf_type = udf_type.unwrap_or_else(|| "query".to_string());
+    if !matches!(udf_type.as_str(), "query" | "mutation" | "action") {
+        return Err(format!("Unknown function type: {}", udf_type));
+    }
+    let concurrency = concurrency.unwrap_or(4).clamp(1, 64);
+    let iterations = iterations.unwrap_or(100).clamp(1, 10_000);
+
+    let deployment_url = deployment_url.trim_end_matches('/').to_string();
+    let client = Arc::new(ConvexClient::for_deployment(&deployment_url, admin_key)?);
+
+    let next = Arc::new(AtomicUsize::new(0));
+    let completed = Arc::new(AtomicUsize::new(0));
+    let latencies = Arc::new(Mutex::new(Vec::with_capacity(iterations)));
+    let errors = Arc::new(AtomicUsize::new(0));
+
+    let started_ms = chrono::Utc::now().timestamp_millis();
+    let start = std::time::Instant::now();
+
+    let mut workers = Vec::with_capacity(concurrency);
+    for _ in 0..concurrency {
+        let client = client.clone();
+        let next = next.clone();
+        let completed = completed.clone();
+        let latencies = latencies.clone();
+        let errors = errors.clone();
+        let app = app.clone();
+        let function_path = function_path.clone();
+        let udf_type = udf_type.clone();
+        let args = args.clone();
+
+        workers.push(tauri::async_runtime::spawn(async move {
+            while next.fetch_add(1, Ordering::Relaxed) < iterations {
+                let call_start = std::time::Instant::now();
+                let ok = match client
+                    .run_function(&udf_type, &function_path, args.clone())
+                    .await
+                {
+                    Ok(result) => result.success,
+                    Err(_) => false,
+                };
+                let elapsed_ms = call_start.elapsed().as_secs_f64() * 1000.0;
+
+                if ok {
+                    latencies.lock().unwrap().push(elapsed_ms);
+                } else {
+                    errors.fetch_add(1, Ordering::Relaxed);
+                }
+
+                let done = completed.fetch_add(1, Ordering::Relaxed) + 1;
+                // Progress every ~2% keeps the event stream light
+                if done % (iterations / 50).max(1) == 0 || done == iterations {
+                    emit_progress(&app, &function_path, done, iterations);
+                }
+            }
+        }));
+    }
+    for worker in workers {
+        let _ = worker.await;
+    }
+
+    let wall_time_ms = start.elapsed().as_millis() as i64;
+    let mut latencies = Arc::try_unwrap(latencies)
+        .map_err(|_| "Benchmark worker leaked")?
+        .into_inner()
+        .map_err(|e| format!("Lock error: {}", e))?;
+    latencies.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+    let successes = latencies.len();
+    let errors = errors.load(Ordering::Relaxed);
+    let avg_ms = if successes > 0 {
+        latencies.iter().sum::<f64>() / successes as f64
+    } else {
+        0.0
+    };
+
+    let result = BenchmarkResult {
+        id: format!("{}-{}", function_path.replace([':', '/'], "-"), started_ms),
+        deployment: deployment_url,
+        function_path,
+        udf_type,
+        concurrency,
+        iterations,
+        started_ms,
+        wall_time_ms,
+        successes,
+        errors,
+        avg_ms,
+        min_ms: latencies.first().copied().unwrap_or(0.0),
+        max_ms: latencies.last().copied().unwrap_or(0.0),
+        p50_ms: percentile(&latencies, 0.50),
+        p90_ms: percentile(&latencies, 0.90),
+        p99_ms: percentile(&latencies, 0.99),
+        per_second: if wall_time_ms > 0 {
+            (successes + errors) as f64 / (wall_time_ms as f64 / 1000.0)
+        } else {
+            0.0
+        },
+    };
+
+    let mut history = load_history();
+    history.insert(0, result.clone());
+    history.truncate(MAX_HISTORY);
+    save_history(&history)?;
+
+    Ok(result)
+}
+
+/// Stored benchmark runs, newest first, optionally for one function
+#[tauri::command]
+pub fn get_benchmark_history(function_path: Option<String>) -> Vec<BenchmarkResult> {
+    let mut history = load_history();
+    if let Some(function_path) = function_path {
+        history.retain(|result| result.function_path == function_path);
+    }
+    history
+}
+
+/// Remove one stored run
+#[tauri::command]
+pub fn delete_benchmark_result(id: String) -> Result<bool, String> {
+    let mut history = load_history();
+    let before = history.len();
+    history.retain(|result| result.id != id);
+
+    if history.len() == before {
+        return Ok(false);
+    }
+    save_history(&history)?;
+    Ok(true)
+}
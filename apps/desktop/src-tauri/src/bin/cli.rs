@@ -0,0 +1,316 @@
+//! Companion CLI for the local log store
+//!
+//! Reads the same SQLite database the desktop app maintains, so captured
+//! logs can be queried from the terminal or CI without the UI:
+//!
+//!     convex-panel-cli logs query [--deployment URL] [--level LEVEL] [--limit N]
+//!     convex-panel-cli logs search <term> [--limit N]
+//!     convex-panel-cli logs export <path> [--deployment URL]
+//!     convex-panel-cli stats
+
+use std::io::Write;
+use std::path::PathBuf;
+
+use convex_panel_desktop_lib::log_store;
+
+const APP_IDENTIFIER: &str = "dev.convexpanel.desktop";
+
+fn usage() -> ! {
+    eprintln!(
+        "usage: convex-panel-cli <command>\n\n\
+         commands:\n  \
+         logs query [--deployment URL] [--level LEVEL] [--limit N] [--since-ms TS]\n  \
+         logs search <term> [--limit N]\n  \
+         logs export <path> [--deployment URL]\n  \
+         stats\n\n\
+         options:\n  \
+         --db <path>    use an explicit database file"
+    );
+    std::process::exit(2);
+}
+
+/// The desktop app's database path for this platform
+fn default_db_path() -> Result<PathBuf, String> {
+    let home = std::env::var("HOME")
+        .or_else(|_| std::env::var("USERPROFILE"))
+        .map_err(|_| "Failed to get home directory")?;
+    let home = PathBuf::from(home);
+
+    let app_data = if cfg!(target_os = "macos") {
+        home.join("Library").join("Application Support").join(APP_IDENTIFIER)
+    } else if cfg!(target_os = "windows") {
+        match std::env::var("APPDATA") {
+            Ok(appdata) => PathBuf::from(appdata).join(APP_IDENTIFIER),
+            Err(_) => home.join("AppData").join("Roaming").join(APP_IDENTIFIER),
+        }
+    } else {
+        match std::env::var("XDG_DATA_HOME") {
+            Ok(data) => PathBuf::from(data).join(APP_IDENTIFIER),
+            Err(_) => home.join(".local").join("share").join(APP_IDENTIFIER),
+        }
+    };
+
+    Ok(app_data.join("convex-logs.db"))
+}
+
+/// Value of a `--flag value` option, removing both from the argument list
+fn take_option(args: &mut Vec<String>, flag: &str) -> Option<String> {
+    let index = args.iter().position(|arg| arg == flag)?;
+    if index + 1 >= args.len() {
+        eprintln!("{} requires a value", flag);
+        std::process::exit(2);
+    }
+    args.remove(index);
+    Some(args.remove(index))
+}
+
+fn open_db(args: &mut Vec<String>) -> log_store::DbConnection {
+    let path = match take_option(args, "--db") {
+        Some(path) => PathBuf::from(path),
+        None => match default_db_path() {
+            Ok(path) => path,
+            Err(e) => {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            }
+        },
+    };
+
+    if !path.exists() {
+        eprintln!("No log database at {} (has the app run?)", path.display());
+        std::process::exit(1);
+    }
+
+    match log_store::open_db_at(&path) {
+        Ok(conn) => conn,
+        Err(e) => {
+            eprintln!("Failed to open {}: {}", path.display(), e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Unwrap a result or print the error and exit non-zero; queries against a
+/// user-supplied term or a busy DB are expected to fail sometimes and
+/// should not panic with a backtrace
+fn unwrap_or_exit<T, E: std::fmt::Display>(result: Result<T, E>, context: &str) -> T {
+    match result {
+        Ok(value) => value,
+        Err(e) => {
+            eprintln!("{}: {}", context, e);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn print_log_row(ts: i64, level: Option<String>, function_path: Option<String>, message: &str) {
+    let when = chrono::DateTime::from_timestamp_millis(ts)
+        .map(|dt| dt.format("%Y-%m-%d %H:%M:%S%.3f").to_string())
+        .unwrap_or_else(|| ts.to_string());
+    println!(
+        "{}  {:5}  {}  {}",
+        when,
+        level.unwrap_or_default(),
+        function_path.unwrap_or_default(),
+        message
+    );
+}
+
+fn cmd_logs_query(mut args: Vec<String>) {
+    let deployment = take_option(&mut args, "--deployment");
+    let level = take_option(&mut args, "--level");
+    let limit: i64 = take_option(&mut args, "--limit")
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(100);
+    let since: i64 = take_option(&mut args, "--since-ms")
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(0);
+
+    let db = open_db(&mut args);
+    let conn = db.lock().unwrap();
+
+    // Placeholders and params are pushed together; rusqlite rejects any
+    // mismatch between the two counts
+    let mut sql = String::from(
+        "SELECT ts, level, function_path, message FROM logs WHERE ts >= ?",
+    );
+    let mut params_vec: Vec<Box<dyn rusqlite::ToSql>> = vec![Box::new(since)];
+    if let Some(deployment) = deployment {
+        sql.push_str(" AND deployment = ?");
+        params_vec.push(Box::new(deployment));
+    }
+    if let Some(level) = level {
+        sql.push_str(" AND level = ?");
+        params_vec.push(Box::new(level));
+    }
+    sql.push_str(" ORDER BY ts DESC LIMIT ?");
+    params_vec.push(Box::new(limit));
+
+    let mut stmt = unwrap_or_exit(conn.prepare(&sql), "Query failed");
+    let params_refs: Vec<&dyn rusqlite::ToSql> = params_vec.iter().map(|b| b.as_ref()).collect();
+    let rows = unwrap_or_exit(
+        stmt.query_map(params_refs.as_slice(), |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, Option<String>>(1)?,
+                row.get::<_, Option<String>>(2)?,
+                row.get::<_, String>(3)?,
+            ))
+        }),
+        "Query failed",
+    );
+
+    // Newest come back first; print oldest first like a terminal tail
+    let mut logs: Vec<_> = unwrap_or_exit(rows.collect::<Result<_, _>>(), "Query failed");
+    logs.reverse();
+    for (ts, level, function_path, message) in logs {
+        print_log_row(ts, level, function_path, &message);
+    }
+}
+
+fn cmd_logs_search(mut args: Vec<String>) {
+    let limit: i64 = take_option(&mut args, "--limit")
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(100);
+    let db = open_db(&mut args);
+
+    let term = match args.first() {
+        Some(term) => term.clone(),
+        None => usage(),
+    };
+
+    let conn = db.lock().unwrap();
+    let mut stmt = unwrap_or_exit(
+        conn.prepare(
+            "SELECT l.ts, l.level, l.function_path, l.message
+             FROM logs_fts f JOIN logs l ON l.rowid = f.rowid
+             WHERE logs_fts MATCH ?1
+             ORDER BY l.ts DESC LIMIT ?2",
+        ),
+        "Query failed",
+    );
+
+    let rows = unwrap_or_exit(
+        stmt.query_map(rusqlite::params![term, limit], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, Option<String>>(1)?,
+                row.get::<_, Option<String>>(2)?,
+                row.get::<_, String>(3)?,
+            ))
+        }),
+        "Search failed",
+    );
+
+    // FTS MATCH syntax errors surface at step time, not prepare time, so
+    // collect instead of flattening them away
+    let mut logs: Vec<_> = unwrap_or_exit(rows.collect::<Result<_, _>>(), "Search failed");
+    logs.reverse();
+    for (ts, level, function_path, message) in logs {
+        print_log_row(ts, level, function_path, &message);
+    }
+}
+
+fn cmd_logs_export(mut args: Vec<String>) {
+    let deployment = take_option(&mut args, "--deployment");
+    let db = open_db(&mut args);
+
+    let path = match args.first() {
+        Some(path) => path.clone(),
+        None => usage(),
+    };
+
+    let conn = db.lock().unwrap();
+    let mut sql = String::from("SELECT json_blob FROM logs");
+    if deployment.is_some() {
+        sql.push_str(" WHERE deployment = ?1");
+    }
+    sql.push_str(" ORDER BY ts ASC");
+
+    let mut stmt = unwrap_or_exit(conn.prepare(&sql), "Query failed");
+    let rows = unwrap_or_exit(
+        match &deployment {
+            Some(deployment) => stmt.query_map(rusqlite::params![deployment], |row| {
+                row.get::<_, String>(0)
+            }),
+            None => stmt.query_map([], |row| row.get::<_, String>(0)),
+        },
+        "Query failed",
+    );
+
+    let mut file = std::fs::File::create(&path).unwrap_or_else(|e| {
+        eprintln!("Failed to create {}: {}", path, e);
+        std::process::exit(1);
+    });
+
+    let mut exported = 0u64;
+    for blob in rows {
+        let blob = unwrap_or_exit(blob, "Query failed");
+        unwrap_or_exit(writeln!(file, "{}", blob), "Failed to write export");
+        exported += 1;
+    }
+    eprintln!("Exported {} logs to {}", exported, path);
+}
+
+fn cmd_stats(mut args: Vec<String>) {
+    let db = open_db(&mut args);
+    let conn = db.lock().unwrap();
+
+    let total: i64 = conn
+        .query_row("SELECT COUNT(*) FROM logs", [], |row| row.get(0))
+        .unwrap_or(0);
+    println!("total logs: {}", total);
+
+    let mut stmt = unwrap_or_exit(
+        conn.prepare(
+            "SELECT deployment, COUNT(*), MIN(ts), MAX(ts)
+             FROM logs GROUP BY deployment ORDER BY COUNT(*) DESC",
+        ),
+        "Query failed",
+    );
+    let rows = unwrap_or_exit(
+        stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, i64>(1)?,
+                row.get::<_, i64>(2)?,
+                row.get::<_, i64>(3)?,
+            ))
+        }),
+        "Query failed",
+    );
+
+    let rows: Vec<_> = unwrap_or_exit(rows.collect::<Result<_, _>>(), "Query failed");
+    for (deployment, count, min_ts, max_ts) in rows {
+        let from = chrono::DateTime::from_timestamp_millis(min_ts)
+            .map(|dt| dt.format("%Y-%m-%d").to_string())
+            .unwrap_or_default();
+        let to = chrono::DateTime::from_timestamp_millis(max_ts)
+            .map(|dt| dt.format("%Y-%m-%d").to_string())
+            .unwrap_or_default();
+        println!("{:8}  {}  ({} – {})", count, deployment, from, to);
+    }
+}
+
+fn main() {
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    if args.is_empty() {
+        usage();
+    }
+
+    match args.remove(0).as_str() {
+        "logs" => {
+            if args.is_empty() {
+                usage();
+            }
+            match args.remove(0).as_str() {
+                "query" => cmd_logs_query(args),
+                "search" => cmd_logs_search(args),
+                "export" => cmd_logs_export(args),
+                _ => usage(),
+            }
+        }
+        "stats" => cmd_stats(args),
+        _ => usage(),
+    }
+}
@@ -0,0 +1,181 @@
+//! Capture-and-replay of function calls
+//!
+//! Extracts recorded function invocations (name + args) from the local log
+//! store and replays a selected set against another deployment through the
+//! Convex client, diffing the results — the workflow for checking that a
+//! preview deployment answers the same way prod does.
+
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+use tauri::State;
+
+use crate::convex_client::ConvexClient;
+use crate::log_store::DbConnection;
+
+/// One invocation reconstructed from the log store
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CapturedCall {
+    pub log_id: String,
+    pub ts: i64,
+    pub function_path: String,
+    pub udf_type: String,
+    pub args: serde_json::Value,
+}
+
+/// Result of replaying one call against both deployments
+#[derive(Debug, Clone, Serialize)]
+pub struct ReplayOutcome {
+    pub function_path: String,
+    pub args: serde_json::Value,
+    pub matched: bool,
+    pub source_value: Option<serde_json::Value>,
+    pub target_value: Option<serde_json::Value>,
+    pub source_error: Option<String>,
+    pub target_error: Option<String>,
+}
+
+/// The raw payload fields invocation arguments can hide under
+fn extract_args(blob: &serde_json::Value) -> serde_json::Value {
+    blob.get("args")
+        .or_else(|| blob.get("requestArgs"))
+        .or_else(|| blob.get("udfArgs"))
+        .cloned()
+        .unwrap_or(serde_json::json!({}))
+}
+
+/// Reconstruct recent invocations of deployment functions from the log
+/// store. Only entries with a recorded function path qualify; duplicate
+/// path+args pairs are collapsed to the most recent occurrence.
+#[tauri::command]
+pub async fn capture_function_calls(
+    db: State<'_, DbConnection>,
+    deployment: String,
+    start_ts: i64,
+    end_ts: i64,
+    function_path: Option<String>,
+    limit: Option<i64>,
+) -> Result<Vec<CapturedCall>, String> {
+    let conn = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+
+    let mut sql = String::from(
+        "SELECT id, ts, function_path, udf_type, json_blob FROM logs
+         WHERE deployment = ?1 AND ts >= ?2 AND ts <= ?3
+           AND function_path IS NOT NULL",
+    );
+    if function_path.is_some() {
+        sql.push_str(" AND function_path = ?5");
+    }
+    sql.push_str(" ORDER BY ts DESC LIMIT ?4");
+
+    let mut stmt = conn
+        .prepare(&sql)
+        .map_err(|e| format!("Query error: {}", e))?;
+
+    let map_row = |row: &rusqlite::Row| {
+        let blob: String = row.get(4)?;
+        let blob: serde_json::Value = serde_json::from_str(&blob).unwrap_or_default();
+        Ok(CapturedCall {
+            log_id: row.get(0)?,
+            ts: row.get(1)?,
+            function_path: row.get(2)?,
+            udf_type: row.get::<_, Option<String>>(3)?.unwrap_or_default(),
+            args: extract_args(&blob),
+        })
+    };
+
+    let limit = limit.unwrap_or(100).min(1000);
+    let rows = match &function_path {
+        Some(path) => stmt.query_map(params![deployment, start_ts, end_ts, limit, path], map_row),
+        None => stmt.query_map(params![deployment, start_ts, end_ts, limit], map_row),
+    }
+    .map_err(|e| format!("Query error: {}", e))?;
+
+    let mut calls: Vec<CapturedCall> = rows
+        .collect::<Result<_, _>>()
+        .map_err(|e| format!("Query error: {}", e))?;
+
+    // Most recent occurrence wins; rows are already newest-first
+    let mut seen = std::collections::HashSet::new();
+    calls.retain(|call| seen.insert((call.function_path.clone(), call.args.to_string())));
+
+    Ok(calls)
+}
+
+async fn run_one(
+    client: &ConvexClient,
+    udf_type: &str,
+    call: &CapturedCall,
+) -> (Option<serde_json::Value>, Option<String>) {
+    match client
+        .run_function(udf_type, &call.function_path, call.args.clone())
+        .await
+    {
+        Ok(result) => {
+            if result.success {
+                (result.value, None)
+            } else {
+                (None, result.error_message)
+            }
+        }
+        Err(e) => (None, Some(e)),
+    }
+}
+
+/// Replay captured calls against a source and a target deployment and diff
+/// the results. Only queries are replayed unless `allow_mutations` is set —
+/// replaying mutations writes to the target.
+#[tauri::command]
+pub async fn replay_function_calls(
+    source_url: String,
+    target_url: String,
+    calls: Vec<CapturedCall>,
+    admin_key: Option<String>,
+    allow_mutations: Option<bool>,
+) -> Result<Vec<ReplayOutcome>, String> {
+    let source = ConvexClient::for_deployment(&source_url, admin_key.clone())?;
+    let target = ConvexClient::for_deployment(&target_url, admin_key)?;
+    let allow_mutations = allow_mutations.unwrap_or(false);
+
+    let mut outcomes = Vec::with_capacity(calls.len());
+    for call in &calls {
+        let udf_type = match call.udf_type.to_lowercase().as_str() {
+            "" | "query" => "query",
+            "mutation" if allow_mutations => "mutation",
+            "action" if allow_mutations => "action",
+            other => {
+                outcomes.push(ReplayOutcome {
+                    function_path: call.function_path.clone(),
+                    args: call.args.clone(),
+                    matched: false,
+                    source_value: None,
+                    target_value: None,
+                    source_error: None,
+                    target_error: Some(format!(
+                        "Skipped {}: replaying a {} requires allow_mutations",
+                        call.function_path, other
+                    )),
+                });
+                continue;
+            }
+        };
+
+        let (source_value, source_error) = run_one(&source, udf_type, call).await;
+        let (target_value, target_error) = run_one(&target, udf_type, call).await;
+
+        let matched = source_error.is_none()
+            && target_error.is_none()
+            && source_value == target_value;
+
+        outcomes.push(ReplayOutcome {
+            function_path: call.function_path.clone(),
+            args: call.args.clone(),
+            matched,
+            source_value,
+            target_value,
+            source_error,
+            target_error,
+        });
+    }
+
+    Ok(outcomes)
+}
@@ -0,0 +1,322 @@
+//! Native Convex deployment API client
+//!
+//! A reqwest-based client for running functions and fetching deployment
+//! metadata directly from Rust, so background services don't need to round
+//! trip through the webview. Deploy keys live in `secure_store` under a
+//! per-deployment name and are resolved transparently.
+
+use serde::Serialize;
+use std::collections::HashMap;
+use std::time::Duration;
+
+/// Secure-store secret name holding the deploy key for a deployment URL
+pub fn deploy_key_secret_name(deployment_url: &str) -> String {
+    format!("deploy-key:{}", deployment_url.trim_end_matches('/'))
+}
+
+/// Resolve the admin key for a deployment: an explicit key wins, otherwise
+/// the stored deploy key is used
+pub(crate) fn resolve_admin_key(
+    deployment_url: &str,
+    admin_key: Option<String>,
+) -> Result<String, String> {
+    if let Some(key) = admin_key {
+        return Ok(key);
+    }
+    crate::secure_store::read_secret(&deploy_key_secret_name(deployment_url))?.ok_or_else(|| {
+        format!("No deploy key stored for {}", deployment_url)
+    })
+}
+
+/// Client for one deployment
+pub struct ConvexClient {
+    deployment_url: String,
+    admin_key: String,
+    http: reqwest::Client,
+}
+
+/// One page of documents from `list_documents`
+#[derive(Debug, Clone, Serialize)]
+pub struct DocumentPage {
+    pub documents: Vec<serde_json::Value>,
+    pub cursor: Option<String>,
+    pub has_more: bool,
+}
+
+/// Result of running a query/mutation/action
+#[derive(Debug, Clone, Serialize)]
+pub struct FunctionResult {
+    pub success: bool,
+    pub value: Option<serde_json::Value>,
+    pub error_message: Option<String>,
+    /// Log lines emitted during execution
+    pub log_lines: Vec<String>,
+}
+
+impl ConvexClient {
+    pub fn new(deployment_url: &str, admin_key: String) -> Result<Self, String> {
+        let http = reqwest::Client::builder()
+            .connect_timeout(Duration::from_secs(10))
+            .timeout(Duration::from_secs(60))
+            .build()
+            .map_err(|e| format!("Failed to create HTTP client: {e}"))?;
+
+        Ok(Self {
+            deployment_url: deployment_url.trim_end_matches('/').to_string(),
+            admin_key,
+            http,
+        })
+    }
+
+    /// Client using the stored deploy key when no explicit key is given
+    pub fn for_deployment(
+        deployment_url: &str,
+        admin_key: Option<String>,
+    ) -> Result<Self, String> {
+        let key = resolve_admin_key(deployment_url, admin_key)?;
+        Self::new(deployment_url, key)
+    }
+
+    fn endpoint(&self, path: &str) -> String {
+        format!("{}/{}", self.deployment_url, path.trim_start_matches('/'))
+    }
+
+    async fn get_json(&self, path: &str) -> Result<serde_json::Value, String> {
+        let response = self
+            .http
+            .get(self.endpoint(path))
+            .header("Authorization", format!("Convex {}", self.admin_key))
+            .send()
+            .await
+            .map_err(|e| format!("Failed to reach deployment: {}", e))?;
+
+        if !response.status().is_success() {
+            return Err(format!("Deployment returned {}", response.status()));
+        }
+
+        response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse deployment response: {}", e))
+    }
+
+    /// Run a query, mutation, or action by its path (e.g. "messages:list").
+    /// `kind` is "query", "mutation", or "action".
+    pub async fn run_function(
+        &self,
+        kind: &str,
+        function_path: &str,
+        args: serde_json::Value,
+    ) -> Result<FunctionResult, String> {
+        if !matches!(kind, "query" | "mutation" | "action") {
+            return Err(format!("Unknown function kind: {}", kind));
+        }
+
+        let response = self
+            .http
+            .post(self.endpoint(&format!("api/{}", kind)))
+            .header("Authorization", format!("Convex {}", self.admin_key))
+            .json(&serde_json::json!({
+                "path": function_path,
+                "args": args,
+                "format": "json",
+            }))
+            .send()
+            .await
+            .map_err(|e| format!("Failed to reach deployment: {}", e))?;
+
+        if !response.status().is_success() {
+            return Err(format!("Deployment returned {}", response.status()));
+        }
+
+        let body: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse deployment response: {}", e))?;
+
+        let log_lines = body
+            .get("logLines")
+            .and_then(|v| v.as_array())
+            .map(|lines| {
+                lines
+                    .iter()
+                    .filter_map(|l| l.as_str().map(String::from))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        match body.get("status").and_then(|v| v.as_str()) {
+            Some("success") => Ok(FunctionResult {
+                success: true,
+                value: body.get("value").cloned(),
+                error_message: None,
+                log_lines,
+            }),
+            _ => Ok(FunctionResult {
+                success: false,
+                value: body.get("errorData").cloned(),
+                error_message: body
+                    .get("errorMessage")
+                    .and_then(|v| v.as_str())
+                    .map(String::from),
+                log_lines,
+            }),
+        }
+    }
+
+    /// The deployment's inferred schema (table shapes)
+    pub async fn schema(&self) -> Result<serde_json::Value, String> {
+        self.get_json("api/shapes2").await
+    }
+
+    /// Table names in the deployment, from the shapes endpoint
+    pub async fn list_tables(&self) -> Result<Vec<String>, String> {
+        let shapes = self.schema().await?;
+
+        let mut tables: Vec<String> = shapes
+            .as_object()
+            .map(|obj| obj.keys().cloned().collect())
+            .unwrap_or_default();
+        tables.sort();
+        Ok(tables)
+    }
+
+    /// One page of a table's documents
+    pub async fn list_documents(
+        &self,
+        table: &str,
+        cursor: Option<&str>,
+        limit: usize,
+    ) -> Result<DocumentPage, String> {
+        let mut request = self
+            .http
+            .get(self.endpoint("api/list_snapshot"))
+            .header("Authorization", format!("Convex {}", self.admin_key))
+            .query(&[("tableName", table), ("limit", &limit.to_string())]);
+        if let Some(cursor) = cursor {
+            request = request.query(&[("cursor", cursor)]);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| format!("Failed to reach deployment: {}", e))?;
+
+        if !response.status().is_success() {
+            return Err(format!("Deployment returned {}", response.status()));
+        }
+
+        let body: serde_json::Value = response
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse deployment response: {}", e))?;
+
+        Ok(DocumentPage {
+            documents: body
+                .get("values")
+                .and_then(|v| v.as_array())
+                .cloned()
+                .unwrap_or_default(),
+            cursor: body
+                .get("cursor")
+                .and_then(|v| v.as_str())
+                .map(String::from),
+            has_more: body.get("hasMore").and_then(|v| v.as_bool()).unwrap_or(false),
+        })
+    }
+
+    /// The deployment's environment variables
+    pub async fn environment_variables(&self) -> Result<HashMap<String, String>, String> {
+        let vars = self.get_json("api/environment_variables").await?;
+
+        Ok(vars
+            .as_array()
+            .map(|list| {
+                list.iter()
+                    .filter_map(|v| {
+                        let name = v.get("name")?.as_str()?.to_string();
+                        let value = v.get("value")?.as_str()?.to_string();
+                        Some((name, value))
+                    })
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    /// Deployment metadata: backend version plus the URL the client is bound to
+    pub async fn deployment_info(&self) -> Result<serde_json::Value, String> {
+        let version = self
+            .http
+            .get(self.endpoint("version"))
+            .send()
+            .await
+            .ok();
+
+        let version = match version {
+            Some(response) if response.status().is_success() => {
+                response.text().await.ok().map(|v| v.trim().to_string())
+            }
+            _ => None,
+        };
+
+        Ok(serde_json::json!({
+            "url": self.deployment_url,
+            "backendVersion": version,
+        }))
+    }
+}
+
+/// Store the deploy key for a deployment in the secure store
+#[tauri::command]
+pub async fn store_deploy_key(deployment_url: String, deploy_key: String) -> Result<(), String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        crate::secure_store::store_secret(&deploy_key_secret_name(&deployment_url), &deploy_key)
+    })
+    .await
+    .map_err(|e| format!("Task failed: {}", e))?
+}
+
+/// Whether a deploy key is stored for a deployment
+#[tauri::command]
+pub async fn has_deploy_key(deployment_url: String) -> Result<bool, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        Ok(crate::secure_store::read_secret(&deploy_key_secret_name(&deployment_url))?.is_some())
+    })
+    .await
+    .map_err(|e| format!("Task failed: {}", e))?
+}
+
+/// Run a query/mutation/action against a deployment
+#[tauri::command]
+pub async fn run_convex_function(
+    deployment_url: String,
+    kind: String,
+    function_path: String,
+    args: Option<serde_json::Value>,
+    admin_key: Option<String>,
+) -> Result<FunctionResult, String> {
+    let client = ConvexClient::for_deployment(&deployment_url, admin_key)?;
+    client
+        .run_function(&kind, &function_path, args.unwrap_or(serde_json::json!({})))
+        .await
+}
+
+/// List a deployment's tables
+#[tauri::command]
+pub async fn list_convex_tables(
+    deployment_url: String,
+    admin_key: Option<String>,
+) -> Result<Vec<String>, String> {
+    let client = ConvexClient::for_deployment(&deployment_url, admin_key)?;
+    client.list_tables().await
+}
+
+/// Fetch deployment metadata
+#[tauri::command]
+pub async fn get_deployment_info(
+    deployment_url: String,
+    admin_key: Option<String>,
+) -> Result<serde_json::Value, String> {
+    let client = ConvexClient::for_deployment(&deployment_url, admin_key)?;
+    client.deployment_info().await
+}
@@ -0,0 +1,174 @@
+//! Usage/cost estimator
+//!
+//! Projects monthly function call volume, bandwidth, and storage from the
+//! locally recorded metrics and maps them onto configurable pricing
+//! assumptions. The numbers are estimates from what the panel observed —
+//! payload sizes stand in for bandwidth — not a bill.
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tauri::{AppHandle, State};
+use tauri_plugin_notification::NotificationExt;
+
+use crate::log_store::DbConnection;
+
+const PRICING_FILE: &str = "pricing.json";
+
+/// Pricing assumptions, editable in settings. Defaults approximate typical
+/// usage-based pricing; they are intentionally not tied to any real plan.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PricingAssumptions {
+    pub per_million_calls_usd: f64,
+    pub per_gb_bandwidth_usd: f64,
+    pub per_gb_storage_month_usd: f64,
+    /// Projected monthly cost above this triggers a warning notification
+    pub monthly_budget_usd: f64,
+}
+
+impl Default for PricingAssumptions {
+    fn default() -> Self {
+        Self {
+            per_million_calls_usd: 2.0,
+            per_gb_bandwidth_usd: 0.20,
+            per_gb_storage_month_usd: 0.20,
+            monthly_budget_usd: 25.0,
+        }
+    }
+}
+
+/// Observed usage over the window plus the monthly projection
+#[derive(Debug, Clone, Serialize)]
+pub struct UsageEstimate {
+    pub window_days: f64,
+    pub calls: i64,
+    pub bandwidth_bytes: i64,
+    pub storage_bytes: i64,
+    pub projected_monthly_calls: f64,
+    pub projected_monthly_bandwidth_gb: f64,
+    pub projected_monthly_cost_usd: f64,
+    pub monthly_budget_usd: f64,
+    pub over_budget: bool,
+}
+
+fn pricing_path() -> Result<PathBuf, String> {
+    let home = std::env::var("HOME")
+        .or_else(|_| std::env::var("USERPROFILE"))
+        .map_err(|_| "Failed to get home directory")?;
+
+    let app_data = PathBuf::from(home).join(".convex-panel");
+    std::fs::create_dir_all(&app_data)
+        .map_err(|e| format!("Failed to create app data directory: {}", e))?;
+
+    Ok(app_data.join(PRICING_FILE))
+}
+
+fn load_pricing() -> PricingAssumptions {
+    pricing_path()
+        .ok()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Current pricing assumptions
+#[tauri::command]
+pub fn get_pricing_assumptions() -> PricingAssumptions {
+    load_pricing()
+}
+
+/// Persist new pricing assumptions
+#[tauri::command]
+pub fn set_pricing_assumptions(pricing: PricingAssumptions) -> Result<(), String> {
+    let path = pricing_path()?;
+    let json = serde_json::to_string_pretty(&pricing)
+        .map_err(|e| format!("Failed to serialize pricing: {}", e))?;
+    std::fs::write(&path, json).map_err(|e| format!("Failed to write pricing: {}", e))
+}
+
+/// Estimate usage over the trailing window (default 7 days) and project it
+/// to a month. Warns with a notification when the projection exceeds the
+/// configured budget.
+#[tauri::command]
+pub async fn estimate_usage(
+    app: AppHandle,
+    db: State<'_, DbConnection>,
+    deployment: String,
+    window_days: Option<f64>,
+) -> Result<UsageEstimate, String> {
+    let pricing = load_pricing();
+    let window_days = window_days.unwrap_or(7.0).clamp(0.25, 90.0);
+
+    let now = chrono::Utc::now().timestamp_millis();
+    let start = now - (window_days * 24.0 * 60.0 * 60.0 * 1000.0) as i64;
+
+    let (calls, bandwidth_bytes, storage_bytes) = {
+        let conn = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+
+        let calls: i64 = conn
+            .query_row(
+                "SELECT COALESCE(SUM(invocations), 0) FROM function_metrics
+                 WHERE deployment = ?1 AND minute_ts >= ?2",
+                rusqlite::params![deployment, start],
+                |row| row.get(0),
+            )
+            .map_err(|e| format!("Query error: {}", e))?;
+
+        // Raw payload size is our best local proxy for bandwidth
+        let bandwidth_bytes: i64 = conn
+            .query_row(
+                "SELECT COALESCE(SUM(LENGTH(json_blob)), 0) FROM logs
+                 WHERE deployment = ?1 AND ts >= ?2",
+                rusqlite::params![deployment, start],
+                |row| row.get(0),
+            )
+            .map_err(|e| format!("Query error: {}", e))?;
+
+        // All payloads ever seen approximate stored data volume
+        let storage_bytes: i64 = conn
+            .query_row(
+                "SELECT COALESCE(SUM(LENGTH(json_blob)), 0) FROM logs WHERE deployment = ?1",
+                rusqlite::params![deployment],
+                |row| row.get(0),
+            )
+            .map_err(|e| format!("Query error: {}", e))?;
+
+        (calls, bandwidth_bytes, storage_bytes)
+    };
+
+    const GB: f64 = 1024.0 * 1024.0 * 1024.0;
+    let scale = 30.0 / window_days;
+
+    let projected_monthly_calls = calls as f64 * scale;
+    let projected_monthly_bandwidth_gb = bandwidth_bytes as f64 * scale / GB;
+    let storage_gb = storage_bytes as f64 / GB;
+
+    let projected_monthly_cost_usd = projected_monthly_calls / 1_000_000.0
+        * pricing.per_million_calls_usd
+        + projected_monthly_bandwidth_gb * pricing.per_gb_bandwidth_usd
+        + storage_gb * pricing.per_gb_storage_month_usd;
+
+    let over_budget = projected_monthly_cost_usd > pricing.monthly_budget_usd;
+    if over_budget && !crate::notifications::muted() {
+        let _ = app
+            .notification()
+            .builder()
+            .title("Projected usage over budget")
+            .body(format!(
+                "{} is projected at ${:.2}/month (budget ${:.2})",
+                deployment, projected_monthly_cost_usd, pricing.monthly_budget_usd
+            ))
+            .show();
+    }
+
+    Ok(UsageEstimate {
+        window_days,
+        calls,
+        bandwidth_bytes,
+        storage_bytes,
+        projected_monthly_calls,
+        projected_monthly_bandwidth_gb,
+        projected_monthly_cost_usd,
+        monthly_budget_usd: pricing.monthly_budget_usd,
+        over_budget,
+    })
+}
@@ -0,0 +1,209 @@
+//! Crash reporting and panic capture
+//!
+//! A panic hook writes the panic message, Rust backtrace, the most recent
+//! command invocations, and app/OS metadata into a local crash-report file
+//! under ~/.convex-panel/crash-reports. Nothing leaves the machine unless
+//! `upload_crash_report` is called explicitly.
+
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+const MAX_RECENT_COMMANDS: usize = 50;
+const MAX_CRASH_REPORTS: usize = 25;
+
+/// Ring buffer of recently invoked commands, for crash context
+static RECENT_COMMANDS: Lazy<Mutex<VecDeque<(i64, String)>>> =
+    Lazy::new(|| Mutex::new(VecDeque::with_capacity(MAX_RECENT_COMMANDS)));
+
+/// Crash report metadata as listed to the frontend
+#[derive(Debug, Clone, Serialize)]
+pub struct CrashReportMeta {
+    pub id: String,
+    pub ts: i64,
+    pub path: String,
+    pub message: String,
+}
+
+fn reports_dir() -> Result<PathBuf, String> {
+    let home = std::env::var("HOME")
+        .or_else(|_| std::env::var("USERPROFILE"))
+        .map_err(|_| "Failed to get home directory")?;
+
+    let dir = PathBuf::from(home)
+        .join(".convex-panel")
+        .join("crash-reports");
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create crash reports directory: {}", e))?;
+
+    Ok(dir)
+}
+
+/// Remember a command invocation for crash context. Arguments are not
+/// recorded — they can contain secrets.
+pub fn note_invocation(command: &str) {
+    let mut recent = RECENT_COMMANDS.lock().unwrap();
+    if recent.len() >= MAX_RECENT_COMMANDS {
+        recent.pop_front();
+    }
+    recent.push_back((chrono::Utc::now().timestamp_millis(), command.to_string()));
+}
+
+/// Write one crash report file; used by the panic hook, so it must not panic
+fn write_report(message: &str, location: Option<String>, backtrace: String) {
+    let dir = match reports_dir() {
+        Ok(dir) => dir,
+        Err(_) => return,
+    };
+
+    let ts = chrono::Utc::now().timestamp_millis();
+    let recent: Vec<serde_json::Value> = RECENT_COMMANDS
+        .lock()
+        .map(|recent| {
+            recent
+                .iter()
+                .map(|(ts, command)| serde_json::json!({ "ts": ts, "command": command }))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let report = serde_json::json!({
+        "ts": ts,
+        "message": message,
+        "location": location,
+        "backtrace": backtrace,
+        "recentCommands": recent,
+        "appVersion": env!("CARGO_PKG_VERSION"),
+        "os": std::env::consts::OS,
+        "arch": std::env::consts::ARCH,
+    });
+
+    let path = dir.join(format!("crash-{}.json", ts));
+    if let Ok(json) = serde_json::to_string_pretty(&report) {
+        let _ = std::fs::write(path, json);
+    }
+
+    // Keep only the newest reports
+    if let Ok(mut files) = list_report_files(&dir) {
+        while files.len() > MAX_CRASH_REPORTS {
+            let _ = std::fs::remove_file(files.remove(0));
+        }
+    }
+}
+
+/// Crash report files sorted oldest first (names embed the timestamp)
+fn list_report_files(dir: &std::path::Path) -> Result<Vec<PathBuf>, String> {
+    let mut files: Vec<PathBuf> = std::fs::read_dir(dir)
+        .map_err(|e| format!("Failed to read crash reports: {}", e))?
+        .flatten()
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .map(|name| name.starts_with("crash-") && name.ends_with(".json"))
+                .unwrap_or(false)
+        })
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+/// Install the panic hook. Called once at startup, before anything that can
+/// crash; chains to the previous hook so panics still reach stderr.
+pub fn install_panic_hook() {
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let message = info
+            .payload()
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| info.payload().downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_string());
+        let location = info.location().map(|l| l.to_string());
+        let backtrace = std::backtrace::Backtrace::force_capture().to_string();
+
+        write_report(&message, location, backtrace);
+        previous(info);
+    }));
+}
+
+/// Captured crash reports, newest first
+#[tauri::command]
+pub fn list_crash_reports() -> Result<Vec<CrashReportMeta>, String> {
+    let dir = reports_dir()?;
+    let mut reports: Vec<CrashReportMeta> = list_report_files(&dir)?
+        .into_iter()
+        .filter_map(|path| {
+            let content = std::fs::read_to_string(&path).ok()?;
+            let report: serde_json::Value = serde_json::from_str(&content).ok()?;
+            Some(CrashReportMeta {
+                id: path.file_stem()?.to_str()?.to_string(),
+                ts: report.get("ts")?.as_i64()?,
+                path: path.to_string_lossy().to_string(),
+                message: report
+                    .get("message")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("")
+                    .to_string(),
+            })
+        })
+        .collect();
+    reports.reverse();
+    Ok(reports)
+}
+
+/// Copy a crash report to a chosen path (e.g. to attach to a bug report)
+#[tauri::command]
+pub fn export_crash_report(report_id: String, path: String) -> Result<(), String> {
+    let source = reports_dir()?.join(format!("{}.json", report_id));
+    if !source.exists() {
+        return Err(format!("Crash report {} not found", report_id));
+    }
+    std::fs::copy(&source, &path)
+        .map(|_| ())
+        .map_err(|e| format!("Failed to export crash report: {}", e))
+}
+
+/// Delete one crash report
+#[tauri::command]
+pub fn delete_crash_report(report_id: String) -> Result<bool, String> {
+    let path = reports_dir()?.join(format!("{}.json", report_id));
+    if !path.exists() {
+        return Ok(false);
+    }
+    std::fs::remove_file(&path).map_err(|e| format!("Failed to delete crash report: {}", e))?;
+    Ok(true)
+}
+
+/// Upload a crash report to an endpoint the user explicitly provided.
+/// There is no automatic upload.
+#[tauri::command]
+pub async fn upload_crash_report(report_id: String, endpoint: String) -> Result<(), String> {
+    let path = reports_dir()?.join(format!("{}.json", report_id));
+    let content = std::fs::read_to_string(&path)
+        .map_err(|_| format!("Crash report {} not found", report_id))?;
+    let report: serde_json::Value = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse crash report: {}", e))?;
+
+    let client = reqwest::Client::new();
+    let response = client
+        .post(&endpoint)
+        .json(&report)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to upload crash report: {}", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!("Upload failed: {}", response.status()));
+    }
+    Ok(())
+}
+
+/// Frontend hook: remember that a command ran (called by the invoke wrapper)
+#[tauri::command]
+pub fn note_command_invocation(command: String) {
+    note_invocation(&command);
+    crate::telemetry::count(&format!("command:{}", command));
+}
@@ -0,0 +1,461 @@
+//! Cron and scheduled function monitor
+//!
+//! Polls a deployment's cron definitions and recent runs through the
+//! Convex client, records expected vs actual executions in the `cron_runs`
+//! table, and raises an alert when a cron misses its window.
+
+use once_cell::sync::Lazy;
+use rusqlite::params;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, State};
+use tauri_plugin_notification::NotificationExt;
+
+use crate::convex_client::ConvexClient;
+use crate::log_store::DbConnection;
+
+/// Active monitor generations per deployment URL (same pattern as
+/// log_stream)
+static MONITORS: Lazy<Mutex<HashMap<String, u64>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+const POLL_INTERVAL: Duration = Duration::from_secs(60);
+
+/// How long past its scheduled time a cron may run before counting as missed
+const MISS_GRACE_MS: i64 = 2 * 60 * 1000;
+
+/// One expected/observed cron execution
+#[derive(Debug, Clone, Serialize)]
+pub struct CronRun {
+    pub name: String,
+    pub scheduled_ts: i64,
+    pub actual_ts: Option<i64>,
+    /// "pending", "ok", "failed", or "missed"
+    pub status: String,
+}
+
+/// Fetch cron definitions and pending scheduled jobs for the Schedules view
+#[tauri::command]
+pub async fn get_schedules(
+    deployment_url: String,
+    admin_key: Option<String>,
+) -> Result<serde_json::Value, String> {
+    let client = ConvexClient::for_deployment(&deployment_url, admin_key)?;
+
+    let crons = client
+        .run_function(
+            "query",
+            "_system/frontend/listCronJobs:default",
+            serde_json::json!({}),
+        )
+        .await?;
+    let scheduled = client
+        .run_function(
+            "query",
+            "_system/frontend/paginatedScheduledJobs:default",
+            serde_json::json!({ "paginationOpts": { "numItems": 100, "cursor": null } }),
+        )
+        .await?;
+
+    Ok(serde_json::json!({
+        "crons": crons.value,
+        "scheduledJobs": scheduled.value,
+    }))
+}
+
+/// Record newly announced next-run times and match observed runs against
+/// pending expectations. Returns crons that newly missed their window.
+fn reconcile(
+    conn: &rusqlite::Connection,
+    deployment: &str,
+    crons: &serde_json::Value,
+    runs: &serde_json::Value,
+) -> Result<Vec<CronRun>, String> {
+    let now = chrono::Utc::now().timestamp_millis();
+
+    // Upcoming executions announced by the cron definitions
+    if let Some(list) = crons.as_array() {
+        for cron in list {
+            let name = cron
+                .get("name")
+                .or_else(|| cron.get("identifier"))
+                .and_then(|v| v.as_str());
+            let next_ts = cron
+                .get("nextRun")
+                .or_else(|| cron.get("nextTs"))
+                .and_then(|v| v.as_f64())
+                .map(|ts| ts as i64);
+
+            if let (Some(name), Some(next_ts)) = (name, next_ts) {
+                conn.execute(
+                    "INSERT OR IGNORE INTO cron_runs (deployment, name, scheduled_ts)
+                     VALUES (?1, ?2, ?3)",
+                    params![deployment, name, next_ts],
+                )
+                .map_err(|e| format!("Insert error: {}", e))?;
+            }
+        }
+    }
+
+    // Observed runs resolve their closest pending expectation
+    if let Some(list) = runs.as_array() {
+        for run in list {
+            let name = run
+                .get("name")
+                .or_else(|| run.get("cronName"))
+                .and_then(|v| v.as_str());
+            let ts = run
+                .get("ts")
+                .or_else(|| run.get("startTs"))
+                .and_then(|v| v.as_f64())
+                .map(|ts| ts as i64);
+            let ok = run
+                .get("status")
+                .and_then(|v| v.as_str())
+                .map(|s| s == "success")
+                .unwrap_or(true);
+
+            if let (Some(name), Some(ts)) = (name, ts) {
+                conn.execute(
+                    "UPDATE cron_runs SET actual_ts = ?4, status = ?5
+                     WHERE deployment = ?1 AND name = ?2 AND status = 'pending'
+                       AND scheduled_ts = (
+                           SELECT scheduled_ts FROM cron_runs
+                           WHERE deployment = ?1 AND name = ?2 AND status = 'pending'
+                             AND scheduled_ts <= ?3
+                           ORDER BY scheduled_ts DESC LIMIT 1
+                       )",
+                    params![
+                        deployment,
+                        name,
+                        ts + MISS_GRACE_MS,
+                        ts,
+                        if ok { "ok" } else { "failed" }
+                    ],
+                )
+                .map_err(|e| format!("Update error: {}", e))?;
+            }
+        }
+    }
+
+    // Anything still pending past its grace window has been missed
+    let mut stmt = conn
+        .prepare(
+            "SELECT name, scheduled_ts FROM cron_runs
+             WHERE deployment = ?1 AND status = 'pending' AND scheduled_ts < ?2",
+        )
+        .map_err(|e| format!("Query error: {}", e))?;
+    let missed: Vec<CronRun> = stmt
+        .query_map(params![deployment, now - MISS_GRACE_MS], |row| {
+            Ok(CronRun {
+                name: row.get(0)?,
+                scheduled_ts: row.get(1)?,
+                actual_ts: None,
+                status: "missed".to_string(),
+            })
+        })
+        .map_err(|e| format!("Query error: {}", e))?
+        .collect::<Result<_, _>>()
+        .map_err(|e| format!("Query error: {}", e))?;
+
+    for run in &missed {
+        conn.execute(
+            "UPDATE cron_runs SET status = 'missed'
+             WHERE deployment = ?1 AND name = ?2 AND scheduled_ts = ?3",
+            params![deployment, run.name, run.scheduled_ts],
+        )
+        .map_err(|e| format!("Update error: {}", e))?;
+    }
+
+    Ok(missed)
+}
+
+async fn poll_once(
+    app: &AppHandle,
+    client: &ConvexClient,
+    deployment_url: &str,
+) -> Result<(), String> {
+    let crons = client
+        .run_function(
+            "query",
+            "_system/frontend/listCronJobs:default",
+            serde_json::json!({}),
+        )
+        .await?
+        .value
+        .unwrap_or_default();
+    let runs = client
+        .run_function(
+            "query",
+            "_system/frontend/listCronJobRuns:default",
+            serde_json::json!({}),
+        )
+        .await?
+        .value
+        .unwrap_or_default();
+
+    let missed = {
+        let db = app.state::<DbConnection>();
+        let conn = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+        reconcile(&conn, deployment_url, &crons, &runs)?
+    };
+
+    for run in missed {
+        if let Some(window) = app.get_webview_window("main") {
+            let _ = window.emit(
+                "cron-missed",
+                serde_json::json!({
+                    "deployment": deployment_url,
+                    "name": run.name,
+                    "scheduledTs": run.scheduled_ts,
+                }),
+            );
+        }
+
+        if !crate::notifications::muted() {
+            let _ = app
+                .notification()
+                .builder()
+                .title("Cron missed its window")
+                .body(format!("{} did not run as scheduled", run.name))
+                .show();
+        }
+    }
+
+    Ok(())
+}
+
+/// Start monitoring a deployment's crons
+#[tauri::command]
+pub fn watch_crons(
+    app: AppHandle,
+    deployment_url: String,
+    admin_key: Option<String>,
+) -> Result<(), String> {
+    let admin_key = crate::convex_client::resolve_admin_key(&deployment_url, admin_key)?;
+    let deployment_url = deployment_url.trim_end_matches('/').to_string();
+
+    let generation = {
+        let mut monitors = MONITORS.lock().unwrap();
+        let generation = monitors.get(&deployment_url).map(|g| g + 1).unwrap_or(1);
+        monitors.insert(deployment_url.clone(), generation);
+        generation
+    };
+
+    tauri::async_runtime::spawn(async move {
+        let client = match ConvexClient::new(&deployment_url, admin_key) {
+            Ok(client) => client,
+            Err(e) => {
+                eprintln!("[cron_monitor] {}", e);
+                return;
+            }
+        };
+
+        crate::background_jobs::set_running("cron-monitor", true);
+        loop {
+            let active = MONITORS
+                .lock()
+                .unwrap()
+                .get(&deployment_url)
+                .map(|g| *g == generation)
+                .unwrap_or(false);
+            if !active {
+                break;
+            }
+
+            if crate::background_jobs::should_run("cron-monitor") {
+                let result = poll_once(&app, &client, &deployment_url).await;
+                if let Err(e) = &result {
+                    eprintln!("[cron_monitor] Poll failed for {}: {}", deployment_url, e);
+                }
+                crate::background_jobs::report("cron-monitor", &result);
+            }
+
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+
+        if MONITORS.lock().unwrap().is_empty() {
+            crate::background_jobs::set_running("cron-monitor", false);
+        }
+    });
+
+    Ok(())
+}
+
+/// Stop monitoring a deployment's crons
+#[tauri::command]
+pub fn unwatch_crons(deployment_url: String) -> Result<bool, String> {
+    let deployment_url = deployment_url.trim_end_matches('/').to_string();
+    Ok(MONITORS.lock().unwrap().remove(&deployment_url).is_some())
+}
+
+/// Escape text for an iCalendar property value
+fn ical_escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+/// iCalendar UTC timestamp
+fn ical_ts(ms: i64) -> String {
+    chrono::DateTime::from_timestamp_millis(ms)
+        .unwrap_or_default()
+        .format("%Y%m%dT%H%M%SZ")
+        .to_string()
+}
+
+/// RRULE for a cron schedule, when it maps onto one. Raw cron expressions
+/// don't translate cleanly and are carried in the description instead.
+fn rrule_for(schedule: &serde_json::Value) -> Option<String> {
+    let kind = schedule.get("type")?.as_str()?;
+    match kind {
+        "interval" => {
+            if let Some(seconds) = schedule.get("seconds").and_then(|v| v.as_i64()) {
+                Some(format!("FREQ=SECONDLY;INTERVAL={}", seconds))
+            } else if let Some(minutes) = schedule.get("minutes").and_then(|v| v.as_i64()) {
+                Some(format!("FREQ=MINUTELY;INTERVAL={}", minutes))
+            } else {
+                schedule
+                    .get("hours")
+                    .and_then(|v| v.as_i64())
+                    .map(|hours| format!("FREQ=HOURLY;INTERVAL={}", hours))
+            }
+        }
+        "hourly" => Some("FREQ=HOURLY".to_string()),
+        "daily" => Some("FREQ=DAILY".to_string()),
+        "weekly" => {
+            let day = match schedule.get("dayOfWeek").and_then(|v| v.as_str())? {
+                "monday" => "MO",
+                "tuesday" => "TU",
+                "wednesday" => "WE",
+                "thursday" => "TH",
+                "friday" => "FR",
+                "saturday" => "SA",
+                "sunday" => "SU",
+                _ => return Some("FREQ=WEEKLY".to_string()),
+            };
+            Some(format!("FREQ=WEEKLY;BYDAY={}", day))
+        }
+        "monthly" => schedule
+            .get("day")
+            .and_then(|v| v.as_i64())
+            .map(|day| format!("FREQ=MONTHLY;BYMONTHDAY={}", day)),
+        _ => None,
+    }
+}
+
+/// Convert a deployment's cron definitions into an .ics calendar (one
+/// recurring event per cron, starting at its next expected run) and write
+/// it to `output_path` or ~/.convex-panel. Returns the file path.
+#[tauri::command]
+pub async fn export_cron_calendar(
+    deployment_url: String,
+    admin_key: Option<String>,
+    output_path: Option<String>,
+) -> Result<String, String> {
+    let client = ConvexClient::for_deployment(&deployment_url, admin_key)?;
+    let crons = client
+        .run_function(
+            "query",
+            "_system/frontend/listCronJobs:default",
+            serde_json::json!({}),
+        )
+        .await?
+        .value
+        .unwrap_or_default();
+
+    let now = chrono::Utc::now().timestamp_millis();
+    let mut ics = String::from(
+        "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:-//convex-panel//cron-calendar//EN\r\n",
+    );
+
+    for cron in crons.as_array().map(|l| l.as_slice()).unwrap_or_default() {
+        let Some(name) = cron
+            .get("name")
+            .or_else(|| cron.get("identifier"))
+            .and_then(|v| v.as_str())
+        else {
+            continue;
+        };
+        let next_ts = cron
+            .get("nextRun")
+            .and_then(|run| run.get("nextTs").or(Some(run)))
+            .and_then(|v| v.as_f64())
+            .map(|ts| ts as i64)
+            .unwrap_or(now);
+        let schedule = cron
+            .get("cronSpec")
+            .and_then(|spec| spec.get("cronSchedule"))
+            .cloned()
+            .unwrap_or_default();
+
+        ics.push_str("BEGIN:VEVENT\r\n");
+        ics.push_str(&format!("UID:{}@{}\r\n", ical_escape(name), deployment_url));
+        ics.push_str(&format!("DTSTAMP:{}\r\n", ical_ts(now)));
+        ics.push_str(&format!("DTSTART:{}\r\n", ical_ts(next_ts)));
+        ics.push_str(&format!("SUMMARY:{}\r\n", ical_escape(name)));
+        if let Some(rrule) = rrule_for(&schedule) {
+            ics.push_str(&format!("RRULE:{}\r\n", rrule));
+        }
+        ics.push_str(&format!(
+            "DESCRIPTION:Convex cron on {}\\nSchedule: {}\r\n",
+            ical_escape(&deployment_url),
+            ical_escape(&schedule.to_string()),
+        ));
+        ics.push_str("END:VEVENT\r\n");
+    }
+    ics.push_str("END:VCALENDAR\r\n");
+
+    let path = match output_path {
+        Some(path) => std::path::PathBuf::from(path),
+        None => {
+            let home = std::env::var("HOME")
+                .or_else(|_| std::env::var("USERPROFILE"))
+                .map_err(|_| "Failed to get home directory")?;
+            let label = deployment_url
+                .trim_end_matches('/')
+                .trim_start_matches("https://")
+                .trim_start_matches("http://")
+                .replace(['/', ':', '.'], "-");
+            std::path::PathBuf::from(home)
+                .join(".convex-panel")
+                .join(format!("crons-{}.ics", label))
+        }
+    };
+    std::fs::write(&path, ics).map_err(|e| format!("Failed to write calendar: {}", e))?;
+
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// Recorded cron executions, newest first, for the Schedules view
+#[tauri::command]
+pub async fn get_cron_health(
+    db: State<'_, DbConnection>,
+    deployment: String,
+    limit: Option<i64>,
+) -> Result<Vec<CronRun>, String> {
+    let conn = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT name, scheduled_ts, actual_ts, status FROM cron_runs
+             WHERE deployment = ?1 ORDER BY scheduled_ts DESC LIMIT ?2",
+        )
+        .map_err(|e| format!("Query error: {}", e))?;
+
+    let rows = stmt
+        .query_map(params![deployment, limit.unwrap_or(200).min(1000)], |row| {
+            Ok(CronRun {
+                name: row.get(0)?,
+                scheduled_ts: row.get(1)?,
+                actual_ts: row.get(2)?,
+                status: row.get(3)?,
+            })
+        })
+        .map_err(|e| format!("Query error: {}", e))?;
+
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Query error: {}", e))
+}
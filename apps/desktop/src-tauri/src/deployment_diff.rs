@@ -0,0 +1,164 @@
+//! Deployment diff tool
+//!
+//! Compares two deployments — environment variable names, deployed
+//! functions, and schemas — through the Convex client, producing the
+//! structured diff behind the "what's different between staging and prod"
+//! view. Env var values are never included; only which names exist differs.
+
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+use crate::convex_client::ConvexClient;
+use crate::schema_store::{diff_schemas, SchemaDiff};
+
+/// One deployed function as reported by the modules system UDF
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FunctionSpec {
+    pub udf_type: String,
+    pub visibility: String,
+}
+
+/// Functions present in both deployments but with differing specs
+#[derive(Debug, Clone, Serialize)]
+pub struct FunctionChange {
+    pub path: String,
+    pub a: FunctionSpec,
+    pub b: FunctionSpec,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DeploymentDiff {
+    pub env_only_in_a: Vec<String>,
+    pub env_only_in_b: Vec<String>,
+    pub functions_only_in_a: Vec<String>,
+    pub functions_only_in_b: Vec<String>,
+    pub functions_changed: Vec<FunctionChange>,
+    pub schema: SchemaDiff,
+}
+
+/// Flatten the modules listing into "module.js:functionName" → spec
+fn function_specs(modules: &serde_json::Value) -> BTreeMap<String, FunctionSpec> {
+    let mut specs = BTreeMap::new();
+
+    let Some(entries) = modules.as_array() else {
+        return specs;
+    };
+    for entry in entries {
+        // Each entry is a [modulePath, module] pair
+        let (Some(path), Some(module)) = (
+            entry.get(0).and_then(|v| v.as_str()),
+            entry.get(1),
+        ) else {
+            continue;
+        };
+
+        let Some(functions) = module.get("functions").and_then(|v| v.as_array()) else {
+            continue;
+        };
+        for function in functions {
+            let Some(name) = function.get("name").and_then(|v| v.as_str()) else {
+                continue;
+            };
+            let udf_type = function
+                .get("udfType")
+                .and_then(|v| v.as_str())
+                .unwrap_or("")
+                .to_string();
+            let visibility = function
+                .get("visibility")
+                .and_then(|v| v.get("kind"))
+                .and_then(|v| v.as_str())
+                .unwrap_or("public")
+                .to_string();
+
+            specs.insert(
+                format!("{}:{}", path, name),
+                FunctionSpec {
+                    udf_type,
+                    visibility,
+                },
+            );
+        }
+    }
+
+    specs
+}
+
+async fn fetch_side(
+    client: &ConvexClient,
+) -> Result<
+    (
+        Vec<String>,
+        BTreeMap<String, FunctionSpec>,
+        serde_json::Value,
+    ),
+    String,
+> {
+    let env: Vec<String> = {
+        let mut names: Vec<String> = client.environment_variables().await?.into_keys().collect();
+        names.sort();
+        names
+    };
+
+    let modules = client
+        .run_function(
+            "query",
+            "_system/frontend/modules:list",
+            serde_json::json!({}),
+        )
+        .await?
+        .value
+        .unwrap_or_default();
+
+    let schema = client.schema().await?;
+
+    Ok((env, function_specs(&modules), schema))
+}
+
+fn missing_keys<V>(from: &BTreeMap<String, V>, of: &BTreeMap<String, V>) -> Vec<String> {
+    of.keys()
+        .filter(|key| !from.contains_key(*key))
+        .cloned()
+        .collect()
+}
+
+/// Diff two deployments. Either side falls back to its stored deploy key
+/// when no admin key is passed.
+#[tauri::command]
+pub async fn diff_deployments(
+    a_url: String,
+    b_url: String,
+    a_admin_key: Option<String>,
+    b_admin_key: Option<String>,
+) -> Result<DeploymentDiff, String> {
+    let a = ConvexClient::for_deployment(&a_url, a_admin_key)?;
+    let b = ConvexClient::for_deployment(&b_url, b_admin_key)?;
+
+    let (a_env, a_functions, a_schema) = fetch_side(&a).await?;
+    let (b_env, b_functions, b_schema) = fetch_side(&b).await?;
+
+    let env_only_in_a = a_env.iter().filter(|n| !b_env.contains(n)).cloned().collect();
+    let env_only_in_b = b_env.iter().filter(|n| !a_env.contains(n)).cloned().collect();
+
+    let functions_changed = a_functions
+        .iter()
+        .filter_map(|(path, a_spec)| {
+            b_functions.get(path).and_then(|b_spec| {
+                (a_spec != b_spec).then(|| FunctionChange {
+                    path: path.clone(),
+                    a: a_spec.clone(),
+                    b: b_spec.clone(),
+                })
+            })
+        })
+        .collect();
+
+    Ok(DeploymentDiff {
+        env_only_in_a,
+        env_only_in_b,
+        functions_only_in_a: missing_keys(&b_functions, &a_functions),
+        functions_only_in_b: missing_keys(&a_functions, &b_functions),
+        functions_changed,
+        schema: diff_schemas(&a_schema, &b_schema),
+    })
+}
@@ -0,0 +1,150 @@
+//! Persistent registry of known deployments
+//!
+//! One place recording every deployment the panel has talked to — name,
+//! URL, team/project, kind, where its credentials live, and when it was
+//! last seen — backing the tray switcher, per-deployment settings, and the
+//! background sync services.
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use crate::convex_client::deploy_key_secret_name;
+
+const DEPLOYMENTS_FILE: &str = "deployments.json";
+
+/// One known deployment. Keyed by URL; credentials stay in `secure_store`
+/// and are only referenced here by secret name.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeploymentRecord {
+    pub name: String,
+    pub url: String,
+    pub team: Option<String>,
+    pub project: Option<String>,
+    /// "cloud-dev", "cloud-prod", "local", or "self-hosted"
+    pub kind: String,
+    /// Secure-store secret name holding this deployment's deploy key
+    pub deploy_key_secret: Option<String>,
+    pub last_seen_ms: Option<i64>,
+}
+
+fn registry_path() -> Result<PathBuf, String> {
+    let home = std::env::var("HOME")
+        .or_else(|_| std::env::var("USERPROFILE"))
+        .map_err(|_| "Failed to get home directory")?;
+
+    let app_data = PathBuf::from(home).join(".convex-panel");
+    std::fs::create_dir_all(&app_data)
+        .map_err(|e| format!("Failed to create app data directory: {}", e))?;
+
+    Ok(app_data.join(DEPLOYMENTS_FILE))
+}
+
+fn load_registry() -> Vec<DeploymentRecord> {
+    registry_path()
+        .ok()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_registry(deployments: &[DeploymentRecord]) -> Result<(), String> {
+    let path = registry_path()?;
+    let json = serde_json::to_string_pretty(deployments)
+        .map_err(|e| format!("Failed to serialize deployments: {}", e))?;
+    std::fs::write(&path, json).map_err(|e| format!("Failed to write deployments: {}", e))
+}
+
+/// Registry entry for a URL, when one exists
+pub fn find_deployment(url: &str) -> Option<DeploymentRecord> {
+    let url = url.trim_end_matches('/');
+    load_registry().into_iter().find(|d| d.url == url)
+}
+
+/// Mark a deployment as seen now, if it's registered
+pub fn touch_deployment(url: &str) {
+    let url = url.trim_end_matches('/');
+    let mut deployments = load_registry();
+    if let Some(record) = deployments.iter_mut().find(|d| d.url == url) {
+        record.last_seen_ms = Some(chrono::Utc::now().timestamp_millis());
+        let _ = save_registry(&deployments);
+    }
+}
+
+/// All known deployments, most recently seen first
+#[tauri::command]
+pub fn list_deployments() -> Vec<DeploymentRecord> {
+    let mut deployments = load_registry();
+    deployments.sort_by(|a, b| b.last_seen_ms.unwrap_or(0).cmp(&a.last_seen_ms.unwrap_or(0)));
+    deployments
+}
+
+/// Add or update a deployment, keyed by URL. A stored deploy key for the
+/// URL is picked up as the credentials reference automatically.
+#[tauri::command]
+pub fn upsert_deployment(
+    name: String,
+    url: String,
+    kind: String,
+    team: Option<String>,
+    project: Option<String>,
+) -> Result<DeploymentRecord, String> {
+    if !matches!(kind.as_str(), "cloud-dev" | "cloud-prod" | "local" | "self-hosted") {
+        return Err(format!("Unknown deployment kind: {}", kind));
+    }
+
+    let url = url.trim_end_matches('/').to_string();
+
+    let secret_name = deploy_key_secret_name(&url);
+    let deploy_key_secret = crate::secure_store::read_secret(&secret_name)?
+        .is_some()
+        .then_some(secret_name);
+
+    let mut deployments = load_registry();
+    let record = DeploymentRecord {
+        name,
+        url: url.clone(),
+        team,
+        project,
+        kind,
+        deploy_key_secret,
+        last_seen_ms: deployments
+            .iter()
+            .find(|d| d.url == url)
+            .and_then(|d| d.last_seen_ms),
+    };
+
+    deployments.retain(|d| d.url != url);
+    deployments.push(record.clone());
+    save_registry(&deployments)?;
+
+    Ok(record)
+}
+
+/// Record that a deployment was reachable just now
+#[tauri::command]
+pub fn mark_deployment_seen(url: String) -> Result<(), String> {
+    touch_deployment(&url);
+    Ok(())
+}
+
+/// Remove a deployment from the registry. Its deploy key is left in the
+/// secure store unless `remove_credentials` is set.
+#[tauri::command]
+pub fn remove_deployment(url: String, remove_credentials: Option<bool>) -> Result<bool, String> {
+    let url = url.trim_end_matches('/').to_string();
+
+    let mut deployments = load_registry();
+    let before = deployments.len();
+    deployments.retain(|d| d.url != url);
+
+    if deployments.len() == before {
+        return Ok(false);
+    }
+    save_registry(&deployments)?;
+
+    if remove_credentials.unwrap_or(false) {
+        crate::secure_store::remove_secret(&deploy_key_secret_name(&url))?;
+    }
+
+    Ok(true)
+}
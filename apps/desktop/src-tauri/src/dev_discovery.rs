@@ -0,0 +1,205 @@
+//! Discovery of running `convex dev` processes
+//!
+//! Scans the machine's processes for already-running `convex dev`
+//! instances and recovers their project directories, so the panel can
+//! attach its log view and terminal to an existing dev server instead of
+//! spawning a duplicate. Also probes the default local backend ports for
+//! self-hosted deployments started outside the CLI.
+
+use serde::Serialize;
+use std::path::Path;
+
+/// Ports a local Convex backend listens on by default
+const LOCAL_BACKEND_PORTS: &[u16] = &[3210, 3211, 3212];
+
+/// One discovered dev server
+#[derive(Debug, Clone, Serialize)]
+pub struct DevServer {
+    pub pid: Option<u32>,
+    pub command: String,
+    /// Project directory, when it could be recovered from the process
+    pub project_path: Option<String>,
+    /// CONVEX_URL from the project's env files, when present
+    pub deployment_url: Option<String>,
+}
+
+fn looks_like_convex_dev(command: &str) -> bool {
+    command.contains("convex") && command.split_whitespace().any(|word| word == "dev")
+}
+
+/// CONVEX_URL (falling back to the deployment name) from a project's env
+/// files
+fn project_deployment_url(project_path: &str) -> Option<String> {
+    for name in [".env.local", ".env"] {
+        let path = Path::new(project_path).join(name);
+        if let Ok(content) = std::fs::read_to_string(&path) {
+            if let Some(url) = crate::env_file::get_value(&content, "CONVEX_URL")
+                .or_else(|| crate::env_file::get_value(&content, "VITE_CONVEX_URL"))
+                .or_else(|| crate::env_file::get_value(&content, "NEXT_PUBLIC_CONVEX_URL"))
+            {
+                if !url.is_empty() {
+                    return Some(url);
+                }
+            }
+        }
+    }
+    None
+}
+
+#[cfg(target_os = "linux")]
+fn scan_processes() -> Vec<DevServer> {
+    let mut servers = Vec::new();
+    let Ok(entries) = std::fs::read_dir("/proc") else {
+        return servers;
+    };
+
+    for entry in entries.flatten() {
+        let Some(pid) = entry
+            .file_name()
+            .to_str()
+            .and_then(|name| name.parse::<u32>().ok())
+        else {
+            continue;
+        };
+
+        let Ok(cmdline) = std::fs::read(entry.path().join("cmdline")) else {
+            continue;
+        };
+        let command = String::from_utf8_lossy(&cmdline).replace('\0', " ");
+        if !looks_like_convex_dev(&command) {
+            continue;
+        }
+
+        let project_path = std::fs::read_link(entry.path().join("cwd"))
+            .ok()
+            .map(|cwd| cwd.display().to_string());
+
+        servers.push(DevServer {
+            pid: Some(pid),
+            command: command.trim().to_string(),
+            deployment_url: project_path.as_deref().and_then(project_deployment_url),
+            project_path,
+        });
+    }
+    servers
+}
+
+#[cfg(target_os = "macos")]
+fn scan_processes() -> Vec<DevServer> {
+    let Ok(output) = std::process::Command::new("ps")
+        .args(["-axo", "pid=,command="])
+        .output()
+    else {
+        return Vec::new();
+    };
+
+    let mut servers = Vec::new();
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        let trimmed = line.trim_start();
+        let Some((pid, command)) = trimmed.split_once(' ') else {
+            continue;
+        };
+        let Ok(pid) = pid.parse::<u32>() else {
+            continue;
+        };
+        if !looks_like_convex_dev(command) {
+            continue;
+        }
+
+        // lsof reports the working directory as an "n" field
+        let project_path = std::process::Command::new("lsof")
+            .args(["-a", "-p", &pid.to_string(), "-d", "cwd", "-Fn"])
+            .output()
+            .ok()
+            .and_then(|output| {
+                String::from_utf8_lossy(&output.stdout)
+                    .lines()
+                    .find(|line| line.starts_with('n'))
+                    .map(|line| line[1..].to_string())
+            });
+
+        servers.push(DevServer {
+            pid: Some(pid),
+            command: command.trim().to_string(),
+            deployment_url: project_path.as_deref().and_then(project_deployment_url),
+            project_path,
+        });
+    }
+    servers
+}
+
+#[cfg(target_os = "windows")]
+fn scan_processes() -> Vec<DevServer> {
+    let Ok(output) = std::process::Command::new("powershell")
+        .args([
+            "-NoProfile",
+            "-Command",
+            "Get-CimInstance Win32_Process | Where-Object { $_.CommandLine -match 'convex' } | \
+             ForEach-Object { \"$($_.ProcessId)`t$($_.CommandLine)\" }",
+        ])
+        .output()
+    else {
+        return Vec::new();
+    };
+
+    let mut servers = Vec::new();
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        let Some((pid, command)) = line.split_once('\t') else {
+            continue;
+        };
+        if !looks_like_convex_dev(command) {
+            continue;
+        }
+        servers.push(DevServer {
+            pid: pid.trim().parse::<u32>().ok(),
+            command: command.trim().to_string(),
+            // Windows gives no cheap way to read another process's cwd
+            project_path: None,
+            deployment_url: None,
+        });
+    }
+    servers
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+fn scan_processes() -> Vec<DevServer> {
+    Vec::new()
+}
+
+/// Probe the default local backend ports for listeners that no scanned
+/// process explains — typically a self-hosted backend in a container
+fn probe_local_ports(known: &[DevServer]) -> Vec<DevServer> {
+    let mut servers = Vec::new();
+    for port in LOCAL_BACKEND_PORTS {
+        let url = format!("http://127.0.0.1:{}", port);
+        if known.iter().any(|s| s.deployment_url.as_deref() == Some(&url)) {
+            continue;
+        }
+
+        let addr = std::net::SocketAddr::from(([127, 0, 0, 1], *port));
+        if std::net::TcpStream::connect_timeout(&addr, std::time::Duration::from_millis(250))
+            .is_ok()
+        {
+            servers.push(DevServer {
+                pid: None,
+                command: format!("listener on port {}", port),
+                project_path: None,
+                deployment_url: Some(url),
+            });
+        }
+    }
+    servers
+}
+
+/// Running `convex dev` instances (and local backend listeners) on this
+/// machine
+#[tauri::command]
+pub async fn discover_local_dev_servers() -> Result<Vec<DevServer>, String> {
+    tauri::async_runtime::spawn_blocking(|| {
+        let mut servers = scan_processes();
+        servers.extend(probe_local_ports(&servers));
+        servers
+    })
+    .await
+    .map_err(|e| format!("Task failed: {}", e))
+}
@@ -0,0 +1,955 @@
+//! Dotenv parsing and formatting-preserving editing
+//!
+//! A small dotenv implementation used by the env commands: handles `export`
+//! prefixes, single/double quoted values, escape sequences, and inline
+//! comments, and rewrites files by replacing only the assignment line that
+//! changed so comments and blank lines survive edits.
+
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter};
+
+/// One parsed `KEY=value` assignment line
+#[derive(Debug, Clone, PartialEq)]
+pub struct Assignment {
+    pub key: String,
+    pub value: String,
+    /// Whether the line used an `export ` prefix
+    pub export: bool,
+    /// Trailing inline comment, including the leading `#`
+    pub comment: Option<String>,
+}
+
+/// Parse one line into an assignment. Returns None for blank lines, full-line
+/// comments, and anything that isn't `KEY=...`.
+pub fn parse_line(line: &str) -> Option<Assignment> {
+    let trimmed = line.trim_start();
+
+    if trimmed.is_empty() || trimmed.starts_with('#') {
+        return None;
+    }
+
+    let (export, rest) = match trimmed.strip_prefix("export ") {
+        Some(rest) => (true, rest.trim_start()),
+        None => (false, trimmed),
+    };
+
+    let eq = rest.find('=')?;
+    let key = rest[..eq].trim().to_string();
+
+    if key.is_empty() || key.contains(char::is_whitespace) {
+        return None;
+    }
+
+    let (value, comment) = parse_value(&rest[eq + 1..]);
+
+    Some(Assignment {
+        key,
+        value,
+        export,
+        comment,
+    })
+}
+
+/// Parse the raw text after `=` into (value, trailing inline comment)
+fn parse_value(raw: &str) -> (String, Option<String>) {
+    let raw = raw.trim_start();
+    let mut chars = raw.chars().peekable();
+
+    let value = match chars.peek() {
+        // Double quotes: escape sequences are processed
+        Some('"') => {
+            chars.next();
+            let mut value = String::new();
+            while let Some(c) = chars.next() {
+                match c {
+                    '"' => break,
+                    '\\' => match chars.next() {
+                        Some('n') => value.push('\n'),
+                        Some('t') => value.push('\t'),
+                        Some('r') => value.push('\r'),
+                        Some(other) => value.push(other),
+                        None => value.push('\\'),
+                    },
+                    other => value.push(other),
+                }
+            }
+            value
+        }
+        // Single quotes: everything is literal
+        Some('\'') => {
+            chars.next();
+            let mut value = String::new();
+            for c in chars.by_ref() {
+                if c == '\'' {
+                    break;
+                }
+                value.push(c);
+            }
+            value
+        }
+        // Unquoted: runs to an inline comment or end of line
+        _ => {
+            let end = raw
+                .char_indices()
+                .find(|(i, c)| {
+                    *c == '#' && (*i == 0 || raw[..*i].ends_with(char::is_whitespace))
+                })
+                .map(|(i, _)| i)
+                .unwrap_or(raw.len());
+            let value = raw[..end].trim_end().to_string();
+            let comment = raw[end..].trim();
+            let comment = (!comment.is_empty()).then(|| comment.to_string());
+            return (value, comment);
+        }
+    };
+
+    // Anything after a closing quote other than a comment is ignored
+    let remainder: String = chars.collect();
+    let comment = remainder.trim();
+    let comment = comment
+        .starts_with('#')
+        .then(|| comment.to_string());
+
+    (value, comment)
+}
+
+/// Parse a whole file into assignments, in order of appearance
+pub fn parse_env(content: &str) -> Vec<Assignment> {
+    content.lines().filter_map(parse_line).collect()
+}
+
+/// Look up a key; the last assignment wins, matching dotenv loaders
+pub fn get_value(content: &str, key: &str) -> Option<String> {
+    parse_env(content)
+        .into_iter()
+        .rev()
+        .find(|a| a.key == key)
+        .map(|a| a.value)
+}
+
+/// Serialize a value, quoting only when the plain form would be ambiguous
+pub fn serialize_value(value: &str) -> String {
+    let needs_quoting = value.is_empty()
+        || value != value.trim()
+        || value.contains(['#', '"', '\'', '\n', '\r', '\t', ' ', '\\']);
+
+    if !needs_quoting {
+        return value.to_string();
+    }
+
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            other => escaped.push(other),
+        }
+    }
+    escaped.push('"');
+    escaped
+}
+
+/// Render an assignment back into a line
+fn render_assignment(assignment: &Assignment) -> String {
+    let mut line = String::new();
+    if assignment.export {
+        line.push_str("export ");
+    }
+    line.push_str(&assignment.key);
+    line.push('=');
+    line.push_str(&serialize_value(&assignment.value));
+    if let Some(comment) = &assignment.comment {
+        line.push(' ');
+        line.push_str(comment);
+    }
+    line
+}
+
+/// Set a key in the file content, replacing the existing assignment line in
+/// place (keeping its `export` prefix and inline comment) or appending a new
+/// one. All other lines are preserved byte-for-byte.
+pub fn set_variable(content: &str, key: &str, value: &str) -> String {
+    let mut lines: Vec<String> = content.lines().map(|s| s.to_string()).collect();
+    let mut found = false;
+
+    for line in lines.iter_mut() {
+        if let Some(mut assignment) = parse_line(line) {
+            if assignment.key == key {
+                assignment.value = value.to_string();
+                *line = render_assignment(&assignment);
+                found = true;
+                break;
+            }
+        }
+    }
+
+    if !found {
+        lines.push(render_assignment(&Assignment {
+            key: key.to_string(),
+            value: value.to_string(),
+            export: false,
+            comment: None,
+        }));
+    }
+
+    let mut result = lines.join("\n");
+    result.push('\n');
+    result
+}
+
+/// Effective key -> value map of a file; later assignments win
+pub fn env_map(content: &str) -> HashMap<String, String> {
+    parse_env(content)
+        .into_iter()
+        .map(|a| (a.key, a.value))
+        .collect()
+}
+
+/// Remove every assignment of a key, preserving all other lines. Returns the
+/// new content and whether anything was removed.
+pub fn remove_variable(content: &str, key: &str) -> (String, bool) {
+    let mut removed = false;
+
+    let lines: Vec<&str> = content
+        .lines()
+        .filter(|line| {
+            let matches = parse_line(line).map(|a| a.key == key).unwrap_or(false);
+            if matches {
+                removed = true;
+            }
+            !matches
+        })
+        .collect();
+
+    let mut result = lines.join("\n");
+    if !result.is_empty() {
+        result.push('\n');
+    }
+
+    (result, removed)
+}
+
+/// One listed variable for the env editor UI
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct EnvVariable {
+    pub key: String,
+    pub value: String,
+    pub export: bool,
+    pub comment: Option<String>,
+}
+
+/// List all variables in a .env file, optionally with masked values so the
+/// editor UI can show keys without exposing secrets
+#[tauri::command]
+pub fn list_env_variables(
+    file_path: String,
+    mask_values: Option<bool>,
+) -> Result<Vec<EnvVariable>, String> {
+    let path = std::path::Path::new(&file_path);
+
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read file: {}", e))?;
+
+    let mask = mask_values.unwrap_or(false);
+
+    Ok(parse_env(&content)
+        .into_iter()
+        .map(|a| EnvVariable {
+            key: a.key,
+            value: if mask {
+                "••••••••".to_string()
+            } else {
+                a.value
+            },
+            export: a.export,
+            comment: a.comment,
+        })
+        .collect())
+}
+
+/// Remove a key from a .env file. Returns whether the key existed.
+#[tauri::command]
+pub fn delete_env_variable(file_path: String, key: String) -> Result<bool, String> {
+    let path = std::path::Path::new(&file_path);
+
+    if !path.exists() {
+        return Ok(false);
+    }
+
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read file: {}", e))?;
+
+    let (updated, removed) = remove_variable(&content, &key);
+
+    if removed {
+        backup_env_file(path)?;
+        write_env_file_atomic(path, &updated)?;
+    }
+
+    Ok(removed)
+}
+
+const MAX_BACKUPS_PER_FILE: usize = 20;
+
+fn backups_dir() -> Result<std::path::PathBuf, String> {
+    let home = std::env::var("HOME")
+        .or_else(|_| std::env::var("USERPROFILE"))
+        .map_err(|_| "Failed to get home directory")?;
+
+    let dir = std::path::PathBuf::from(home)
+        .join(".convex-panel")
+        .join("env-backups");
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create backup directory: {}", e))?;
+
+    Ok(dir)
+}
+
+/// Stable per-file prefix for backup names, derived from the absolute path
+fn backup_prefix(path: &std::path::Path) -> String {
+    use sha2::{Digest, Sha256};
+
+    let digest = Sha256::digest(path.to_string_lossy().as_bytes());
+    hex::encode(&digest[..8])
+}
+
+/// Save a timestamped backup of the file before modifying it
+pub fn backup_env_file(path: &std::path::Path) -> Result<(), String> {
+    if !path.exists() {
+        return Ok(());
+    }
+
+    let dir = backups_dir()?;
+    let prefix = backup_prefix(path);
+    let backup = dir.join(format!(
+        "{}-{}.env",
+        prefix,
+        chrono::Utc::now().timestamp_millis()
+    ));
+
+    std::fs::copy(path, &backup).map_err(|e| format!("Failed to back up env file: {}", e))?;
+
+    // Prune the oldest backups beyond the per-file cap
+    let mut backups = list_backups(&dir, &prefix)?;
+    while backups.len() > MAX_BACKUPS_PER_FILE {
+        let oldest = backups.remove(0);
+        let _ = std::fs::remove_file(oldest);
+    }
+
+    Ok(())
+}
+
+/// Backups for one file, sorted oldest first (names embed the timestamp)
+fn list_backups(
+    dir: &std::path::Path,
+    prefix: &str,
+) -> Result<Vec<std::path::PathBuf>, String> {
+    let mut backups: Vec<std::path::PathBuf> = std::fs::read_dir(dir)
+        .map_err(|e| format!("Failed to read backup directory: {}", e))?
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|n| n.to_str())
+                .map(|n| n.starts_with(prefix))
+                .unwrap_or(false)
+        })
+        .collect();
+
+    backups.sort();
+    Ok(backups)
+}
+
+/// Write an env file atomically (temp file + rename) so a crash mid-write
+/// can't leave a truncated file behind
+pub fn write_env_file_atomic(path: &std::path::Path, content: &str) -> Result<(), String> {
+    let dir = path
+        .parent()
+        .ok_or_else(|| format!("No parent directory for {}", path.display()))?;
+
+    let temp = dir.join(format!(
+        ".{}.tmp-{}",
+        path.file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("env"),
+        std::process::id()
+    ));
+
+    std::fs::write(&temp, content).map_err(|e| format!("Failed to write file: {}", e))?;
+    std::fs::rename(&temp, path).map_err(|e| {
+        let _ = std::fs::remove_file(&temp);
+        format!("Failed to write file: {}", e)
+    })
+}
+
+/// Restore the most recent backup of an env file, consuming it so repeated
+/// undos step further back. Returns whether a backup existed.
+#[tauri::command]
+pub fn undo_env_change(file_path: String) -> Result<bool, String> {
+    let path = std::path::PathBuf::from(&file_path);
+    let dir = backups_dir()?;
+
+    let mut backups = list_backups(&dir, &backup_prefix(&path))?;
+    let latest = match backups.pop() {
+        Some(latest) => latest,
+        None => return Ok(false),
+    };
+
+    let content = std::fs::read_to_string(&latest)
+        .map_err(|e| format!("Failed to read backup: {}", e))?;
+    write_env_file_atomic(&path, &content)?;
+
+    let _ = std::fs::remove_file(latest);
+
+    Ok(true)
+}
+
+/// One changed key in an `env-file-changed` event
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct EnvDiffEntry {
+    pub key: String,
+    pub old_value: Option<String>,
+    pub new_value: Option<String>,
+}
+
+/// Diff two env maps into added/removed/changed entries, sorted by key
+pub fn diff_env_maps(
+    old: &HashMap<String, String>,
+    new: &HashMap<String, String>,
+) -> Vec<EnvDiffEntry> {
+    let mut keys: Vec<&String> = old.keys().chain(new.keys()).collect();
+    keys.sort();
+    keys.dedup();
+
+    keys.into_iter()
+        .filter_map(|key| {
+            let old_value = old.get(key).cloned();
+            let new_value = new.get(key).cloned();
+            (old_value != new_value).then(|| EnvDiffEntry {
+                key: key.clone(),
+                old_value,
+                new_value,
+            })
+        })
+        .collect()
+}
+
+// Active watchers and the last parsed snapshot per watched file
+static WATCHERS: Lazy<Mutex<HashMap<String, notify::RecommendedWatcher>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+static SNAPSHOTS: Lazy<Mutex<HashMap<String, HashMap<String, String>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Watch a .env file and emit `env-file-changed` events with a parsed diff
+/// whenever it changes on disk (editor saves, `npx convex env` rewrites, ...)
+#[tauri::command]
+pub fn watch_env_file(app: AppHandle, file_path: String) -> Result<(), String> {
+    use notify::{RecursiveMode, Watcher};
+
+    if WATCHERS.lock().unwrap().contains_key(&file_path) {
+        return Ok(());
+    }
+
+    let path = std::path::PathBuf::from(&file_path);
+    let parent = path
+        .parent()
+        .map(|p| p.to_path_buf())
+        .ok_or_else(|| format!("No parent directory for {}", file_path))?;
+
+    let snapshot = std::fs::read_to_string(&path)
+        .map(|content| env_map(&content))
+        .unwrap_or_default();
+    SNAPSHOTS.lock().unwrap().insert(file_path.clone(), snapshot);
+
+    let watched = path.clone();
+    let watched_key = file_path.clone();
+    let mut watcher = notify::recommended_watcher(
+        move |event: Result<notify::Event, notify::Error>| {
+            let event = match event {
+                Ok(event) => event,
+                Err(_) => return,
+            };
+
+            if !event.paths.iter().any(|p| p == &watched) {
+                return;
+            }
+
+            let new_map = std::fs::read_to_string(&watched)
+                .map(|content| env_map(&content))
+                .unwrap_or_default();
+
+            let changes = {
+                let mut snapshots = SNAPSHOTS.lock().unwrap();
+                let old_map = snapshots.entry(watched_key.clone()).or_default();
+                let changes = diff_env_maps(old_map, &new_map);
+                if !changes.is_empty() {
+                    *old_map = new_map;
+                }
+                changes
+            };
+
+            if changes.is_empty() {
+                return;
+            }
+
+            let _ = app.emit(
+                "env-file-changed",
+                serde_json::json!({
+                    "path": watched_key,
+                    "changes": changes,
+                }),
+            );
+        },
+    )
+    .map_err(|e| format!("Failed to create watcher: {}", e))?;
+
+    // Watch the parent directory so save-by-rename editors don't detach us
+    watcher
+        .watch(&parent, RecursiveMode::NonRecursive)
+        .map_err(|e| format!("Failed to watch {}: {}", parent.display(), e))?;
+
+    WATCHERS.lock().unwrap().insert(file_path, watcher);
+
+    Ok(())
+}
+
+/// Stop watching a .env file
+#[tauri::command]
+pub fn unwatch_env_file(file_path: String) -> Result<(), String> {
+    WATCHERS.lock().unwrap().remove(&file_path);
+    SNAPSHOTS.lock().unwrap().remove(&file_path);
+    Ok(())
+}
+
+/// One resolved variable with the file its effective value came from
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct ResolvedEnvVariable {
+    pub key: String,
+    pub value: String,
+    pub source: String,
+}
+
+/// The dotenv files `convex dev` loads, in ascending override precedence
+pub fn env_file_hierarchy(mode: Option<&str>) -> Vec<String> {
+    let mut files = vec![".env".to_string(), ".env.local".to_string()];
+    if let Some(mode) = mode {
+        files.push(format!(".env.{}", mode));
+        files.push(format!(".env.{}.local", mode));
+    }
+    files
+}
+
+/// Merge parsed file contents (in ascending precedence) into the effective
+/// environment, remembering which file each value came from
+fn merge_env_contents(files: &[(String, String)]) -> Vec<ResolvedEnvVariable> {
+    let mut merged: HashMap<String, (String, String)> = HashMap::new();
+
+    for (name, content) in files {
+        for (key, value) in env_map(content) {
+            merged.insert(key, (value, name.clone()));
+        }
+    }
+
+    let mut resolved: Vec<ResolvedEnvVariable> = merged
+        .into_iter()
+        .map(|(key, (value, source))| ResolvedEnvVariable { key, value, source })
+        .collect();
+    resolved.sort_by(|a, b| a.key.cmp(&b.key));
+    resolved
+}
+
+/// Resolve the effective environment from the standard dotenv file hierarchy
+/// (.env, .env.local, and mode-specific variants), matching what `convex dev`
+/// actually loads
+#[tauri::command]
+pub fn resolve_env_files(
+    project_path: String,
+    mode: Option<String>,
+) -> Result<Vec<ResolvedEnvVariable>, String> {
+    let project = std::path::Path::new(&project_path);
+
+    let files: Vec<(String, String)> = env_file_hierarchy(mode.as_deref())
+        .into_iter()
+        .filter_map(|name| {
+            let path = project.join(&name);
+            if !path.exists() {
+                return None;
+            }
+            match std::fs::read_to_string(&path) {
+                Ok(content) => Some(Ok((name, content))),
+                Err(e) => Some(Err(format!("Failed to read {}: {}", name, e))),
+            }
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok(merge_env_contents(&files))
+}
+
+/// One difference between the local .env and the deployment environment
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct EnvDiff {
+    pub key: String,
+    pub local: Option<String>,
+    pub remote: Option<String>,
+}
+
+/// Report from `sync_env`; on a dry run nothing was actually applied
+#[derive(Debug, Clone, serde::Serialize)]
+pub struct SyncReport {
+    pub applied: bool,
+    pub changes: Vec<EnvDiff>,
+}
+
+fn local_env_path(project_path: &str) -> std::path::PathBuf {
+    std::path::Path::new(project_path).join(".env.local")
+}
+
+fn read_local_env(project_path: &str) -> Result<(std::path::PathBuf, String), String> {
+    // convex dev writes deployment config to .env.local; fall back to .env
+    let env_local = local_env_path(project_path);
+    let path = if env_local.exists() {
+        env_local
+    } else {
+        std::path::Path::new(project_path).join(".env")
+    };
+
+    let content = if path.exists() {
+        std::fs::read_to_string(&path).map_err(|e| format!("Failed to read file: {}", e))?
+    } else {
+        String::new()
+    };
+
+    Ok((path, content))
+}
+
+/// Fetch the deployment's environment variables via the deployment API
+async fn fetch_deployment_env(
+    deployment_url: &str,
+    admin_key: &str,
+) -> Result<HashMap<String, String>, String> {
+    let client = crate::auth_http_client()?;
+
+    let response = client
+        .get(format!(
+            "{}/api/environment_variables",
+            deployment_url.trim_end_matches('/')
+        ))
+        .header("Authorization", format!("Convex {}", admin_key))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch deployment env: {}", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!(
+            "Failed to fetch deployment env: {}",
+            response.status()
+        ));
+    }
+
+    let vars: Vec<serde_json::Value> = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse deployment env: {}", e))?;
+
+    Ok(vars
+        .into_iter()
+        .filter_map(|v| {
+            let name = v.get("name")?.as_str()?.to_string();
+            let value = v.get("value")?.as_str()?.to_string();
+            Some((name, value))
+        })
+        .collect())
+}
+
+fn diff_local_remote(
+    local: &HashMap<String, String>,
+    remote: &HashMap<String, String>,
+) -> Vec<EnvDiff> {
+    let mut keys: Vec<&String> = local.keys().chain(remote.keys()).collect();
+    keys.sort();
+    keys.dedup();
+
+    keys.into_iter()
+        .filter_map(|key| {
+            let local = local.get(key).cloned();
+            let remote = remote.get(key).cloned();
+            (local != remote).then(|| EnvDiff {
+                key: key.clone(),
+                local,
+                remote,
+            })
+        })
+        .collect()
+}
+
+/// Compare local .env keys with the deployment's environment variables
+#[tauri::command]
+pub async fn diff_env(
+    project_path: String,
+    deployment_url: String,
+    admin_key: String,
+) -> Result<Vec<EnvDiff>, String> {
+    let (_, content) = read_local_env(&project_path)?;
+    let local = env_map(&content);
+    let remote = fetch_deployment_env(&deployment_url, &admin_key).await?;
+
+    Ok(diff_local_remote(&local, &remote))
+}
+
+/// Push or pull selected keys between the local .env and the deployment.
+/// `direction` is "push" (local -> deployment) or "pull" (deployment ->
+/// local); with `dry_run` only the report is produced.
+#[tauri::command]
+pub async fn sync_env(
+    project_path: String,
+    deployment_url: String,
+    admin_key: String,
+    direction: String,
+    keys: Vec<String>,
+    dry_run: bool,
+) -> Result<SyncReport, String> {
+    if direction != "push" && direction != "pull" {
+        return Err(format!("Unknown sync direction: {}", direction));
+    }
+
+    let (path, content) = read_local_env(&project_path)?;
+    let local = env_map(&content);
+    let remote = fetch_deployment_env(&deployment_url, &admin_key).await?;
+
+    // Restrict the diff to the requested keys
+    let changes: Vec<EnvDiff> = diff_local_remote(&local, &remote)
+        .into_iter()
+        .filter(|d| keys.contains(&d.key))
+        .collect();
+
+    if dry_run || changes.is_empty() {
+        return Ok(SyncReport {
+            applied: false,
+            changes,
+        });
+    }
+
+    if direction == "push" {
+        let updates: Vec<serde_json::Value> = changes
+            .iter()
+            .map(|d| serde_json::json!({ "name": d.key, "value": d.local }))
+            .collect();
+
+        let client = crate::auth_http_client()?;
+        let response = client
+            .post(format!(
+                "{}/api/update_environment_variables",
+                deployment_url.trim_end_matches('/')
+            ))
+            .header("Authorization", format!("Convex {}", admin_key))
+            .json(&serde_json::json!({ "changes": updates }))
+            .send()
+            .await
+            .map_err(|e| format!("Failed to update deployment env: {}", e))?;
+
+        if !response.status().is_success() {
+            return Err(format!(
+                "Failed to update deployment env: {}",
+                response.status()
+            ));
+        }
+    } else {
+        let mut updated = content;
+        for diff in &changes {
+            updated = match &diff.remote {
+                Some(value) => set_variable(&updated, &diff.key, value),
+                None => remove_variable(&updated, &diff.key).0,
+            };
+        }
+        backup_env_file(&path)?;
+        write_env_file_atomic(&path, &updated)?;
+    }
+
+    Ok(SyncReport {
+        applied: true,
+        changes,
+    })
+}
+
+/// Prefix marking an env value as a reference into the secure store
+/// (e.g. `CONVEX_DEPLOY_KEY=@convex-panel:prod-key`)
+pub const SECRET_REF_PREFIX: &str = "@convex-panel:";
+
+/// Secret name when the value is a secure-store reference
+pub fn secret_ref_name(value: &str) -> Option<&str> {
+    value
+        .strip_prefix(SECRET_REF_PREFIX)
+        .filter(|name| !name.is_empty())
+}
+
+/// Expand secure-store references in an env map, leaving plain values
+/// untouched. Errors when a referenced secret does not exist.
+pub fn resolve_secret_refs(
+    env: HashMap<String, String>,
+) -> Result<HashMap<String, String>, String> {
+    env.into_iter()
+        .map(|(key, value)| {
+            let resolved = match secret_ref_name(&value) {
+                Some(name) => crate::secure_store::read_secret(name)?.ok_or_else(|| {
+                    format!("Secret '{}' referenced by {} not found in secure store", name, key)
+                })?,
+                None => value,
+            };
+            Ok((key, resolved))
+        })
+        .collect()
+}
+
+/// Store a secret and write a `@convex-panel:` reference to it into the env
+/// file, keeping the real value out of the repo directory
+#[tauri::command]
+pub fn write_env_secret_ref(
+    file_path: String,
+    key: String,
+    secret_name: String,
+    value: String,
+) -> Result<(), String> {
+    crate::secure_store::store_secret(&secret_name, &value)?;
+
+    let path = std::path::Path::new(&file_path);
+    let content = if path.exists() {
+        std::fs::read_to_string(path).map_err(|e| format!("Failed to read file: {}", e))?
+    } else {
+        String::new()
+    };
+
+    let updated = set_variable(
+        &content,
+        &key,
+        &format!("{}{}", SECRET_REF_PREFIX, secret_name),
+    );
+
+    backup_env_file(path)?;
+    write_env_file_atomic(path, &updated)
+}
+
+/// Effective environment for a file with secure-store references expanded,
+/// for PTY sessions and API calls
+#[tauri::command]
+pub async fn resolve_env_secrets(file_path: String) -> Result<HashMap<String, String>, String> {
+    tauri::async_runtime::spawn_blocking(move || {
+        let content = std::fs::read_to_string(&file_path)
+            .map_err(|e| format!("Failed to read file: {}", e))?;
+        resolve_secret_refs(env_map(&content))
+    })
+    .await
+    .map_err(|e| format!("Task failed: {}", e))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_line_basic() {
+        let a = parse_line("FOO=bar").unwrap();
+        assert_eq!(a.key, "FOO");
+        assert_eq!(a.value, "bar");
+        assert!(!a.export);
+        assert!(a.comment.is_none());
+    }
+
+    #[test]
+    fn test_parse_line_export_and_comment() {
+        let a = parse_line("export FOO=bar # prod key").unwrap();
+        assert_eq!(a.key, "FOO");
+        assert_eq!(a.value, "bar");
+        assert!(a.export);
+        assert_eq!(a.comment.as_deref(), Some("# prod key"));
+    }
+
+    #[test]
+    fn test_parse_line_quoted_values() {
+        assert_eq!(parse_line(r#"A="has # hash""#).unwrap().value, "has # hash");
+        assert_eq!(parse_line(r#"B="escaped \"q\"""#).unwrap().value, "escaped \"q\"");
+        assert_eq!(parse_line("C='literal \\n'").unwrap().value, "literal \\n");
+        assert_eq!(parse_line(r#"D="line\nbreak""#).unwrap().value, "line\nbreak");
+    }
+
+    #[test]
+    fn test_parse_line_skips_non_assignments() {
+        assert!(parse_line("# comment").is_none());
+        assert!(parse_line("   ").is_none());
+        assert!(parse_line("not an assignment").is_none());
+    }
+
+    #[test]
+    fn test_value_round_trip() {
+        for value in ["plain", "has # hash", "quo\"te", "multi\nline", " padded "] {
+            let line = format!("KEY={}", serialize_value(value));
+            assert_eq!(parse_line(&line).unwrap().value, value);
+        }
+    }
+
+    #[test]
+    fn test_set_variable_preserves_formatting() {
+        let content = "# Deployment config\nexport FOO=old # keep me\n\nBAR=1\n";
+        let updated = set_variable(content, "FOO", "new # value");
+        assert_eq!(
+            updated,
+            "# Deployment config\nexport FOO=\"new # value\" # keep me\n\nBAR=1\n"
+        );
+    }
+
+    #[test]
+    fn test_set_variable_appends_missing_key() {
+        let updated = set_variable("FOO=1\n", "BAZ", "2");
+        assert_eq!(updated, "FOO=1\nBAZ=2\n");
+    }
+
+    #[test]
+    fn test_merge_env_contents_precedence() {
+        let files = vec![
+            (".env".to_string(), "A=base\nB=base\n".to_string()),
+            (".env.local".to_string(), "B=local\n".to_string()),
+        ];
+
+        let resolved = merge_env_contents(&files);
+        assert_eq!(resolved.len(), 2);
+        assert_eq!(resolved[0].value, "base");
+        assert_eq!(resolved[0].source, ".env");
+        assert_eq!(resolved[1].value, "local");
+        assert_eq!(resolved[1].source, ".env.local");
+    }
+
+    #[test]
+    fn test_env_file_hierarchy_with_mode() {
+        assert_eq!(
+            env_file_hierarchy(Some("production")),
+            vec![".env", ".env.local", ".env.production", ".env.production.local"]
+        );
+    }
+
+    #[test]
+    fn test_remove_variable() {
+        let (updated, removed) = remove_variable("# keep\nFOO=1\nBAR=2\n", "FOO");
+        assert!(removed);
+        assert_eq!(updated, "# keep\nBAR=2\n");
+
+        let (unchanged, removed) = remove_variable("BAR=2\n", "FOO");
+        assert!(!removed);
+        assert_eq!(unchanged, "BAR=2\n");
+    }
+
+    #[test]
+    fn test_secret_ref_name() {
+        assert_eq!(secret_ref_name("@convex-panel:prod-key"), Some("prod-key"));
+        assert_eq!(secret_ref_name("@convex-panel:"), None);
+        assert_eq!(secret_ref_name("plain-value"), None);
+    }
+}
@@ -1,10 +1,46 @@
 mod secure_store;
+mod auth_tokens;
+mod oauth_server;
+mod api_server;
+mod background_jobs;
+mod benchmark;
+mod call_replay;
+mod convex_client;
+mod cost_estimator;
+mod crash_reports;
+mod cron_monitor;
+mod deployment_diff;
+mod deployments;
+mod dev_discovery;
+mod env_file;
+mod power_monitor;
+mod recent_projects;
 mod pty;
-mod log_store;
+mod log_aggregation;
+// Public so the companion CLI binary can reuse the local log store
+pub mod log_store;
+mod log_stream;
+mod metrics_store;
+mod schema_store;
+mod session_recorder;
+mod settings_sync;
+mod source_index;
+mod stack_resolver;
+mod table_import;
+mod table_snapshots;
 mod notifications;
+mod otlp_export;
+mod plugins;
+mod shortcuts;
+mod telemetry;
+mod updater;
+mod usage_analytics;
+mod webhook_receiver;
+mod workspace_profiles;
+mod window_state;
 
 use tauri::{Manager, Emitter, AppHandle, include_image};
-use tauri::menu::{Menu, MenuItem, IconMenuItem, Submenu, PredefinedMenuItem};
+use tauri::menu::{Menu, MenuItem, CheckMenuItem, IconMenuItem, Submenu, PredefinedMenuItem};
 use tauri::tray::{TrayIconBuilder, MouseButton, MouseButtonState, TrayIconEvent};
 use tauri_plugin_notification::NotificationExt;
 use std::sync::Mutex;
@@ -22,9 +58,27 @@ pub struct NetworkTestStatus {
     pub overall: String,
 }
 
-static NETWORK_STATUS: Lazy<Mutex<NetworkTestStatus>> = Lazy::new(|| {
-    Mutex::new(NetworkTestStatus::default())
-});
+// Test results keyed by deployment URL; the tray shows the active one
+static NETWORK_STATUS: Lazy<Mutex<HashMap<String, NetworkTestStatus>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+// Deployment URL whose status drives the tray (first reported until chosen)
+static ACTIVE_NETWORK_DEPLOYMENT: Lazy<Mutex<Option<String>>> = Lazy::new(|| Mutex::new(None));
+
+/// All per-deployment statuses plus which one the tray is showing
+#[derive(Clone, serde::Serialize, Default)]
+pub struct NetworkStatusSnapshot {
+    pub active: Option<String>,
+    pub deployments: HashMap<String, NetworkTestStatus>,
+}
+
+/// Status of the deployment currently shown in the tray
+fn active_network_status() -> NetworkTestStatus {
+    let active = ACTIVE_NETWORK_DEPLOYMENT.lock().unwrap().clone();
+    active
+        .and_then(|url| NETWORK_STATUS.lock().unwrap().get(&url).cloned())
+        .unwrap_or_default()
+}
 
 // Deployment push notification tracking
 #[derive(Clone, serde::Serialize, serde::Deserialize)]
@@ -60,9 +114,457 @@ struct TrayMenuItems {
     proxy_status: MenuItem<tauri::Wry>,
 }
 
+// Deployment switcher state shown in the tray submenu
+static TRAY_DEPLOYMENTS: Lazy<Mutex<TrayDeployments>> = Lazy::new(|| {
+    Mutex::new(TrayDeployments {
+        known: Vec::new(),
+        active: None,
+    })
+});
+
+// Handle to the tray's "Deployments" submenu so it can be rebuilt on changes
+static TRAY_DEPLOYMENT_MENU: Lazy<Mutex<Option<Submenu<tauri::Wry>>>> =
+    Lazy::new(|| Mutex::new(None));
+
+struct TrayDeployments {
+    known: Vec<String>,
+    active: Option<String>,
+}
+
+/// Rebuild the tray deployment submenu from the current known/active state
+fn rebuild_deployment_submenu(app: &AppHandle) {
+    let submenu_guard = TRAY_DEPLOYMENT_MENU.lock().unwrap();
+    let submenu = match submenu_guard.as_ref() {
+        Some(s) => s,
+        None => return,
+    };
+
+    // Clear existing entries
+    if let Ok(items) = submenu.items() {
+        for item in items {
+            let _ = submenu.remove(&item);
+        }
+    }
+
+    let state = TRAY_DEPLOYMENTS.lock().unwrap();
+
+    if state.known.is_empty() {
+        if let Ok(placeholder) =
+            MenuItem::with_id(app, "no_deployments", "No deployments", false, None::<&str>)
+        {
+            let _ = submenu.append(&placeholder);
+        }
+        return;
+    }
+
+    for deployment in &state.known {
+        let checked = state.active.as_deref() == Some(deployment.as_str());
+        if let Ok(item) = CheckMenuItem::with_id(
+            app,
+            format!("deployment:{}", deployment),
+            deployment,
+            true,
+            checked,
+            None::<&str>,
+        ) {
+            let _ = submenu.append(&item);
+        }
+    }
+}
+
+/// Record a deployment in the tray switcher, rebuilding the submenu if new
+fn track_tray_deployment(app: &AppHandle, deployment: &str) {
+    let added = {
+        let mut state = TRAY_DEPLOYMENTS.lock().unwrap();
+        if state.known.iter().any(|d| d == deployment) {
+            false
+        } else {
+            state.known.push(deployment.to_string());
+            true
+        }
+    };
+
+    if added {
+        rebuild_deployment_submenu(app);
+    }
+}
+
+/// Replace the tray switcher's deployment list (called by the frontend when
+/// the set of known deployments or the active one changes)
+#[tauri::command]
+fn set_tray_deployments(
+    app: AppHandle,
+    deployments: Vec<String>,
+    active: Option<String>,
+) -> Result<(), String> {
+    {
+        let mut state = TRAY_DEPLOYMENTS.lock().unwrap();
+        state.known = deployments;
+        state.active = active;
+    }
+
+    rebuild_deployment_submenu(&app);
+    Ok(())
+}
+
+// Handle to the tray's "Recent Pushes" submenu, rebuilt on each push
+static TRAY_RECENT_PUSHES_MENU: Lazy<Mutex<Option<Submenu<tauri::Wry>>>> =
+    Lazy::new(|| Mutex::new(None));
+
+/// Human-friendly relative time for tray entries ("just now", "5m ago", ...)
+fn format_relative_time(timestamp_ms: i64) -> String {
+    let now = chrono::Utc::now().timestamp_millis();
+    let elapsed_secs = ((now - timestamp_ms) / 1000).max(0);
+
+    match elapsed_secs {
+        0..=59 => "just now".to_string(),
+        60..=3599 => format!("{}m ago", elapsed_secs / 60),
+        3600..=86399 => format!("{}h ago", elapsed_secs / 3600),
+        _ => format!("{}d ago", elapsed_secs / 86400),
+    }
+}
+
+/// Rebuild the tray submenu of recent deployment pushes
+fn rebuild_recent_pushes_submenu(app: &AppHandle) {
+    let submenu_guard = TRAY_RECENT_PUSHES_MENU.lock().unwrap();
+    let submenu = match submenu_guard.as_ref() {
+        Some(s) => s,
+        None => return,
+    };
+
+    if let Ok(items) = submenu.items() {
+        for item in items {
+            let _ = submenu.remove(&item);
+        }
+    }
+
+    let state = DEPLOYMENT_STATE.lock().unwrap();
+
+    if state.recent_pushes.is_empty() {
+        if let Ok(placeholder) =
+            MenuItem::with_id(app, "no_recent_pushes", "No recent pushes", false, None::<&str>)
+        {
+            let _ = submenu.append(&placeholder);
+        }
+        return;
+    }
+
+    for push in &state.recent_pushes {
+        let label = match &push.version {
+            Some(version) => format!(
+                "{} ({}) - {}",
+                push.deployment_name,
+                version,
+                format_relative_time(push.timestamp)
+            ),
+            None => format!(
+                "{} - {}",
+                push.deployment_name,
+                format_relative_time(push.timestamp)
+            ),
+        };
+
+        if let Ok(item) = MenuItem::with_id(
+            app,
+            format!("push:{}", push.deployment_name),
+            label,
+            true,
+            None::<&str>,
+        ) {
+            let _ = submenu.append(&item);
+        }
+    }
+}
+
+// Tray item doubling as "Check for Updates…" and the pending-update indicator
+static TRAY_UPDATE_ITEM: Lazy<Mutex<Option<MenuItem<tauri::Wry>>>> =
+    Lazy::new(|| Mutex::new(None));
+
+/// Run an update check in the background, flipping the tray item into an
+/// install prompt when a new version is found
+fn spawn_update_check(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        match updater::check_for_updates(app.clone()).await {
+            Ok(status) if status.available => {
+                if let Some(item) = TRAY_UPDATE_ITEM.lock().unwrap().as_ref() {
+                    let version = status.version.as_deref().unwrap_or("latest");
+                    let _ = item.set_text(format!("Install Update {}…", version));
+                }
+            }
+            Ok(_) => {
+                let _ = app.emit("update-not-available", ());
+            }
+            Err(e) => {
+                eprintln!("[updater] Update check failed: {}", e);
+            }
+        }
+    });
+}
+
+/// Shared handler for the "Check for Updates…" entries in the app menu and
+/// tray: checks when idle, installs when an update is already pending
+fn handle_check_updates(app: AppHandle) {
+    if updater::update_pending().is_some() {
+        tauri::async_runtime::spawn(async move {
+            if let Err(e) = updater::install_pending_update(app).await {
+                eprintln!("[updater] Failed to install update: {}", e);
+            }
+        });
+    } else {
+        spawn_update_check(app);
+    }
+}
+
+// Tray icon handle kept around so health changes can swap the icon
+static TRAY_HANDLE: Lazy<Mutex<Option<tauri::tray::TrayIcon>>> = Lazy::new(|| Mutex::new(None));
+
+// Unread error alerts feeding into the aggregated tray health
+static UNREAD_ALERTS: std::sync::atomic::AtomicUsize = std::sync::atomic::AtomicUsize::new(0);
+
+/// Aggregated app health shown in the menu bar
+#[derive(Clone, Copy, PartialEq)]
+enum TrayHealth {
+    Ok,
+    Warning,
+    Error,
+}
+
+fn status_is_failing(status: &str) -> bool {
+    let lower = status.to_lowercase();
+    lower.contains("fail") || lower.contains("error") || lower.contains("timeout")
+}
+
+/// Aggregate network test results and unread alerts into one health level
+fn compute_tray_health() -> TrayHealth {
+    // No connectivity trumps everything else
+    if !IS_ONLINE.load(std::sync::atomic::Ordering::Relaxed) {
+        return TrayHealth::Error;
+    }
+
+    let alerts = UNREAD_ALERTS.load(std::sync::atomic::Ordering::Relaxed);
+
+    let status = active_network_status();
+    let checks = [
+        &status.websocket,
+        &status.http,
+        &status.sse,
+        &status.proxied_websocket,
+    ];
+    let failing = checks.iter().filter(|s| status_is_failing(s)).count();
+
+    if failing > 0 && status_is_failing(&status.overall) {
+        TrayHealth::Error
+    } else if failing > 0 || alerts > 0 {
+        TrayHealth::Warning
+    } else {
+        TrayHealth::Ok
+    }
+}
+
+/// 3x5 bitmap glyphs for badge digits, one row per array entry (3 LSBs used)
+const BADGE_DIGITS: [[u8; 5]; 10] = [
+    [0b111, 0b101, 0b101, 0b101, 0b111], // 0
+    [0b010, 0b110, 0b010, 0b010, 0b111], // 1
+    [0b111, 0b001, 0b111, 0b100, 0b111], // 2
+    [0b111, 0b001, 0b111, 0b001, 0b111], // 3
+    [0b101, 0b101, 0b111, 0b001, 0b001], // 4
+    [0b111, 0b100, 0b111, 0b001, 0b111], // 5
+    [0b111, 0b100, 0b111, 0b101, 0b111], // 6
+    [0b111, 0b001, 0b010, 0b010, 0b010], // 7
+    [0b111, 0b101, 0b111, 0b101, 0b111], // 8
+    [0b111, 0b101, 0b111, 0b001, 0b111], // 9
+];
+
+/// Draw a red count badge (top-right) onto the icon's RGBA buffer
+fn draw_alert_badge(rgba: &mut [u8], width: u32, height: u32, count: usize) {
+    let radius = (width.min(height) as i32 / 4).max(4);
+    let center_x = width as i32 - radius - 1;
+    let center_y = radius + 1;
+
+    for y in 0..height as i32 {
+        for x in 0..width as i32 {
+            let dx = x - center_x;
+            let dy = y - center_y;
+            if dx * dx + dy * dy <= radius * radius {
+                let idx = ((y as u32 * width + x as u32) * 4) as usize;
+                rgba[idx..idx + 4].copy_from_slice(&[229, 57, 53, 255]);
+            }
+        }
+    }
+
+    // Single digits get drawn into the badge; larger counts stay a plain dot
+    if count > 9 {
+        return;
+    }
+
+    let glyph = BADGE_DIGITS[count];
+    let scale = (radius / 4).max(1);
+    let glyph_w = 3 * scale;
+    let glyph_h = 5 * scale;
+    let origin_x = center_x - glyph_w / 2;
+    let origin_y = center_y - glyph_h / 2;
+
+    for (row, bits) in glyph.iter().enumerate() {
+        for col in 0..3 {
+            if bits & (0b100 >> col) == 0 {
+                continue;
+            }
+            for sy in 0..scale {
+                for sx in 0..scale {
+                    let x = origin_x + col * scale + sx;
+                    let y = origin_y + row as i32 * scale + sy;
+                    if x >= 0 && y >= 0 && (x as u32) < width && (y as u32) < height {
+                        let idx = ((y as u32 * width + x as u32) * 4) as usize;
+                        rgba[idx..idx + 4].copy_from_slice(&[255, 255, 255, 255]);
+                    }
+                }
+            }
+        }
+    }
+}
+
+// Native theme selected from the in-app toggle ("dark" | "light" | "system")
+static NATIVE_THEME: Lazy<Mutex<String>> = Lazy::new(|| Mutex::new("system".to_string()));
+
+/// Tray icon base matching the selected theme. The bundled glyph is dark; the
+/// dark variant recolors it white, since template rendering (which would adapt
+/// it automatically) is only used when following the system theme.
+fn themed_tray_base() -> tauri::image::Image<'static> {
+    let base = include_image!("icons/tray-icon.png");
+
+    if *NATIVE_THEME.lock().unwrap() != "dark" {
+        return base.to_owned();
+    }
+
+    let width = base.width();
+    let height = base.height();
+    let mut rgba = base.rgba().to_vec();
+    for pixel in rgba.chunks_exact_mut(4) {
+        if pixel[3] > 0 {
+            pixel[0] = 255;
+            pixel[1] = 255;
+            pixel[2] = 255;
+        }
+    }
+
+    tauri::image::Image::new_owned(rgba, width, height)
+}
+
+/// Composite a colored status dot (and unread alert badge) onto the tray icon
+fn tray_icon_for_health(health: TrayHealth) -> tauri::image::Image<'static> {
+    let base = themed_tray_base();
+    let alerts = UNREAD_ALERTS.load(std::sync::atomic::Ordering::Relaxed);
+
+    if health == TrayHealth::Ok && alerts == 0 {
+        return base;
+    }
+
+    let width = base.width();
+    let height = base.height();
+    let mut rgba = base.rgba().to_vec();
+
+    if health != TrayHealth::Ok {
+        let color: [u8; 4] = match health {
+            TrayHealth::Ok => unreachable!(),
+            TrayHealth::Warning => [255, 145, 0, 255],
+            TrayHealth::Error => [229, 57, 53, 255],
+        };
+
+        // Dot in the bottom-right corner, sized relative to the icon
+        let radius = (width.min(height) as i32 / 5).max(2);
+        let center_x = width as i32 - radius - 1;
+        let center_y = height as i32 - radius - 1;
+
+        for y in 0..height as i32 {
+            for x in 0..width as i32 {
+                let dx = x - center_x;
+                let dy = y - center_y;
+                if dx * dx + dy * dy <= radius * radius {
+                    let idx = ((y as u32 * width + x as u32) * 4) as usize;
+                    rgba[idx..idx + 4].copy_from_slice(&color);
+                }
+            }
+        }
+    }
+
+    if alerts > 0 {
+        draw_alert_badge(&mut rgba, width, height, alerts);
+    }
+
+    tauri::image::Image::new_owned(rgba, width, height)
+}
+
+/// Re-render the tray icon from the current aggregated health
+fn refresh_tray_health() {
+    let health = compute_tray_health();
+    let alerts = UNREAD_ALERTS.load(std::sync::atomic::Ordering::Relaxed);
+
+    if let Some(tray) = TRAY_HANDLE.lock().unwrap().as_ref() {
+        let _ = tray.set_icon(Some(tray_icon_for_health(health)));
+        // The colored dot/badge and the themed variants need full-color
+        // rendering; only the plain system-theme icon adapts as a template
+        let template = health == TrayHealth::Ok
+            && alerts == 0
+            && *NATIVE_THEME.lock().unwrap() == "system";
+        let _ = tray.set_icon_as_template(template);
+    }
+
+    if let Some(item) = TRAY_ALERTS_ITEM.lock().unwrap().as_ref() {
+        let text = match alerts {
+            0 => "No unread alerts".to_string(),
+            1 => "1 unread alert".to_string(),
+            n => format!("{} unread alerts", n),
+        };
+        let _ = item.set_text(text);
+    }
+}
+
+// Tray menu item showing the unread alert count
+static TRAY_ALERTS_ITEM: Lazy<Mutex<Option<MenuItem<tauri::Wry>>>> =
+    Lazy::new(|| Mutex::new(None));
+
+/// Set the unread error alert count shown as a tray icon badge. The frontend
+/// (and the alerting pipeline) bumps this; focusing the window clears it.
+#[tauri::command]
+fn set_unread_alert_count(count: usize) -> Result<(), String> {
+    UNREAD_ALERTS.store(count, std::sync::atomic::Ordering::Relaxed);
+    refresh_tray_health();
+    Ok(())
+}
+
 const AUTH_ISSUER: &str = "https://auth.convex.dev";
 const BIG_BRAIN_URL: &str = "https://api.convex.dev";
 
+/// Navigation payload emitted for non-OAuth `convex-panel://` deep links,
+/// e.g. `convex-panel://logs?requestId=abc` or `convex-panel://deployment/prod`
+#[derive(Clone, serde::Serialize)]
+struct DeepLinkNavigation {
+    /// Route from the URL host and path, e.g. "logs" or "deployment/prod"
+    route: String,
+    params: HashMap<String, String>,
+}
+
+/// Focus the main window and emit a navigation event for a deep link
+fn handle_navigation_deep_link(app_handle: &AppHandle, url: &url::Url) {
+    let route = match url.host_str() {
+        Some(host) => format!("{}{}", host, url.path()),
+        None => url.path().trim_start_matches('/').to_string(),
+    };
+    let route = route.trim_end_matches('/').to_string();
+
+    let params: HashMap<String, String> = url
+        .query_pairs()
+        .map(|(k, v)| (k.into_owned(), v.into_owned()))
+        .collect();
+
+    if let Some(window) = app_handle.get_webview_window("main") {
+        let _ = window.show();
+        let _ = window.set_focus();
+    }
+
+    let _ = app_handle.emit("deep-link-navigate", DeepLinkNavigation { route, params });
+}
+
 #[derive(serde::Deserialize)]
 struct OidcDiscoveryResponse {
     device_authorization_endpoint: Option<String>,
@@ -422,124 +924,660 @@ fn show_native_about_windows(window_handle: isize) {
     }
 }
 
-/// Update network test status from frontend and update tray menu
-#[tauri::command]
-fn update_network_status(status: NetworkTestStatus) -> Result<(), String> {
-    // Store the status
-    {
-        let mut network_status = NETWORK_STATUS.lock().unwrap();
-        *network_status = status.clone();
-    }
-    
-    // Update tray menu items
-    if let Some(items) = TRAY_MENU_ITEMS.lock().unwrap().as_ref() {
-        let _ = items.ws_status.set_text(format!("WebSocket: {}", status.websocket));
-        let _ = items.http_status.set_text(format!("HTTP: {}", status.http));
-        let _ = items.sse_status.set_text(format!("SSE: {}", status.sse));
-        let _ = items.proxy_status.set_text(format!("Proxied WS: {}", status.proxied_websocket));
-    }
-    
-    Ok(())
+/// Show a native Linux About dialog via zenity (GTK message dialog).
+/// Returns false if zenity isn't installed so the caller can fall back to
+/// the webview About component.
+#[cfg(target_os = "linux")]
+fn show_native_about_linux() -> bool {
+    let version = env!("CARGO_PKG_VERSION");
+    let text = format!(
+        "Convex Panel v{}\n\nA powerful debugging and monitoring tool for Convex applications.",
+        version
+    );
+
+    std::process::Command::new("zenity")
+        .arg("--info")
+        .arg("--title")
+        .arg("About Convex Panel")
+        .arg("--text")
+        .arg(&text)
+        .spawn()
+        .is_ok()
 }
 
-/// Get current network test status
+// System connectivity as last observed by the connectivity monitor
+static IS_ONLINE: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(true);
+
+/// Headless agent mode (`--headless`): no windows, but the background
+/// services — log sync, retention, schedulers — keep running, controllable
+/// via the tray and the local control socket
+static HEADLESS: Lazy<bool> = Lazy::new(|| std::env::args().any(|arg| arg == "--headless"));
+
+/// Port for the local control socket in headless mode
+const CONTROL_SOCKET_PORT: u16 = 14300;
+
+/// Whether the app was launched in headless agent mode
 #[tauri::command]
-fn get_network_status() -> NetworkTestStatus {
-    NETWORK_STATUS.lock().unwrap().clone()
+fn is_headless() -> bool {
+    *HEADLESS
 }
 
-// ============================================================================
-// Deployment Notification Commands
-// ============================================================================
+/// Line-based control socket on localhost so scripts (and a second panel
+/// instance) can talk to a headless agent: "status", "show", and "quit"
+fn start_control_socket(app: AppHandle) {
+    std::thread::spawn(move || {
+        use std::io::{BufRead, BufReader, Write};
+
+        let listener =
+            match std::net::TcpListener::bind(("127.0.0.1", CONTROL_SOCKET_PORT)) {
+                Ok(listener) => listener,
+                Err(e) => {
+                    eprintln!("[control] Failed to bind control socket: {}", e);
+                    return;
+                }
+            };
+
+        for stream in listener.incoming() {
+            let mut stream = match stream {
+                Ok(stream) => stream,
+                Err(_) => continue,
+            };
+
+            let mut reader = match stream.try_clone() {
+                Ok(clone) => BufReader::new(clone),
+                Err(_) => continue,
+            };
+            let mut line = String::new();
+            if reader.read_line(&mut line).is_err() {
+                continue;
+            }
 
-/// Track a new deployment push and send a system notification
-#[tauri::command]
-async fn notify_deployment_push(
-    app: AppHandle,
-    deployment_name: String,
-    deployment_url: String,
-    timestamp: i64,
-    version: Option<String>,
-) -> Result<(), String> {
-    let push = DeploymentPush {
-        deployment_name: deployment_name.clone(),
-        deployment_url,
-        timestamp,
-        version: version.clone(),
+            let response = match line.trim() {
+                "status" => serde_json::json!({
+                    "version": app.package_info().version.to_string(),
+                    "headless": *HEADLESS,
+                    "online": IS_ONLINE.load(std::sync::atomic::Ordering::Relaxed),
+                    "logStreams": log_stream::list_log_streams(),
+                })
+                .to_string(),
+                "show" => {
+                    if let Some(window) = app.get_webview_window("main") {
+                        let _ = window.show();
+                        let _ = window.set_focus();
+                    }
+                    "ok".to_string()
+                }
+                "quit" => {
+                    let _ = writeln!(stream, "ok");
+                    app.exit(0);
+                    return;
+                }
+                other => format!("unknown command: {}", other),
+            };
+
+            let _ = writeln!(stream, "{}", response);
+        }
+    });
+}
+
+/// Cheap connectivity probe: can we open a TCP connection to the Convex API?
+/// DNS failure counts as offline too.
+fn probe_connectivity() -> bool {
+    use std::net::{TcpStream, ToSocketAddrs};
+
+    let addrs = match ("api.convex.dev", 443).to_socket_addrs() {
+        Ok(addrs) => addrs,
+        Err(_) => return false,
     };
 
-    // Update state
-    {
-        let mut state = DEPLOYMENT_STATE.lock().unwrap();
-        
-        // Add to recent pushes (keep last 10)
-        state.recent_pushes.push_front(push.clone());
-        if state.recent_pushes.len() > 10 {
-            state.recent_pushes.pop_back();
+    for addr in addrs {
+        if TcpStream::connect_timeout(&addr, Duration::from_secs(5)).is_ok() {
+            return true;
         }
-        
-        state.last_push_timestamp = Some(timestamp);
     }
 
-    let title = "Deployment Updated";
-    let subtitle = deployment_name.clone();
-    let body = version.as_ref()
-        .map(|v| format!("Version {}", v))
-        .unwrap_or_else(|| "Deployment completed successfully".to_string());
-
-    println!("[Rust] Sending deployment notification: {} - {} - {}", title, subtitle, body);
+    false
+}
 
-    #[cfg(target_os = "macos")]
-    {
-        // Use terminal-notifier if available (better for dev), otherwise osascript
-        // First try terminal-notifier
-        match std::process::Command::new("terminal-notifier")
-            .arg("-title")
-            .arg(&title)
-            .arg("-subtitle")
-            .arg(&subtitle)
-            .arg("-message")
-            .arg(&body)
-            .arg("-sound")
-            .arg("Glass")
-            .output()
-        {
-            Ok(output) if output.status.success() => {
-                println!("[Rust] ✓ Notification sent via terminal-notifier");
-                return Ok(());
-            }
-            Ok(_) | Err(_) => {
-                // terminal-notifier not available, use osascript with subtitle
-                let script = format!(
-                    "display notification \"{}\" with title \"{}\" subtitle \"{}\" sound name \"Glass\"",
-                    body.replace("\"", "\\\""),
-                    title.replace("\"", "\\\""),
-                    subtitle.replace("\"", "\\\"")
+/// Watch system connectivity, emitting `connectivity-changed` on transitions
+/// and flipping the tray into an explicit "Offline" state instead of leaving
+/// stale Pending/OK labels around
+fn start_connectivity_monitor(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let online = tauri::async_runtime::spawn_blocking(probe_connectivity)
+                .await
+                .unwrap_or(false);
+
+            let was_online = IS_ONLINE.swap(online, std::sync::atomic::Ordering::Relaxed);
+
+            if online != was_online {
+                println!(
+                    "[connectivity] System went {}",
+                    if online { "online" } else { "offline" }
                 );
 
-                match std::process::Command::new("osascript")
-                    .arg("-e")
-                    .arg(&script)
-                    .output()
-                {
-                    Ok(output) => {
-                        if output.status.success() {
-                            println!("[Rust] ✓ Deployment notification sent via osascript");
-                            return Ok(());
-                        } else {
-                            eprintln!("[Rust] osascript failed: {:?}", String::from_utf8_lossy(&output.stderr));
-                        }
-                    }
-                    Err(e) => {
-                        eprintln!("[Rust] Failed to execute osascript: {}", e);
+                let _ = app.emit("connectivity-changed", serde_json::json!({ "online": online }));
+
+                if online {
+                    // Back online: refresh the stale test results right away
+                    if let Some(window) = app.get_webview_window("main") {
+                        let _ = window.emit("run-network-tests", ());
                     }
+                } else if let Some(items) = TRAY_MENU_ITEMS.lock().unwrap().as_ref() {
+                    let _ = items.ws_status.set_text("WebSocket: Offline");
+                    let _ = items.http_status.set_text("HTTP: Offline");
+                    let _ = items.sse_status.set_text("SSE: Offline");
+                    let _ = items.proxy_status.set_text("Proxied WS: Offline");
                 }
+
+                refresh_tray_health();
             }
+
+            tokio::time::sleep(Duration::from_secs(15)).await;
         }
-    }
+    });
+}
 
-    // Fallback to Tauri notification API (cross-platform)
-    let mut notification = app.notification()
+// Background monitor re-running the network tests on an interval
+struct NetworkMonitor {
+    interval_secs: u64,
+    // Bumped on every (re)start so stale loops exit
+    generation: u64,
+}
+
+static NETWORK_MONITOR: Lazy<Mutex<NetworkMonitor>> = Lazy::new(|| {
+    Mutex::new(NetworkMonitor {
+        interval_secs: 300,
+        generation: 0,
+    })
+});
+
+/// (Re)start the background network monitor loop. The webview keeps running
+/// while hidden to tray, so asking it to re-run the tests works there too.
+fn start_network_monitor(app: AppHandle) {
+    let generation = {
+        let mut monitor = NETWORK_MONITOR.lock().unwrap();
+        monitor.generation += 1;
+        monitor.generation
+    };
+
+    tauri::async_runtime::spawn(async move {
+        background_jobs::set_running("network-tests", true);
+        loop {
+            let secs = {
+                let monitor = NETWORK_MONITOR.lock().unwrap();
+                if monitor.generation != generation {
+                    return;
+                }
+                monitor.interval_secs
+            };
+
+            // Interval of 0 disables background monitoring
+            if secs == 0 {
+                return;
+            }
+
+            tokio::time::sleep(Duration::from_secs(secs)).await;
+
+            {
+                let monitor = NETWORK_MONITOR.lock().unwrap();
+                if monitor.generation != generation {
+                    return;
+                }
+            }
+
+            // Pause while offline; the connectivity monitor re-runs the
+            // tests once we come back
+            if !IS_ONLINE.load(std::sync::atomic::Ordering::Relaxed) {
+                continue;
+            }
+
+            if !background_jobs::should_run("network-tests") {
+                continue;
+            }
+
+            if let Some(window) = app.get_webview_window("main") {
+                let _ = window.emit("run-network-tests", ());
+                background_jobs::report("network-tests", &Ok(()));
+            }
+        }
+    });
+}
+
+/// Change how often the background monitor re-runs the tests (0 disables it)
+#[tauri::command]
+fn set_network_monitor_interval(app: AppHandle, seconds: u64) -> Result<(), String> {
+    {
+        let mut monitor = NETWORK_MONITOR.lock().unwrap();
+        monitor.interval_secs = seconds;
+    }
+
+    // Restarting picks the new interval up immediately and stops the old loop
+    start_network_monitor(app);
+
+    Ok(())
+}
+
+/// Current background monitor interval in seconds
+#[tauri::command]
+fn get_network_monitor_interval() -> u64 {
+    NETWORK_MONITOR.lock().unwrap().interval_secs
+}
+
+/// Last observed system connectivity
+#[tauri::command]
+fn is_online() -> bool {
+    IS_ONLINE.load(std::sync::atomic::Ordering::Relaxed)
+}
+
+/// One hop in a route trace toward the deployment
+#[derive(Clone, serde::Serialize)]
+struct RouteHop {
+    hop: u32,
+    /// Responding host (address or name), if the hop answered
+    host: Option<String>,
+    latency_ms: Option<f64>,
+}
+
+/// Parse one traceroute/tracert output line into a hop, leniently enough to
+/// cover both tools' formats. Returns None for header/noise lines.
+fn parse_traceroute_line(line: &str) -> Option<RouteHop> {
+    let mut tokens = line.split_whitespace().peekable();
+
+    // A hop line starts with the hop number
+    let hop: u32 = tokens.peek()?.parse().ok()?;
+    tokens.next();
+
+    let mut host = None;
+    let mut latency_ms = None;
+    let mut previous: Option<&str> = None;
+
+    for token in tokens {
+        if token == "ms" {
+            // The token before "ms" is the latency ("<1" on tracert)
+            if latency_ms.is_none() {
+                latency_ms = previous.and_then(|t| t.trim_start_matches('<').parse().ok());
+            }
+        } else if host.is_none()
+            && token != "*"
+            && token.trim_start_matches('<').parse::<f64>().is_err()
+        {
+            host = Some(token.to_string());
+        }
+        previous = Some(token);
+    }
+
+    Some(RouteHop {
+        hop,
+        host,
+        latency_ms,
+    })
+}
+
+/// Run a per-hop route diagnostic toward the deployment host using the
+/// system traceroute/tracert, for debugging regional/ISP issues with Convex
+#[tauri::command]
+async fn trace_deployment_route(deployment_url: String) -> Result<Vec<RouteHop>, String> {
+    // Accept a full deployment URL or a bare hostname
+    let host = url::Url::parse(&deployment_url)
+        .ok()
+        .and_then(|u| u.host_str().map(|h| h.to_string()))
+        .unwrap_or(deployment_url);
+
+    if host.is_empty() {
+        return Err("No deployment host to trace".to_string());
+    }
+
+    tauri::async_runtime::spawn_blocking(move || {
+        #[cfg(target_os = "windows")]
+        let output = std::process::Command::new("tracert")
+            .args(["-h", "20", "-w", "2000"])
+            .arg(&host)
+            .output();
+
+        #[cfg(not(target_os = "windows"))]
+        let output = std::process::Command::new("traceroute")
+            .args(["-q", "1", "-w", "2", "-m", "20"])
+            .arg(&host)
+            .output();
+
+        let output = output.map_err(|e| format!("Failed to run traceroute: {}", e))?;
+
+        if !output.status.success() && output.stdout.is_empty() {
+            return Err(format!(
+                "Traceroute failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+
+        let hops = String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .filter_map(parse_traceroute_line)
+            .collect();
+
+        Ok(hops)
+    })
+    .await
+    .map_err(|e| format!("Trace task failed: {}", e))?
+}
+
+/// Redact credentials from a proxy URL so reports are safe to share
+fn redact_proxy_url(value: &str) -> String {
+    match url::Url::parse(value) {
+        Ok(mut parsed) => {
+            if !parsed.username().is_empty() || parsed.password().is_some() {
+                let _ = parsed.set_username("redacted");
+                let _ = parsed.set_password(Some("redacted"));
+            }
+            parsed.to_string()
+        }
+        // Not a URL; don't risk leaking anything embedded in it
+        Err(_) => "<redacted>".to_string(),
+    }
+}
+
+/// Gather the latest network test results, latency history, DNS diagnostics,
+/// proxy settings (redacted), and app/OS versions into one JSON report for
+/// attaching to Convex support tickets. Returns the written path.
+#[tauri::command]
+async fn export_network_report(
+    app: AppHandle,
+    db: tauri::State<'_, log_store::DbConnection>,
+    path: Option<String>,
+) -> Result<String, String> {
+    let status = get_network_status();
+    let online = IS_ONLINE.load(std::sync::atomic::Ordering::Relaxed);
+
+    // Last 24 hours of recorded samples
+    let history = {
+        let conn = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+        let cutoff = chrono::Utc::now().timestamp_millis() - 24 * 60 * 60 * 1000;
+
+        let mut stmt = conn
+            .prepare(
+                "SELECT ts, check_name, success, latency_ms FROM network_history
+                 WHERE ts >= ? ORDER BY ts ASC",
+            )
+            .map_err(|e| format!("Prepare error: {}", e))?;
+
+        let samples: Vec<serde_json::Value> = stmt
+            .query_map(rusqlite::params![cutoff], |row| {
+                Ok(serde_json::json!({
+                    "ts": row.get::<_, i64>(0)?,
+                    "check": row.get::<_, String>(1)?,
+                    "success": row.get::<_, i32>(2)? != 0,
+                    "latencyMs": row.get::<_, Option<i64>>(3)?,
+                }))
+            })
+            .map_err(|e| format!("Query error: {}", e))?
+            .filter_map(|r| r.ok())
+            .collect();
+
+        samples
+    };
+
+    // DNS diagnostic: resolve the Convex API and time it
+    let dns = {
+        use std::net::ToSocketAddrs;
+
+        let started = std::time::Instant::now();
+        match ("api.convex.dev", 443).to_socket_addrs() {
+            Ok(addrs) => serde_json::json!({
+                "host": "api.convex.dev",
+                "resolvedAddresses": addrs.map(|a| a.ip().to_string()).collect::<Vec<_>>(),
+                "durationMs": started.elapsed().as_millis() as u64,
+            }),
+            Err(e) => serde_json::json!({
+                "host": "api.convex.dev",
+                "error": e.to_string(),
+            }),
+        }
+    };
+
+    // Proxy environment with credentials redacted
+    let proxies: HashMap<String, String> = ["HTTP_PROXY", "HTTPS_PROXY", "ALL_PROXY", "NO_PROXY"]
+        .iter()
+        .filter_map(|name| {
+            std::env::var(name)
+                .or_else(|_| std::env::var(name.to_lowercase()))
+                .ok()
+                .map(|value| {
+                    let value = if *name == "NO_PROXY" {
+                        value
+                    } else {
+                        redact_proxy_url(&value)
+                    };
+                    (name.to_string(), value)
+                })
+        })
+        .collect();
+
+    let report = serde_json::json!({
+        "generatedAt": chrono::Utc::now().to_rfc3339(),
+        "appVersion": app.package_info().version.to_string(),
+        "os": std::env::consts::OS,
+        "arch": std::env::consts::ARCH,
+        "online": online,
+        "latestStatus": status,
+        "history": history,
+        "dns": dns,
+        "proxies": proxies,
+    });
+
+    // Default to a timestamped file in the app data directory
+    let path = match path {
+        Some(p) => std::path::PathBuf::from(p),
+        None => {
+            let home = std::env::var("HOME")
+                .or_else(|_| std::env::var("USERPROFILE"))
+                .map_err(|_| "Failed to get home directory")?;
+            let dir = std::path::PathBuf::from(home).join(".convex-panel");
+            std::fs::create_dir_all(&dir)
+                .map_err(|e| format!("Failed to create app data directory: {}", e))?;
+            dir.join(format!(
+                "network-report-{}.json",
+                chrono::Utc::now().format("%Y%m%d-%H%M%S")
+            ))
+        }
+    };
+
+    let json = serde_json::to_string_pretty(&report)
+        .map_err(|e| format!("Failed to serialize report: {}", e))?;
+    std::fs::write(&path, json).map_err(|e| format!("Failed to write report: {}", e))?;
+
+    Ok(path.to_string_lossy().to_string())
+}
+
+/// Reflect a deployment's status in the tray menu items
+fn refresh_tray_status_items(status: &NetworkTestStatus) {
+    if let Some(items) = TRAY_MENU_ITEMS.lock().unwrap().as_ref() {
+        let _ = items.ws_status.set_text(format!("WebSocket: {}", status.websocket));
+        let _ = items.http_status.set_text(format!("HTTP: {}", status.http));
+        let _ = items.sse_status.set_text(format!("SSE: {}", status.sse));
+        let _ = items.proxy_status.set_text(format!("Proxied WS: {}", status.proxied_websocket));
+    }
+}
+
+/// Update one deployment's network test status and, when it is the active
+/// deployment, the tray menu
+#[tauri::command]
+fn update_network_status(
+    app: AppHandle,
+    deployment_url: String,
+    status: NetworkTestStatus,
+) -> Result<(), String> {
+    // A status report means we talked to the deployment; update the registry
+    if !status_is_failing(&status.overall) {
+        deployments::touch_deployment(&deployment_url);
+    }
+
+    // Store the status, remembering whether this deployment was failing
+    let was_failing = {
+        let mut statuses = NETWORK_STATUS.lock().unwrap();
+        let was_failing = statuses
+            .get(&deployment_url)
+            .map(|s| status_is_failing(&s.overall))
+            .unwrap_or(false);
+        statuses.insert(deployment_url.clone(), status.clone());
+        was_failing
+    };
+
+    // The first reported deployment drives the tray until one is chosen
+    let is_active = {
+        let mut active = ACTIVE_NETWORK_DEPLOYMENT.lock().unwrap();
+        if active.is_none() {
+            *active = Some(deployment_url.clone());
+        }
+        active.as_deref() == Some(deployment_url.as_str())
+    };
+
+    // Notify when connectivity degrades, including while hidden to tray
+    if status_is_failing(&status.overall) && !was_failing && !notifications::muted() {
+        let _ = app
+            .notification()
+            .builder()
+            .title("Convex Panel - Connection Degraded")
+            .body(format!(
+                "Network tests against {} started failing",
+                deployment_url
+            ))
+            .show();
+    }
+
+    if is_active {
+        refresh_tray_status_items(&status);
+        refresh_tray_health();
+    }
+
+    Ok(())
+}
+
+/// Pick which deployment's status the tray should show
+#[tauri::command]
+fn set_active_network_deployment(deployment_url: String) -> Result<(), String> {
+    {
+        let mut active = ACTIVE_NETWORK_DEPLOYMENT.lock().unwrap();
+        *active = Some(deployment_url);
+    }
+
+    refresh_tray_status_items(&active_network_status());
+    refresh_tray_health();
+
+    Ok(())
+}
+
+/// Get the network test status of every monitored deployment
+#[tauri::command]
+fn get_network_status() -> NetworkStatusSnapshot {
+    NetworkStatusSnapshot {
+        active: ACTIVE_NETWORK_DEPLOYMENT.lock().unwrap().clone(),
+        deployments: NETWORK_STATUS.lock().unwrap().clone(),
+    }
+}
+
+// ============================================================================
+// Deployment Notification Commands
+// ============================================================================
+
+/// Track a new deployment push and send a system notification
+#[tauri::command]
+async fn notify_deployment_push(
+    app: AppHandle,
+    deployment_name: String,
+    deployment_url: String,
+    timestamp: i64,
+    version: Option<String>,
+) -> Result<(), String> {
+    let push = DeploymentPush {
+        deployment_name: deployment_name.clone(),
+        deployment_url,
+        timestamp,
+        version: version.clone(),
+    };
+
+    // Update state
+    {
+        let mut state = DEPLOYMENT_STATE.lock().unwrap();
+        
+        // Add to recent pushes (keep last 10)
+        state.recent_pushes.push_front(push.clone());
+        if state.recent_pushes.len() > 10 {
+            state.recent_pushes.pop_back();
+        }
+        
+        state.last_push_timestamp = Some(timestamp);
+    }
+
+    // Pushed deployments are "known" for the tray switcher
+    track_tray_deployment(&app, &deployment_name);
+    rebuild_recent_pushes_submenu(&app);
+
+    // Presentation mode suppresses the native notification (state still updates)
+    if notifications::muted() {
+        println!("[Rust] Notifications muted, skipping deployment notification");
+        return Ok(());
+    }
+
+    let title = "Deployment Updated";
+    let subtitle = deployment_name.clone();
+    let body = version.as_ref()
+        .map(|v| format!("Version {}", v))
+        .unwrap_or_else(|| "Deployment completed successfully".to_string());
+
+    println!("[Rust] Sending deployment notification: {} - {} - {}", title, subtitle, body);
+
+    #[cfg(target_os = "macos")]
+    {
+        // Use terminal-notifier if available (better for dev), otherwise osascript
+        // First try terminal-notifier
+        match std::process::Command::new("terminal-notifier")
+            .arg("-title")
+            .arg(&title)
+            .arg("-subtitle")
+            .arg(&subtitle)
+            .arg("-message")
+            .arg(&body)
+            .arg("-sound")
+            .arg("Glass")
+            .output()
+        {
+            Ok(output) if output.status.success() => {
+                println!("[Rust] ✓ Notification sent via terminal-notifier");
+                return Ok(());
+            }
+            Ok(_) | Err(_) => {
+                // terminal-notifier not available, use osascript with subtitle
+                let script = format!(
+                    "display notification \"{}\" with title \"{}\" subtitle \"{}\" sound name \"Glass\"",
+                    body.replace("\"", "\\\""),
+                    title.replace("\"", "\\\""),
+                    subtitle.replace("\"", "\\\"")
+                );
+
+                match std::process::Command::new("osascript")
+                    .arg("-e")
+                    .arg(&script)
+                    .output()
+                {
+                    Ok(output) => {
+                        if output.status.success() {
+                            println!("[Rust] ✓ Deployment notification sent via osascript");
+                            return Ok(());
+                        } else {
+                            eprintln!("[Rust] osascript failed: {:?}", String::from_utf8_lossy(&output.stderr));
+                        }
+                    }
+                    Err(e) => {
+                        eprintln!("[Rust] Failed to execute osascript: {}", e);
+                    }
+                }
+            }
+        }
+    }
+
+    // Fallback to Tauri notification API (cross-platform)
+    let mut notification = app.notification()
         .builder()
         .title(&format!("{} - {}", title, subtitle))
         .body(&body);
@@ -594,6 +1632,40 @@ fn set_window_size(window: tauri::Window, width: f64, height: f64) -> Result<(),
         .map_err(|e| e.to_string())
 }
 
+/// Center the window on the monitor it currently occupies (falling back to
+/// the monitor under the cursor, then the primary display). Tauri's built-in
+/// `center` always targets the primary monitor, which is wrong on
+/// multi-monitor setups.
+fn center_on_occupied_monitor(window: &tauri::Window) -> Result<(), String> {
+    let monitor = window
+        .current_monitor()
+        .ok()
+        .flatten()
+        .or_else(|| {
+            window
+                .cursor_position()
+                .ok()
+                .and_then(|pos| window.monitor_from_point(pos.x, pos.y).ok().flatten())
+        })
+        .or_else(|| window.primary_monitor().ok().flatten());
+
+    let monitor = match monitor {
+        Some(m) => m,
+        // No monitor information at all; let tauri do its default thing
+        None => return window.center().map_err(|e| e.to_string()),
+    };
+
+    let area = monitor.work_area();
+    let size = window.outer_size().map_err(|e| e.to_string())?;
+
+    let x = area.position.x + (area.size.width as i32 - size.width as i32) / 2;
+    let y = area.position.y + (area.size.height as i32 - size.height as i32) / 2;
+
+    window
+        .set_position(tauri::PhysicalPosition::new(x, y))
+        .map_err(|e| e.to_string())
+}
+
 /// Command to set window size and keep it centered (for smooth center-out expansion)
 #[tauri::command]
 fn set_window_size_centered(window: tauri::Window, width: f64, height: f64) -> Result<(), String> {
@@ -601,16 +1673,161 @@ fn set_window_size_centered(window: tauri::Window, width: f64, height: f64) -> R
     window.set_resizable(true).map_err(|e| e.to_string())?;
     window.set_min_size(None::<tauri::LogicalSize<f64>>).map_err(|e| e.to_string())?;
     window.set_max_size(None::<tauri::LogicalSize<f64>>).map_err(|e| e.to_string())?;
-    
-    // Set size and immediately center
+
+    // Set size and immediately center on the occupied monitor
     window.set_size(tauri::LogicalSize::new(width, height)).map_err(|e| e.to_string())?;
-    window.center().map_err(|e| e.to_string())
+    center_on_occupied_monitor(&window)
 }
 
-/// Command to center the window on screen
+/// Command to center the window on the monitor it occupies
 #[tauri::command]
 fn center_window(window: tauri::Window) -> Result<(), String> {
-    window.center().map_err(|e| e.to_string())
+    center_on_occupied_monitor(&window)
+}
+
+/// Move the window to the monitor at `index` (in `available_monitors` order),
+/// centered in its work area and keeping the current size
+#[tauri::command]
+fn move_to_monitor(window: tauri::Window, index: usize) -> Result<(), String> {
+    let monitors = window.available_monitors().map_err(|e| e.to_string())?;
+    let monitor = monitors
+        .get(index)
+        .ok_or_else(|| format!("No monitor at index {} ({} available)", index, monitors.len()))?;
+
+    let area = monitor.work_area();
+    let size = window.outer_size().map_err(|e| e.to_string())?;
+
+    let x = area.position.x + (area.size.width as i32 - size.width as i32) / 2;
+    let y = area.position.y + (area.size.height as i32 - size.height as i32) / 2;
+
+    window
+        .set_position(tauri::PhysicalPosition::new(x, y))
+        .map_err(|e| e.to_string())
+}
+
+/// Toggle whether the window floats above other applications, persisted per
+/// window so the preference survives restarts
+#[tauri::command]
+fn set_always_on_top(window: tauri::WebviewWindow, always_on_top: bool) -> Result<(), String> {
+    window
+        .set_always_on_top(always_on_top)
+        .map_err(|e| e.to_string())?;
+
+    // Make sure a geometry record exists before updating the flag on it
+    window_state::persist(&window);
+    window_state::set_always_on_top_state(window.label(), always_on_top);
+
+    Ok(())
+}
+
+/// Match the native chrome (title bar, macOS background color, tray icon) to
+/// the in-app theme toggle. Accepts "dark", "light", or "system".
+#[tauri::command]
+fn set_native_theme(window: tauri::WebviewWindow, theme: String) -> Result<(), String> {
+    let native = match theme.as_str() {
+        "dark" => Some(tauri::Theme::Dark),
+        "light" => Some(tauri::Theme::Light),
+        "system" => None,
+        other => return Err(format!("Unknown theme: {}", other)),
+    };
+
+    window
+        .set_theme(native)
+        .map_err(|e| format!("Failed to set window theme: {}", e))?;
+
+    #[cfg(target_os = "macos")]
+    {
+        // Resolve "system" to whatever the OS currently reports
+        let effective = match theme.as_str() {
+            "system" => match window.theme() {
+                Ok(tauri::Theme::Light) => "light".to_string(),
+                _ => "dark".to_string(),
+            },
+            other => other.to_string(),
+        };
+        apply_macos_background_color(&window, theme_background_rgb(&effective));
+    }
+
+    *NATIVE_THEME.lock().unwrap() = theme;
+    refresh_tray_health();
+
+    Ok(())
+}
+
+/// Distraction-free fullscreen mode for demos and screen sharing: fullscreen
+/// the window, mute native notifications, quiet the tray tooltip, and hint the
+/// frontend to scale up its fonts
+#[tauri::command]
+async fn set_presentation_mode(
+    app: AppHandle,
+    window: tauri::WebviewWindow,
+    enabled: bool,
+) -> Result<(), String> {
+    window
+        .set_fullscreen(enabled)
+        .map_err(|e| format!("Failed to toggle fullscreen: {}", e))?;
+
+    notifications::set_muted(enabled);
+
+    if let Some(tray) = TRAY_HANDLE.lock().unwrap().as_ref() {
+        let tooltip = if enabled {
+            None
+        } else {
+            Some("Convex Panel - Network Status")
+        };
+        let _ = tray.set_tooltip(tooltip);
+    }
+
+    let _ = app.emit(
+        "presentation-mode-changed",
+        serde_json::json!({
+            "enabled": enabled,
+            // Suggested UI scale while presenting; the frontend applies it
+            "fontScale": if enabled { 1.25 } else { 1.0 },
+        }),
+    );
+
+    Ok(())
+}
+
+const ZOOM_STEP: f64 = 0.1;
+const ZOOM_MIN: f64 = 0.5;
+const ZOOM_MAX: f64 = 3.0;
+
+/// Set the webview zoom for a window, persisted per window label so HiDPI and
+/// projector setups keep their scale across restarts. Returns the clamped
+/// factor actually applied.
+#[tauri::command]
+fn set_window_zoom(window: tauri::WebviewWindow, zoom: f64) -> Result<f64, String> {
+    let zoom = zoom.clamp(ZOOM_MIN, ZOOM_MAX);
+
+    window
+        .set_zoom(zoom)
+        .map_err(|e| format!("Failed to set zoom: {}", e))?;
+
+    // Make sure a geometry record exists before updating the factor on it
+    window_state::persist(&window);
+    window_state::set_zoom_state(window.label(), zoom);
+
+    Ok(zoom)
+}
+
+/// Saved zoom factor for a window
+#[tauri::command]
+fn get_window_zoom(window: tauri::WebviewWindow) -> f64 {
+    window_state::zoom_state(window.label())
+}
+
+/// Step the zoom from the View menu; `None` resets to 100%
+fn step_window_zoom(window: &tauri::WebviewWindow, delta: Option<f64>) {
+    let target = match delta {
+        Some(delta) => window_state::zoom_state(window.label()) + delta,
+        None => 1.0,
+    };
+
+    if let Err(e) = set_window_zoom(window.clone(), target) {
+        eprintln!("[menu] Failed to change zoom: {}", e);
+    }
 }
 
 /// Command to set window to fixed size with min/max constraints (for welcome screen)
@@ -626,6 +1843,263 @@ fn set_window_fixed_size(window: tauri::Window, width: f64, height: f64) -> Resu
     window.center().map_err(|e| e.to_string())
 }
 
+// Labels of currently open detached windows, keyed by view name
+static DETACHED_WINDOWS: Lazy<Mutex<HashMap<String, String>>> = Lazy::new(|| {
+    Mutex::new(HashMap::new())
+});
+
+/// Open a view (logs, data browser, terminal, ...) in its own window so it can
+/// live on a second monitor while the main window shows something else.
+/// Re-invoking for an already-detached view focuses the existing window.
+#[tauri::command]
+async fn open_detached_window(
+    app: AppHandle,
+    view: String,
+    params: Option<HashMap<String, String>>,
+) -> Result<String, String> {
+    let label = format!("detached-{}", view.replace('/', "-"));
+
+    // Focus the existing window instead of opening a duplicate
+    if let Some(window) = app.get_webview_window(&label) {
+        window.show().map_err(|e| e.to_string())?;
+        window.set_focus().map_err(|e| e.to_string())?;
+        return Ok(label);
+    }
+
+    let query = params
+        .map(|p| {
+            p.iter()
+                .map(|(k, v)| format!("{}={}", k, urlencoding_encode(v)))
+                .collect::<Vec<_>>()
+                .join("&")
+        })
+        .filter(|q| !q.is_empty())
+        .map(|q| format!("?{}", q))
+        .unwrap_or_default();
+
+    let url = format!("index.html#/detached/{}{}", view, query);
+
+    let window = tauri::WebviewWindowBuilder::new(&app, &label, tauri::WebviewUrl::App(url.into()))
+        .title(format!("Convex Panel - {}", view))
+        .inner_size(1100.0, 700.0)
+        .min_inner_size(800.0, 500.0)
+        .build()
+        .map_err(|e| format!("Failed to create window: {}", e))?;
+
+    {
+        let mut windows = DETACHED_WINDOWS.lock().unwrap();
+        windows.insert(view.clone(), label.clone());
+    }
+
+    // Drop the tracking entry when the window goes away
+    let view_for_cleanup = view.clone();
+    window.on_window_event(move |event| {
+        if let tauri::WindowEvent::Destroyed = event {
+            let mut windows = DETACHED_WINDOWS.lock().unwrap();
+            windows.remove(&view_for_cleanup);
+        }
+    });
+
+    Ok(label)
+}
+
+const MINI_MONITOR_LABEL: &str = "mini-monitor";
+
+/// Open the compact always-on-top mini monitor window streaming live error
+/// counts and latest log lines, for keeping an eye on a deployment while the
+/// IDE has the screen
+#[tauri::command]
+async fn open_mini_monitor(app: AppHandle) -> Result<(), String> {
+    if let Some(window) = app.get_webview_window(MINI_MONITOR_LABEL) {
+        window.show().map_err(|e| e.to_string())?;
+        window.set_focus().map_err(|e| e.to_string())?;
+        return Ok(());
+    }
+
+    tauri::WebviewWindowBuilder::new(
+        &app,
+        MINI_MONITOR_LABEL,
+        tauri::WebviewUrl::App("index.html#/mini-monitor".into()),
+    )
+    .title("Convex Monitor")
+    .inner_size(340.0, 180.0)
+    .min_inner_size(280.0, 140.0)
+    .decorations(false)
+    .always_on_top(true)
+    .skip_taskbar(true)
+    .build()
+    .map_err(|e| format!("Failed to create mini monitor window: {}", e))?;
+
+    Ok(())
+}
+
+/// Close the mini monitor window, if open
+#[tauri::command]
+fn close_mini_monitor(app: AppHandle) -> Result<(), String> {
+    match app.get_webview_window(MINI_MONITOR_LABEL) {
+        Some(window) => window.close().map_err(|e| e.to_string()),
+        None => Ok(()),
+    }
+}
+
+/// Minimal percent-encoding for detached window query params
+fn urlencoding_encode(value: &str) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+/// List views currently open in detached windows
+#[tauri::command]
+fn list_detached_windows() -> Vec<String> {
+    DETACHED_WINDOWS.lock().unwrap().keys().cloned().collect()
+}
+
+/// Close the detached window for a view, if open
+#[tauri::command]
+fn close_detached_window(app: AppHandle, view: String) -> Result<(), String> {
+    let label = {
+        let windows = DETACHED_WINDOWS.lock().unwrap();
+        windows.get(&view).cloned()
+    };
+
+    match label.and_then(|l| app.get_webview_window(&l)) {
+        Some(window) => window.close().map_err(|e| e.to_string()),
+        None => Err(format!("No detached window for view: {}", view)),
+    }
+}
+
+/// Snap the window to a preset region of the current monitor's work area,
+/// so the panel can be docked beside an editor with one command
+#[tauri::command]
+fn snap_window(window: tauri::WebviewWindow, position: String) -> Result<(), String> {
+    let monitor = window
+        .current_monitor()
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| "Could not determine current monitor".to_string())?;
+
+    let work_area = monitor.work_area();
+    let area_x = work_area.position.x;
+    let area_y = work_area.position.y;
+    let area_w = work_area.size.width as i32;
+    let area_h = work_area.size.height as i32;
+
+    let half_w = area_w / 2;
+    let half_h = area_h / 2;
+
+    // (x, y, width, height) within the work area
+    let (x, y, w, h) = match position.as_str() {
+        "left-half" => (area_x, area_y, half_w, area_h),
+        "right-half" => (area_x + half_w, area_y, area_w - half_w, area_h),
+        "top-half" => (area_x, area_y, area_w, half_h),
+        "bottom-half" => (area_x, area_y + half_h, area_w, area_h - half_h),
+        "top-left-quarter" => (area_x, area_y, half_w, half_h),
+        "top-right-quarter" => (area_x + half_w, area_y, area_w - half_w, half_h),
+        "bottom-left-quarter" => (area_x, area_y + half_h, half_w, area_h - half_h),
+        "bottom-right-quarter" => {
+            (area_x + half_w, area_y + half_h, area_w - half_w, area_h - half_h)
+        }
+        "full" => (area_x, area_y, area_w, area_h),
+        other => return Err(format!("Unknown snap position: {}", other)),
+    };
+
+    // Snapping must override any fixed-size constraints still in place
+    window.set_resizable(true).map_err(|e| e.to_string())?;
+    window.set_min_size(None::<tauri::LogicalSize<f64>>).map_err(|e| e.to_string())?;
+    window.set_max_size(None::<tauri::LogicalSize<f64>>).map_err(|e| e.to_string())?;
+
+    window
+        .set_size(tauri::PhysicalSize::new(w as u32, h as u32))
+        .map_err(|e| e.to_string())?;
+    window
+        .set_position(tauri::PhysicalPosition::new(x, y))
+        .map_err(|e| e.to_string())
+}
+
+/// Solid background colors per theme, matching the app chrome
+fn theme_background_rgb(theme: &str) -> (f64, f64, f64) {
+    match theme {
+        "light" => (246.0, 245.0, 244.0),
+        _ => (42.0, 40.0, 37.0),
+    }
+}
+
+/// Set a solid NSWindow background color (used when vibrancy is off)
+#[cfg(target_os = "macos")]
+fn apply_macos_background_color(window: &tauri::WebviewWindow, rgb: (f64, f64, f64)) {
+    use objc2_app_kit::NSColor;
+    use objc2::runtime::AnyObject;
+    use objc2::msg_send;
+
+    if let Ok(ns_window_ptr) = window.ns_window() {
+        unsafe {
+            let bg_color = NSColor::colorWithRed_green_blue_alpha(
+                rgb.0 / 255.0,
+                rgb.1 / 255.0,
+                rgb.2 / 255.0,
+                1.0,
+            );
+
+            let ns_window: *mut AnyObject = ns_window_ptr as *mut AnyObject;
+            let _: () = msg_send![ns_window, setBackgroundColor: &*bg_color];
+        }
+    }
+}
+
+/// Enable or disable native window translucency (macOS vibrancy / Windows
+/// acrylic). When disabled, a per-theme solid background color is applied.
+#[tauri::command]
+fn set_window_vibrancy(
+    window: tauri::WebviewWindow,
+    enabled: bool,
+    theme: Option<String>,
+) -> Result<(), String> {
+    let theme = theme.unwrap_or_else(|| "dark".to_string());
+
+    #[cfg(target_os = "macos")]
+    {
+        use window_vibrancy::{apply_vibrancy, clear_vibrancy, NSVisualEffectMaterial};
+
+        if enabled {
+            apply_vibrancy(&window, NSVisualEffectMaterial::UnderWindowBackground, None, None)
+                .map_err(|e| format!("Failed to apply vibrancy: {}", e))?;
+        } else {
+            clear_vibrancy(&window).map_err(|e| format!("Failed to clear vibrancy: {}", e))?;
+            apply_macos_background_color(&window, theme_background_rgb(&theme));
+        }
+
+        Ok(())
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        use window_vibrancy::{apply_acrylic, clear_acrylic};
+
+        if enabled {
+            let (r, g, b) = theme_background_rgb(&theme);
+            apply_acrylic(&window, Some((r as u8, g as u8, b as u8, 125)))
+                .map_err(|e| format!("Failed to apply acrylic: {}", e))?;
+        } else {
+            clear_acrylic(&window).map_err(|e| format!("Failed to clear acrylic: {}", e))?;
+        }
+
+        Ok(())
+    }
+
+    #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+    {
+        let _ = (window, enabled, theme);
+        Err("Window vibrancy is not supported on this platform".to_string())
+    }
+}
+
 /// Command to remove window size constraints and make it resizable (for main app)
 #[tauri::command]
 fn remove_window_constraints(window: tauri::Window) -> Result<(), String> {
@@ -648,46 +2122,675 @@ async fn select_directory() -> Result<Option<String>, String> {
     Ok(None)
 }
 
-/// List files in a directory
+/// One entry from `list_directory_files`, path relative to the listed root
+#[derive(Clone, serde::Serialize)]
+struct DirectoryEntry {
+    path: String,
+    is_dir: bool,
+    size: u64,
+    modified_ms: Option<i64>,
+}
+
+/// List a directory respecting .gitignore (node_modules and .git are always
+/// skipped), with an optional glob pattern matched against relative paths
 #[tauri::command]
-fn list_directory_files(path: String, pattern: Option<String>) -> Result<Vec<String>, String> {
-    use walkdir::WalkDir;
-    
-    let path = std::path::Path::new(&path);
-    if !path.exists() {
+fn list_directory_files(
+    path: String,
+    pattern: Option<String>,
+    max_depth: Option<usize>,
+    include_ignored: Option<bool>,
+) -> Result<Vec<DirectoryEntry>, String> {
+    use ignore::WalkBuilder;
+
+    let root = std::path::PathBuf::from(&path);
+    if !root.exists() {
         return Err("Directory does not exist".to_string());
     }
-    
-    let mut files = Vec::new();
-    for entry in WalkDir::new(path)
-        .max_depth(5)
-        .into_iter()
-        .filter_map(|e| e.ok())
+
+    let matcher = pattern
+        .map(|p| {
+            globset::Glob::new(&p)
+                .map(|g| g.compile_matcher())
+                .map_err(|e| format!("Invalid glob pattern: {}", e))
+        })
+        .transpose()?;
+
+    let mut builder = WalkBuilder::new(&root);
+    builder.max_depth(Some(max_depth.unwrap_or(5)));
+
+    if include_ignored.unwrap_or(false) {
+        // Still never descend into .git, but surface ignored files
+        builder
+            .git_ignore(false)
+            .git_exclude(false)
+            .ignore(false)
+            .hidden(false);
+        builder.filter_entry(|entry| entry.file_name() != ".git");
+    } else {
+        builder.filter_entry(|entry| {
+            entry.file_name() != ".git" && entry.file_name() != "node_modules"
+        });
+    }
+
+    let mut entries = Vec::new();
+    for entry in builder.build().filter_map(|e| e.ok()) {
+        // Skip the root itself
+        if entry.path() == root {
+            continue;
+        }
+
+        let relative = match entry.path().strip_prefix(&root) {
+            Ok(relative) => relative.display().to_string(),
+            Err(_) => continue,
+        };
+
+        if let Some(ref matcher) = matcher {
+            if !matcher.is_match(&relative) {
+                continue;
+            }
+        }
+
+        let metadata = entry.metadata().ok();
+        let is_dir = entry.file_type().map(|t| t.is_dir()).unwrap_or(false);
+
+        entries.push(DirectoryEntry {
+            path: relative,
+            is_dir,
+            size: metadata.as_ref().map(|m| m.len()).unwrap_or(0),
+            modified_ms: metadata.and_then(|m| m.modified().ok()).and_then(|t| {
+                t.duration_since(std::time::UNIX_EPOCH)
+                    .ok()
+                    .map(|d| d.as_millis() as i64)
+            }),
+        });
+    }
+
+    Ok(entries)
+}
+
+// Active convex/ directory watchers and their pending debounced changes
+static CONVEX_WATCHERS: Lazy<Mutex<HashMap<String, notify::RecommendedWatcher>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+static CONVEX_PENDING: Lazy<Mutex<HashMap<String, Vec<ConvexFileChange>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// One debounced change inside a watched convex/ directory
+#[derive(Clone, PartialEq, serde::Serialize)]
+struct ConvexFileChange {
+    path: String,
+    /// "created" | "modified" | "deleted"
+    kind: String,
+}
+
+/// Whether a path inside convex/ is a function source file worth reporting
+fn is_convex_function_file(path: &std::path::Path) -> bool {
+    if path
+        .components()
+        .any(|c| c.as_os_str() == "_generated" || c.as_os_str() == "node_modules")
     {
-        if entry.file_type().is_file() {
-            let file_name = entry.file_name().to_string_lossy().to_string();
-            
-            // Apply pattern filter if provided
-            if let Some(ref pat) = pattern {
-                if !file_name.ends_with(pat) && !file_name.contains(pat) {
+        return false;
+    }
+
+    matches!(
+        path.extension().and_then(|e| e.to_str()),
+        Some("ts") | Some("tsx") | Some("js") | Some("jsx")
+    )
+}
+
+/// Watch a project's convex/ directory and emit debounced
+/// `convex-dir-changed` events so the function list can refresh without
+/// polling `list_directory_files`
+#[tauri::command]
+fn watch_convex_dir(app: AppHandle, project_path: String) -> Result<(), String> {
+    use notify::{EventKind, RecursiveMode, Watcher};
+
+    if CONVEX_WATCHERS.lock().unwrap().contains_key(&project_path) {
+        return Ok(());
+    }
+
+    let convex_dir = std::path::Path::new(&project_path).join("convex");
+    if !convex_dir.exists() {
+        return Err(format!("No convex/ directory in {}", project_path));
+    }
+
+    let pending_key = project_path.clone();
+    let mut watcher = notify::recommended_watcher(
+        move |event: Result<notify::Event, notify::Error>| {
+            let event = match event {
+                Ok(event) => event,
+                Err(_) => return,
+            };
+
+            let kind = match event.kind {
+                EventKind::Create(_) => "created",
+                EventKind::Remove(_) => "deleted",
+                EventKind::Modify(_) => "modified",
+                _ => return,
+            };
+
+            let mut pending = CONVEX_PENDING.lock().unwrap();
+            let changes = pending.entry(pending_key.clone()).or_default();
+
+            for path in &event.paths {
+                if !is_convex_function_file(path) {
                     continue;
                 }
+                let change = ConvexFileChange {
+                    path: path.display().to_string(),
+                    kind: kind.to_string(),
+                };
+                if !changes.contains(&change) {
+                    changes.push(change);
+                }
             }
-            
-            if let Ok(relative) = entry.path().strip_prefix(path) {
-                files.push(relative.display().to_string());
+        },
+    )
+    .map_err(|e| format!("Failed to create watcher: {}", e))?;
+
+    watcher
+        .watch(&convex_dir, RecursiveMode::Recursive)
+        .map_err(|e| format!("Failed to watch {}: {}", convex_dir.display(), e))?;
+
+    CONVEX_WATCHERS
+        .lock()
+        .unwrap()
+        .insert(project_path.clone(), watcher);
+
+    // Debounce loop: flush accumulated changes every half second until the
+    // watcher is removed
+    tauri::async_runtime::spawn(async move {
+        loop {
+            tokio::time::sleep(Duration::from_millis(500)).await;
+
+            if !CONVEX_WATCHERS.lock().unwrap().contains_key(&project_path) {
+                CONVEX_PENDING.lock().unwrap().remove(&project_path);
+                return;
+            }
+
+            let changes = CONVEX_PENDING
+                .lock()
+                .unwrap()
+                .get_mut(&project_path)
+                .map(std::mem::take)
+                .unwrap_or_default();
+
+            if !changes.is_empty() {
+                // Keep the source index in step with the files on disk
+                for change in &changes {
+                    source_index::refresh_file(
+                        &project_path,
+                        &change.path,
+                        change.kind == "deleted",
+                    );
+                }
+
+                let _ = app.emit(
+                    "convex-dir-changed",
+                    serde_json::json!({
+                        "projectPath": project_path,
+                        "changes": changes,
+                    }),
+                );
             }
         }
+    });
+
+    Ok(())
+}
+
+/// Stop watching a project's convex/ directory
+#[tauri::command]
+fn unwatch_convex_dir(project_path: String) -> Result<(), String> {
+    CONVEX_WATCHERS.lock().unwrap().remove(&project_path);
+    Ok(())
+}
+
+const MAX_PROJECT_WRITE_BYTES: usize = 5 * 1024 * 1024;
+
+/// Write a file inside the project root, for features like "apply suggested
+/// schema change". The relative path is confined to the root, writes are
+/// size-limited and atomic, and the previous version is backed up first.
+#[tauri::command]
+fn write_project_file(
+    project_root: String,
+    path: String,
+    contents: String,
+    create_dirs: Option<bool>,
+) -> Result<(), String> {
+    use std::path::{Component, Path};
+
+    if contents.len() > MAX_PROJECT_WRITE_BYTES {
+        return Err(format!(
+            "File too large to write ({} bytes, max {})",
+            contents.len(),
+            MAX_PROJECT_WRITE_BYTES
+        ));
+    }
+
+    let root = Path::new(&project_root)
+        .canonicalize()
+        .map_err(|e| format!("Invalid project root: {}", e))?;
+
+    // Confinement: only plain relative components, no absolute paths or `..`
+    let relative = Path::new(&path);
+    if relative.is_absolute()
+        || relative
+            .components()
+            .any(|c| !matches!(c, Component::Normal(_)))
+    {
+        return Err(format!("Path escapes the project root: {}", path));
+    }
+
+    let target = root.join(relative);
+
+    if let Some(parent) = target.parent() {
+        if !parent.exists() {
+            if create_dirs.unwrap_or(false) {
+                std::fs::create_dir_all(parent)
+                    .map_err(|e| format!("Failed to create directories: {}", e))?;
+            } else {
+                return Err(format!("Directory does not exist: {}", parent.display()));
+            }
+        }
+    }
+
+    // Back up the previous version before overwriting
+    if target.exists() {
+        let home = std::env::var("HOME")
+            .or_else(|_| std::env::var("USERPROFILE"))
+            .map_err(|_| "Failed to get home directory")?;
+        let backups = std::path::PathBuf::from(home)
+            .join(".convex-panel")
+            .join("file-backups");
+        std::fs::create_dir_all(&backups)
+            .map_err(|e| format!("Failed to create backup directory: {}", e))?;
+
+        let name = target
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| "file".to_string());
+        let backup = backups.join(format!(
+            "{}-{}",
+            chrono::Utc::now().timestamp_millis(),
+            name
+        ));
+        std::fs::copy(&target, &backup)
+            .map_err(|e| format!("Failed to back up file: {}", e))?;
+    }
+
+    // Atomic write: temp file in the same directory, then rename
+    let temp = target.with_extension("convex-panel-tmp");
+    std::fs::write(&temp, &contents).map_err(|e| format!("Failed to write file: {}", e))?;
+    std::fs::rename(&temp, &target).map_err(|e| {
+        let _ = std::fs::remove_file(&temp);
+        format!("Failed to write file: {}", e)
+    })
+}
+
+/// Default cap on how much of a file a single read returns (2 MB). Large
+/// generated files are paged in with `offset`/`length` instead.
+const MAX_PROJECT_READ_BYTES: usize = 2 * 1024 * 1024;
+
+/// Result of reading a project file
+#[derive(serde::Serialize)]
+struct ProjectFileContents {
+    content: String,
+    /// Detected source encoding ("utf-8", "utf-16le", "utf-16be", "latin-1")
+    encoding: String,
+    /// Total file size in bytes
+    size: u64,
+    /// Byte offset this read started at
+    offset: u64,
+    /// True when the file extends beyond the returned range
+    truncated: bool,
+    /// True when the file looks binary; content is empty in that case
+    binary: bool,
+}
+
+/// NUL byte in the sample is our binary heuristic (same as git's)
+fn looks_binary(bytes: &[u8]) -> bool {
+    bytes.iter().take(8192).any(|&b| b == 0)
+}
+
+/// Decode bytes, detecting UTF-16 via BOM and falling back to latin-1 when
+/// the content is not valid UTF-8
+fn decode_file_bytes(bytes: &[u8]) -> (String, String) {
+    if bytes.starts_with(&[0xFF, 0xFE]) {
+        let (content, _, _) = encoding_rs::UTF_16LE.decode(&bytes[2..]);
+        return (content.into_owned(), "utf-16le".to_string());
+    }
+    if bytes.starts_with(&[0xFE, 0xFF]) {
+        let (content, _, _) = encoding_rs::UTF_16BE.decode(&bytes[2..]);
+        return (content.into_owned(), "utf-16be".to_string());
+    }
+
+    let without_bom = bytes.strip_prefix(&[0xEF, 0xBB, 0xBF][..]).unwrap_or(bytes);
+    match std::str::from_utf8(without_bom) {
+        Ok(content) => (content.to_string(), "utf-8".to_string()),
+        Err(_) => {
+            let (content, _, _) = encoding_rs::WINDOWS_1252.decode(without_bom);
+            (content.into_owned(), "latin-1".to_string())
+        }
+    }
+}
+
+/// Read a file's contents with a size guard, binary detection, and encoding
+/// detection. `offset`/`length` select a byte range for paging through files
+/// larger than the default cap.
+#[tauri::command]
+fn read_project_file(
+    path: String,
+    offset: Option<u64>,
+    length: Option<usize>,
+) -> Result<ProjectFileContents, String> {
+    use std::io::{Read, Seek, SeekFrom};
+
+    let size = std::fs::metadata(&path)
+        .map_err(|e| format!("Failed to read file: {}", e))?
+        .len();
+
+    let offset = offset.unwrap_or(0).min(size);
+    let length = length.unwrap_or(MAX_PROJECT_READ_BYTES).min(MAX_PROJECT_READ_BYTES);
+
+    let mut file = std::fs::File::open(&path)
+        .map_err(|e| format!("Failed to read file: {}", e))?;
+    file.seek(SeekFrom::Start(offset))
+        .map_err(|e| format!("Failed to read file: {}", e))?;
+
+    let mut bytes = Vec::with_capacity(length.min((size - offset) as usize));
+    file.take(length as u64)
+        .read_to_end(&mut bytes)
+        .map_err(|e| format!("Failed to read file: {}", e))?;
+
+    let truncated = offset + bytes.len() as u64 < size;
+
+    if looks_binary(&bytes) {
+        return Ok(ProjectFileContents {
+            content: String::new(),
+            encoding: "binary".to_string(),
+            size,
+            offset,
+            truncated,
+            binary: true,
+        });
+    }
+
+    let (content, encoding) = decode_file_bytes(&bytes);
+    Ok(ProjectFileContents {
+        content,
+        encoding,
+        size,
+        offset,
+        truncated,
+        binary: false,
+    })
+}
+
+/// Dependency information parsed from a project's package.json
+#[derive(serde::Serialize)]
+struct ProjectDependencies {
+    /// Package manager detected from lockfiles / the packageManager field
+    package_manager: String,
+    /// Version range for `convex` declared in package.json, if any
+    convex_range: Option<String>,
+    /// Installed `convex` version from node_modules, if present
+    convex_installed: Option<String>,
+    /// npm scripts (name -> command)
+    scripts: HashMap<String, String>,
+}
+
+/// Package manager for a project, preferring the explicit packageManager
+/// field over lockfile detection
+fn detect_package_manager(root: &std::path::Path, package_json: &serde_json::Value) -> String {
+    if let Some(pm) = package_json.get("packageManager").and_then(|v| v.as_str()) {
+        // Field looks like "pnpm@9.1.0"
+        if let Some(name) = pm.split('@').next() {
+            if !name.is_empty() {
+                return name.to_string();
+            }
+        }
+    }
+
+    for (lockfile, manager) in [
+        ("pnpm-lock.yaml", "pnpm"),
+        ("yarn.lock", "yarn"),
+        ("bun.lockb", "bun"),
+        ("bun.lock", "bun"),
+        ("package-lock.json", "npm"),
+    ] {
+        if root.join(lockfile).exists() {
+            return manager.to_string();
+        }
+    }
+
+    "npm".to_string()
+}
+
+/// Parse a project's package.json to report the convex dependency, scripts,
+/// and package manager
+#[tauri::command]
+fn get_project_dependencies(path: String) -> Result<ProjectDependencies, String> {
+    let root = std::path::Path::new(&path);
+
+    let content = std::fs::read_to_string(root.join("package.json"))
+        .map_err(|e| format!("Failed to read package.json: {}", e))?;
+    let package_json: serde_json::Value = serde_json::from_str(&content)
+        .map_err(|e| format!("Failed to parse package.json: {}", e))?;
+
+    let convex_range = ["dependencies", "devDependencies"]
+        .iter()
+        .find_map(|section| {
+            package_json
+                .get(section)
+                .and_then(|deps| deps.get("convex"))
+                .and_then(|v| v.as_str())
+                .map(|v| v.to_string())
+        });
+
+    // The installed version comes from node_modules, which is more accurate
+    // than resolving the declared range
+    let convex_installed = std::fs::read_to_string(
+        root.join("node_modules").join("convex").join("package.json"),
+    )
+    .ok()
+    .and_then(|content| serde_json::from_str::<serde_json::Value>(&content).ok())
+    .and_then(|pkg| pkg.get("version").and_then(|v| v.as_str()).map(String::from));
+
+    let scripts = package_json
+        .get("scripts")
+        .and_then(|v| v.as_object())
+        .map(|scripts| {
+            scripts
+                .iter()
+                .filter_map(|(name, cmd)| {
+                    cmd.as_str().map(|c| (name.clone(), c.to_string()))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    Ok(ProjectDependencies {
+        package_manager: detect_package_manager(root, &package_json),
+        convex_range,
+        convex_installed,
+        scripts,
+    })
+}
+
+/// One package in a workspace that contains a Convex app
+#[derive(serde::Serialize)]
+struct WorkspaceApp {
+    /// Package name from its package.json
+    name: String,
+    path: String,
+    /// Path relative to the workspace root ("." for the root package)
+    relative_path: String,
+    /// Version range for `convex` declared by the package, if any
+    convex_range: Option<String>,
+}
+
+/// Result of scanning a directory for workspaces and Convex apps
+#[derive(serde::Serialize)]
+struct WorkspaceScan {
+    root: String,
+    package_manager: String,
+    /// True when the root declares workspaces (pnpm/yarn/npm) or has turbo.json
+    is_monorepo: bool,
+    apps: Vec<WorkspaceApp>,
+}
+
+/// Workspace package globs from pnpm-workspace.yaml or the package.json
+/// `workspaces` field
+fn workspace_globs(root: &std::path::Path, package_json: &serde_json::Value) -> Vec<String> {
+    // pnpm-workspace.yaml: a flat `packages:` list is the only shape we need
+    if let Ok(content) = std::fs::read_to_string(root.join("pnpm-workspace.yaml")) {
+        let globs: Vec<String> = content
+            .lines()
+            .map(|line| line.trim())
+            .filter_map(|line| line.strip_prefix("- "))
+            .map(|glob| glob.trim_matches(|c| c == '"' || c == '\'').to_string())
+            .filter(|glob| !glob.is_empty())
+            .collect();
+        if !globs.is_empty() {
+            return globs;
+        }
+    }
+
+    // yarn/npm: "workspaces": [...] or "workspaces": { "packages": [...] }
+    let workspaces = package_json.get("workspaces");
+    let packages = match workspaces {
+        Some(serde_json::Value::Array(list)) => Some(list),
+        Some(serde_json::Value::Object(obj)) => obj.get("packages").and_then(|v| v.as_array()),
+        _ => None,
+    };
+
+    packages
+        .map(|list| {
+            list.iter()
+                .filter_map(|v| v.as_str().map(String::from))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Whether a package directory contains a Convex app, returning the declared
+/// convex version range when it comes from package.json
+fn convex_app_in(dir: &std::path::Path, package_json: &serde_json::Value) -> Option<Option<String>> {
+    let range = ["dependencies", "devDependencies"].iter().find_map(|section| {
+        package_json
+            .get(section)
+            .and_then(|deps| deps.get("convex"))
+            .and_then(|v| v.as_str())
+            .map(String::from)
+    });
+
+    if range.is_some() || dir.join("convex").is_dir() {
+        Some(range)
+    } else {
+        None
     }
-    
-    Ok(files)
 }
 
-/// Read a file's contents
+/// Scan a directory for Convex apps, understanding pnpm/yarn/turborepo
+/// workspaces so the project picker can list every app in a monorepo
 #[tauri::command]
-fn read_project_file(path: String) -> Result<String, String> {
-    std::fs::read_to_string(&path)
-        .map_err(|e| format!("Failed to read file: {}", e))
+fn scan_workspace(root: String) -> Result<WorkspaceScan, String> {
+    let root_path = std::path::Path::new(&root)
+        .canonicalize()
+        .map_err(|e| format!("Invalid workspace root: {}", e))?;
+
+    let root_package: serde_json::Value = std::fs::read_to_string(root_path.join("package.json"))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_else(|| serde_json::json!({}));
+
+    let globs = workspace_globs(&root_path, &root_package);
+    let is_monorepo = !globs.is_empty() || root_path.join("turbo.json").exists();
+
+    let mut apps = Vec::new();
+
+    // The root itself can be a Convex app (single-package repos, or a
+    // monorepo whose backend lives at the top level)
+    if let Some(convex_range) = convex_app_in(&root_path, &root_package) {
+        apps.push(WorkspaceApp {
+            name: root_package
+                .get("name")
+                .and_then(|v| v.as_str())
+                .unwrap_or("root")
+                .to_string(),
+            path: root_path.to_string_lossy().to_string(),
+            relative_path: ".".to_string(),
+            convex_range,
+        });
+    }
+
+    if !globs.is_empty() {
+        let mut builder = globset::GlobSetBuilder::new();
+        for glob in &globs {
+            if let Ok(g) = globset::Glob::new(glob) {
+                builder.add(g);
+            }
+        }
+        let matcher = builder
+            .build()
+            .map_err(|e| format!("Invalid workspace globs: {}", e))?;
+
+        let walker = ignore::WalkBuilder::new(&root_path)
+            .max_depth(Some(4))
+            .filter_entry(|entry| {
+                entry
+                    .file_name()
+                    .to_str()
+                    .map(|name| name != ".git" && name != "node_modules")
+                    .unwrap_or(true)
+            })
+            .build();
+
+        for entry in walker.flatten() {
+            let path = entry.path();
+            if !path.is_dir() || path == root_path {
+                continue;
+            }
+            let relative = match path.strip_prefix(&root_path) {
+                Ok(rel) => rel,
+                Err(_) => continue,
+            };
+            if !matcher.is_match(relative) {
+                continue;
+            }
+
+            let package_json: serde_json::Value =
+                match std::fs::read_to_string(path.join("package.json"))
+                    .ok()
+                    .and_then(|content| serde_json::from_str(&content).ok())
+                {
+                    Some(pkg) => pkg,
+                    None => continue,
+                };
+
+            if let Some(convex_range) = convex_app_in(path, &package_json) {
+                apps.push(WorkspaceApp {
+                    name: package_json
+                        .get("name")
+                        .and_then(|v| v.as_str())
+                        .unwrap_or(&relative.to_string_lossy())
+                        .to_string(),
+                    path: path.to_string_lossy().to_string(),
+                    relative_path: relative.to_string_lossy().to_string(),
+                    convex_range,
+                });
+            }
+        }
+    }
+
+    apps.sort_by(|a, b| a.relative_path.cmp(&b.relative_path));
+
+    Ok(WorkspaceScan {
+        root: root_path.to_string_lossy().to_string(),
+        package_manager: detect_package_manager(&root_path, &root_package),
+        is_monorepo,
+        apps,
+    })
 }
 
 /// Write or update an environment variable in a .env file
@@ -697,52 +2800,21 @@ fn read_project_file(path: String) -> Result<String, String> {
 fn write_env_variable(file_path: String, key: String, value: String) -> Result<(), String> {
     use std::fs;
     use std::path::Path;
-    
+
     let path = Path::new(&file_path);
-    
+
     // Read existing content or start with empty string
     let existing_content = if path.exists() {
         fs::read_to_string(path).unwrap_or_default()
     } else {
         String::new()
     };
-    
-    let mut lines: Vec<String> = existing_content.lines().map(|s| s.to_string()).collect();
-    let key_prefix = format!("{}=", key);
-    let new_line = format!("{}={}", key, value);
-    
-    // Check if the key already exists
-    let mut found = false;
-    for line in lines.iter_mut() {
-        let trimmed = line.trim();
-        // Match key= at start of line (ignoring comments)
-        if !trimmed.starts_with('#') && trimmed.starts_with(&key_prefix) {
-            *line = new_line.clone();
-            found = true;
-            break;
-        }
-    }
-    
-    // If key wasn't found, append it
-    if !found {
-        // Add a newline before if file doesn't end with one and has content
-        if !lines.is_empty() && !existing_content.ends_with('\n') {
-            lines.push(String::new());
-        }
-        lines.push(new_line);
-    }
-    
-    // Write back to file
-    let new_content = lines.join("\n");
-    // Ensure file ends with newline
-    let final_content = if new_content.ends_with('\n') {
-        new_content
-    } else {
-        format!("{}\n", new_content)
-    };
-    
-    fs::write(path, final_content)
-        .map_err(|e| format!("Failed to write file: {}", e))
+
+    let final_content = env_file::set_variable(&existing_content, &key, &value);
+
+    // Back up the previous version (for undo_env_change) and write atomically
+    env_file::backup_env_file(path)?;
+    env_file::write_env_file_atomic(path, &final_content)
 }
 
 /// Read an environment variable from a .env file
@@ -750,29 +2822,17 @@ fn write_env_variable(file_path: String, key: String, value: String) -> Result<(
 fn read_env_variable(file_path: String, key: String) -> Result<Option<String>, String> {
     use std::fs;
     use std::path::Path;
-    
+
     let path = Path::new(&file_path);
-    
+
     if !path.exists() {
         return Ok(None);
     }
-    
+
     let content = fs::read_to_string(path)
         .map_err(|e| format!("Failed to read file: {}", e))?;
-    
-    let key_prefix = format!("{}=", key);
-    
-    for line in content.lines() {
-        let trimmed = line.trim();
-        if !trimmed.starts_with('#') && trimmed.starts_with(&key_prefix) {
-            let value = trimmed[key_prefix.len()..].to_string();
-            // Remove surrounding quotes if present
-            let value = value.trim_matches('"').trim_matches('\'').to_string();
-            return Ok(Some(value));
-        }
-    }
-    
-    Ok(None)
+
+    Ok(env_file::get_value(&content, &key))
 }
 
 /// Open a file in an external editor (Cursor, VS Code, etc.)
@@ -870,31 +2930,141 @@ async fn check_editor_available(editor: String) -> Result<bool, String> {
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    // Capture panics into local crash reports before anything can crash
+    crash_reports::install_panic_hook();
+
     tauri::Builder::default()
         .plugin(tauri_plugin_http::init())
         .plugin(tauri_plugin_shell::init())
         .plugin(tauri_plugin_dialog::init())
         .plugin(tauri_plugin_fs::init())
         .plugin(tauri_plugin_notification::init())
+        .plugin(tauri_plugin_deep_link::init())
         .invoke_handler(tauri::generate_handler![
             expand_window, 
             set_window_size,
             set_window_size_centered,
             center_window,
+            move_to_monitor,
+            set_always_on_top,
+            set_window_zoom,
+            get_window_zoom,
+            set_window_vibrancy,
+            set_native_theme,
+            set_presentation_mode,
+            snap_window,
             set_window_fixed_size,
             remove_window_constraints,
+            open_detached_window,
+            list_detached_windows,
+            close_detached_window,
+            open_mini_monitor,
+            close_mini_monitor,
             secure_store::set_secret,
             secure_store::get_secret,
             secure_store::delete_secret,
+            // Convex client commands
+            convex_client::store_deploy_key,
+            convex_client::has_deploy_key,
+            convex_client::run_convex_function,
+            convex_client::list_convex_tables,
+            convex_client::get_deployment_info,
+            // Telemetry commands
+            telemetry::record_telemetry_event,
+            telemetry::get_telemetry_report,
+            telemetry::get_telemetry_opt_in,
+            telemetry::set_telemetry_opt_in,
+            telemetry::upload_telemetry,
+            // Crash report commands
+            crash_reports::list_crash_reports,
+            crash_reports::export_crash_report,
+            crash_reports::delete_crash_report,
+            crash_reports::upload_crash_report,
+            crash_reports::note_command_invocation,
+            // Plugin commands
+            plugins::list_plugins,
+            plugins::enable_plugin,
+            plugins::disable_plugin,
+            plugins::reload_plugins,
+            // Local API server commands
+            api_server::start_api_server,
+            api_server::stop_api_server,
+            api_server::get_api_server_status,
+            api_server::get_api_token,
+            // Webhook receiver commands
+            webhook_receiver::start_webhook_receiver,
+            webhook_receiver::stop_webhook_receiver,
+            webhook_receiver::get_webhook_receiver_status,
+            webhook_receiver::list_webhook_requests,
+            webhook_receiver::clear_webhook_requests,
+            webhook_receiver::replay_webhook_request,
+            // Background job supervisor commands
+            background_jobs::get_background_jobs,
+            background_jobs::set_background_job_enabled,
+            // Power monitor commands
+            power_monitor::get_power_settings,
+            power_monitor::set_power_settings,
+            power_monitor::get_power_status,
+            // Call replay commands
+            call_replay::capture_function_calls,
+            call_replay::replay_function_calls,
+            // Benchmark commands
+            benchmark::benchmark_function,
+            benchmark::get_benchmark_history,
+            benchmark::delete_benchmark_result,
+            // Cron monitor commands
+            cron_monitor::get_schedules,
+            cron_monitor::watch_crons,
+            cron_monitor::unwatch_crons,
+            cron_monitor::get_cron_health,
+            cron_monitor::export_cron_calendar,
+            // Deployment diff command
+            deployment_diff::diff_deployments,
+            // Deployment registry commands
+            deployments::list_deployments,
+            deployments::upsert_deployment,
+            deployments::mark_deployment_seen,
+            deployments::remove_deployment,
+            // Dev server discovery command
+            dev_discovery::discover_local_dev_servers,
             // File system commands
             select_directory,
             list_directory_files,
             read_project_file,
+            write_project_file,
+            watch_convex_dir,
+            unwatch_convex_dir,
             open_in_editor,
             check_editor_available,
+            get_project_dependencies,
+            scan_workspace,
+            // Source index commands
+            source_index::build_source_index,
+            source_index::list_indexed_functions,
+            source_index::resolve_function_source,
+            stack_resolver::resolve_stack,
+            // Workspace profile commands
+            workspace_profiles::list_workspace_profiles,
+            workspace_profiles::save_workspace_profile,
+            workspace_profiles::delete_workspace_profile,
+            workspace_profiles::switch_profile,
+            // Recent project commands
+            recent_projects::record_recent_project,
+            recent_projects::list_recent_projects,
+            recent_projects::remove_recent_project,
             // Env file commands
             write_env_variable,
             read_env_variable,
+            env_file::list_env_variables,
+            env_file::delete_env_variable,
+            env_file::watch_env_file,
+            env_file::unwatch_env_file,
+            env_file::diff_env,
+            env_file::sync_env,
+            env_file::resolve_env_files,
+            env_file::undo_env_change,
+            env_file::write_env_secret_ref,
+            env_file::resolve_env_secrets,
             // PTY commands
             pty::pty_spawn,
             pty::pty_write,
@@ -905,9 +3075,31 @@ pub fn run() {
             // Network status commands
             update_network_status,
             get_network_status,
+            set_active_network_deployment,
+            set_network_monitor_interval,
+            get_network_monitor_interval,
+            is_online,
+            is_headless,
+            trace_deployment_route,
+            export_network_report,
+            set_tray_deployments,
+            set_unread_alert_count,
+            // Updater commands
+            updater::check_for_updates,
+            updater::install_pending_update,
+            updater::get_update_status,
+            updater::set_update_channel,
+            // Keyboard shortcut commands
+            shortcuts::get_keyboard_shortcuts,
+            shortcuts::set_keyboard_shortcut,
+            shortcuts::reset_keyboard_shortcuts,
             // Generic HTTP bridge command
             http_fetch,
             // OAuth auth commands
+            oauth_server::start_oauth_server,
+            auth_tokens::auth_exchange_code,
+            auth_tokens::auth_refresh_now,
+            auth_tokens::auth_sign_out,
             auth_start_device_authorization,
             auth_poll_device_token,
             auth_exchange_dashboard_token,
@@ -922,34 +3114,198 @@ pub fn run() {
             log_store::ingest_logs,
             log_store::query_logs,
             log_store::search_logs,
+            log_store::search_logs_regex,
             log_store::get_log_by_id,
+            log_store::get_log_context,
+            log_store::get_log_lines,
             log_store::delete_logs_older_than,
+            log_store::set_deployment_retention,
+            log_store::list_deployment_retention,
+            log_store::list_log_archives,
+            log_store::import_log_archive,
+            log_store::import_logs,
             log_store::get_log_stats,
             log_store::get_log_store_settings,
             log_store::set_log_store_settings,
+            log_store::get_redaction_patterns,
+            log_store::set_redaction_patterns,
             log_store::clear_all_logs,
-            log_store::optimize_log_db
+            log_store::optimize_log_db,
+            log_store::histogram_logs,
+            log_store::get_duration_histogram,
+            log_store::get_insights,
+            log_store::get_error_groups,
+            // Log tag commands
+            log_store::tag_log,
+            log_store::untag_log,
+            log_store::star_log,
+            log_store::get_log_tags,
+            // Log annotation commands
+            log_store::set_log_note,
+            log_store::get_log_notes,
+            // Saved search commands
+            log_store::create_saved_search,
+            log_store::list_saved_searches,
+            log_store::delete_saved_search,
+            // Alert rule commands
+            log_store::create_alert_rule,
+            log_store::list_alert_rules,
+            log_store::set_alert_rule_enabled,
+            log_store::delete_alert_rule,
+            // Live tail subscription commands
+            log_store::subscribe_logs,
+            log_store::unsubscribe_logs,
+            log_store::list_log_subscriptions,
+            // Network history commands
+            log_store::record_network_samples,
+            log_store::get_network_history,
+            // Cost estimator commands
+            cost_estimator::get_pricing_assumptions,
+            cost_estimator::set_pricing_assumptions,
+            cost_estimator::estimate_usage,
+            // Log aggregation command
+            log_aggregation::aggregate_logs,
+            // OTLP trace export commands
+            otlp_export::export_otlp_traces,
+            otlp_export::get_otlp_config,
+            otlp_export::set_otlp_config,
+            // Usage analytics commands
+            usage_analytics::get_top_functions_by_identity,
+            usage_analytics::get_new_functions,
+            // Function metrics commands
+            metrics_store::rollup_function_metrics,
+            metrics_store::get_function_metrics,
+            metrics_store::get_function_metric_summaries,
+            // Settings sync commands
+            settings_sync::get_sync_config,
+            settings_sync::set_sync_config,
+            settings_sync::push_settings_sync,
+            settings_sync::pull_settings_sync,
+            // Session recording commands
+            session_recorder::start_session_recording,
+            session_recorder::record_session_terminal,
+            session_recorder::stop_session_recording,
+            session_recorder::load_session_recording,
+            // Table snapshot commands
+            table_snapshots::export_table_snapshot,
+            table_snapshots::list_table_snapshots,
+            table_snapshots::verify_table_snapshot,
+            table_snapshots::restore_snapshot,
+            table_snapshots::delete_table_snapshot,
+            table_import::import_table_data,
+            // Schema snapshot commands
+            schema_store::snapshot_schema,
+            schema_store::watch_schema,
+            schema_store::unwatch_schema,
+            schema_store::get_schema_history,
+            schema_store::get_schema_snapshot,
+            schema_store::diff_schema,
+            // Log streaming commands
+            log_stream::start_log_stream,
+            log_stream::stop_log_stream,
+            log_stream::list_log_streams
         ])
         .setup(|app| {
+            // Register the convex-panel:// scheme and route OAuth callback
+            // deep links into the same oauth-code event as the local server
+            {
+                use tauri_plugin_deep_link::DeepLinkExt;
+
+                #[cfg(any(windows, target_os = "linux"))]
+                app.deep_link().register_all()?;
+
+                let handle = app.handle().clone();
+                app.deep_link().on_open_url(move |event| {
+                    for url in event.urls() {
+                        // OAuth callbacks take priority; anything else is
+                        // treated as in-app navigation
+                        if !oauth_server::handle_deep_link(&handle, &url) {
+                            handle_navigation_deep_link(&handle, &url);
+                        }
+                    }
+                });
+            }
+
+            // The log store open and migrations below can take seconds on a
+            // large database; show a lightweight splash instead of leaving a
+            // frozen white webview on screen (main starts hidden). Headless
+            // agents never show a window at all.
+            let splash = if *HEADLESS {
+                None
+            } else {
+                Some(
+                    tauri::WebviewWindowBuilder::new(
+                        app,
+                        "splash",
+                        tauri::WebviewUrl::App("splash.html".into()),
+                    )
+                    .title("Convex Panel")
+                    .inner_size(420.0, 240.0)
+                    .resizable(false)
+                    .decorations(false)
+                    .always_on_top(true)
+                    .skip_taskbar(true)
+                    .center()
+                    .build()?,
+                )
+            };
+
             // Initialize log store database
             let db_conn = log_store::init_db(&app.handle())
                 .expect("Failed to initialize log store database");
             
             // Start retention scheduler
             log_store::start_retention_scheduler(db_conn.clone(), app.handle().clone());
-            
+
+            // Start periodic metric rollups
+            metrics_store::start_metrics_scheduler(db_conn.clone());
+
+            // Start any enabled plugins
+            plugins::start_enabled_plugins();
+
+            // Watch for sleep/wake and battery changes
+            power_monitor::start_power_monitor(app.handle().clone());
+
             // Store DB connection in app state
             app.manage(db_conn);
             
             let window = app.get_webview_window("main").unwrap();
 
-            // Set window size constraints for welcome screen (960x600 fixed)
-            let _ = window.set_min_size(Some(tauri::LogicalSize::new(960.0, 600.0)));
-            let _ = window.set_max_size(Some(tauri::LogicalSize::new(960.0, 600.0)));
+            // Restore the previous session's geometry; fall back to the fixed
+            // welcome-screen constraints (960x600) on first launch
+            if !window_state::restore(&window) {
+                let _ = window.set_min_size(Some(tauri::LogicalSize::new(960.0, 600.0)));
+                let _ = window.set_max_size(Some(tauri::LogicalSize::new(960.0, 600.0)));
+            }
+            window_state::attach_listeners(&window);
+
+            // Focusing the window counts as seeing the alerts: clear the badge
+            window.on_window_event(move |event| {
+                if let tauri::WindowEvent::Focused(true) = event {
+                    if UNREAD_ALERTS.swap(0, std::sync::atomic::Ordering::Relaxed) > 0 {
+                        refresh_tray_health();
+                    }
+                }
+            });
+
+            // Swap the splash for the fully initialized main window; a
+            // headless agent stays hidden and opens its control socket
+            if let Some(splash) = splash {
+                let _ = window.show();
+                let _ = window.set_focus();
+                let _ = splash.close();
+            } else {
+                start_control_socket(app.handle().clone());
+            }
 
             // Create custom menu
-            let about_item = MenuItem::with_id(app, "about", "About Convex Panel", true, None::<&str>)?;
-            let settings_item = MenuItem::with_id(app, "settings", "Settings...", true, Some("CmdOrCtrl+,"))?;
+            // Accelerators come from the user-editable shortcut map
+            let about_item = MenuItem::with_id(app, "about", "About Convex Panel", true, shortcuts::accelerator_for("about").as_deref())?;
+            let check_updates_item = MenuItem::with_id(app, "check_updates", "Check for Updates...", true, shortcuts::accelerator_for("check_updates").as_deref())?;
+            let settings_item = MenuItem::with_id(app, "settings", "Settings...", true, shortcuts::accelerator_for("settings").as_deref())?;
+            shortcuts::register_item("about", &about_item);
+            shortcuts::register_item("check_updates", &check_updates_item);
+            shortcuts::register_item("settings", &settings_item);
             let separator1 = PredefinedMenuItem::separator(app)?;
             let hide = PredefinedMenuItem::hide(app, Some("Hide Convex Panel"))?;
             let hide_others = PredefinedMenuItem::hide_others(app, Some("Hide Others"))?;
@@ -963,6 +3319,7 @@ pub fn run() {
                 true,
                 &[
                     &about_item,
+                    &check_updates_item,
                     &separator1,
                     &settings_item,
                     &separator2,
@@ -1001,7 +3358,22 @@ pub fn run() {
             // View menu
             let fullscreen = PredefinedMenuItem::fullscreen(app, None)?;
             let minimize = PredefinedMenuItem::minimize(app, None)?;
-            
+            let always_on_top_item = CheckMenuItem::with_id(
+                app,
+                "always_on_top",
+                "Always on Top",
+                true,
+                window_state::always_on_top_state("main"),
+                shortcuts::accelerator_for("always_on_top").as_deref(),
+            )?;
+
+            let zoom_in_item = MenuItem::with_id(app, "zoom_in", "Zoom In", true, shortcuts::accelerator_for("zoom_in").as_deref())?;
+            let zoom_out_item = MenuItem::with_id(app, "zoom_out", "Zoom Out", true, shortcuts::accelerator_for("zoom_out").as_deref())?;
+            let zoom_reset_item = MenuItem::with_id(app, "zoom_reset", "Actual Size", true, shortcuts::accelerator_for("zoom_reset").as_deref())?;
+            shortcuts::register_item("zoom_in", &zoom_in_item);
+            shortcuts::register_item("zoom_out", &zoom_out_item);
+            shortcuts::register_item("zoom_reset", &zoom_reset_item);
+
             let view_menu = Submenu::with_items(
                 app,
                 "View",
@@ -1009,6 +3381,12 @@ pub fn run() {
                 &[
                     &fullscreen,
                     &minimize,
+                    &PredefinedMenuItem::separator(app)?,
+                    &zoom_in_item,
+                    &zoom_out_item,
+                    &zoom_reset_item,
+                    &PredefinedMenuItem::separator(app)?,
+                    &always_on_top_item,
                 ],
             )?;
 
@@ -1032,8 +3410,18 @@ pub fn run() {
 
             // Handle menu events
             let window_clone = window.clone();
-            app.on_menu_event(move |_app, event| {
+            let always_on_top_for_menu = always_on_top_item.clone();
+            app.on_menu_event(move |app, event| {
                 match event.id().as_ref() {
+                    "check_updates" => {
+                        handle_check_updates(app.clone());
+                    }
+                    "always_on_top" => {
+                        let checked = always_on_top_for_menu.is_checked().unwrap_or(false);
+                        if let Err(e) = set_always_on_top(window_clone.clone(), checked) {
+                            eprintln!("[menu] Failed to toggle always-on-top: {}", e);
+                        }
+                    }
                     "about" => {
                         // Show native About dialog
                         #[cfg(target_os = "macos")]
@@ -1046,15 +3434,31 @@ pub fn run() {
                                 show_native_about_windows(hwnd.0 as isize);
                             }
                         }
-                        #[cfg(not(any(target_os = "macos", target_os = "windows")))]
+                        #[cfg(target_os = "linux")]
+                        {
+                            // Native GTK dialog via zenity, webview fallback
+                            if !show_native_about_linux() {
+                                let _ = window_clone.emit("show-about", ());
+                            }
+                        }
+                        #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
                         {
-                            // Fallback to React component for Linux
+                            // Fallback to React component on other platforms
                             let _ = window_clone.emit("show-about", ());
                         }
                     }
                     "settings" => {
                         let _ = window_clone.emit("show-settings", ());
                     }
+                    "zoom_in" => {
+                        step_window_zoom(&window_clone, Some(ZOOM_STEP));
+                    }
+                    "zoom_out" => {
+                        step_window_zoom(&window_clone, Some(-ZOOM_STEP));
+                    }
+                    "zoom_reset" => {
+                        step_window_zoom(&window_clone, None);
+                    }
                     _ => {}
                 }
             });
@@ -1079,7 +3483,39 @@ pub fn run() {
             
             // Load menu icon for "Show Convex Panel" item
             let menu_icon = include_image!("icons/menu-icon.png");
-            
+
+            // Deployment switcher submenu, rebuilt as deployments come and go
+            let deployment_submenu = Submenu::with_id(app, "deployments", "Deployments", true)?;
+            {
+                let mut menu_handle = TRAY_DEPLOYMENT_MENU.lock().unwrap();
+                *menu_handle = Some(deployment_submenu.clone());
+            }
+            rebuild_deployment_submenu(app.handle());
+
+            // Recent deployment pushes submenu
+            let recent_pushes_submenu = Submenu::with_id(app, "recent_pushes", "Recent Pushes", true)?;
+            {
+                let mut menu_handle = TRAY_RECENT_PUSHES_MENU.lock().unwrap();
+                *menu_handle = Some(recent_pushes_submenu.clone());
+            }
+            rebuild_recent_pushes_submenu(app.handle());
+
+            // Unread alert count, mirrored by the badge on the tray icon
+            let tray_alerts_item =
+                MenuItem::with_id(app, "unread_alerts", "No unread alerts", true, None::<&str>)?;
+            {
+                let mut item_handle = TRAY_ALERTS_ITEM.lock().unwrap();
+                *item_handle = Some(tray_alerts_item.clone());
+            }
+
+            // Tray entry doubling as update check trigger and pending indicator
+            let tray_update_item =
+                MenuItem::with_id(app, "check_updates", "Check for Updates...", true, None::<&str>)?;
+            {
+                let mut item_handle = TRAY_UPDATE_ITEM.lock().unwrap();
+                *item_handle = Some(tray_update_item.clone());
+            }
+
             let tray_menu = Menu::with_items(app, &[
                 &MenuItem::with_id(app, "network_header", "Network Status", false, None::<&str>)?,
                 &PredefinedMenuItem::separator(app)?,
@@ -1088,7 +3524,12 @@ pub fn run() {
                 &sse_status_item,
                 &proxy_status_item,
                 &PredefinedMenuItem::separator(app)?,
+                &deployment_submenu,
+                &recent_pushes_submenu,
+                &tray_alerts_item,
+                &PredefinedMenuItem::separator(app)?,
                 &MenuItem::with_id(app, "run_tests", "Run Network Tests", true, None::<&str>)?,
+                &tray_update_item,
                 &PredefinedMenuItem::separator(app)?,
                 &IconMenuItem::with_id(app, "show_window", "Show Convex Panel", true, Some(menu_icon), None::<&str>)?,
                 &PredefinedMenuItem::quit(app, Some("Quit"))?,
@@ -1103,12 +3544,45 @@ pub fn run() {
                 .icon_as_template(true) // Makes it adapt to light/dark menu bar
                 .menu(&tray_menu)
                 .tooltip("Convex Panel - Network Status")
-                .on_menu_event(move |_app, event| {
-                    match event.id().as_ref() {
+                .on_menu_event(move |app, event| {
+                    let id = event.id().as_ref();
+
+                    if id == "check_updates" {
+                        handle_check_updates(app.clone());
+                        return;
+                    }
+
+                    // Deployment switcher entries carry a "deployment:" prefix
+                    if let Some(deployment) = id.strip_prefix("deployment:") {
+                        let deployment = deployment.to_string();
+                        {
+                            let mut state = TRAY_DEPLOYMENTS.lock().unwrap();
+                            state.active = Some(deployment.clone());
+                        }
+                        rebuild_deployment_submenu(app);
+                        let _ = app.emit("switch-deployment", deployment);
+                        return;
+                    }
+
+                    // Recent push entries open the window on that deployment's logs
+                    if let Some(deployment) = id.strip_prefix("push:") {
+                        let _ = window_for_tray.show();
+                        let _ = window_for_tray.set_focus();
+                        let _ = window_for_tray.emit("open-deployment-logs", deployment.to_string());
+                        return;
+                    }
+
+                    match id {
                         "show_window" => {
                             let _ = window_for_tray.show();
                             let _ = window_for_tray.set_focus();
                         }
+                        // Focusing the window clears the alert badge
+                        "unread_alerts" => {
+                            let _ = window_for_tray.show();
+                            let _ = window_for_tray.set_focus();
+                            let _ = window_for_tray.emit("open-alerts", ());
+                        }
                         "run_tests" => {
                             let _ = window_for_tray.emit("run-network-tests", ());
                         }
@@ -1123,28 +3597,23 @@ pub fn run() {
                 })
                 .build(app)?;
 
-            // set background color only when building for macOS
-            #[cfg(target_os = "macos")]
+            // Keep the tray handle for dynamic health icon updates
             {
-                use objc2_app_kit::NSColor;
-                use objc2::runtime::AnyObject;
-                use objc2::msg_send;
-
-                let ns_window_ptr = window.ns_window().unwrap();
-                unsafe {
-                    let bg_color = NSColor::colorWithRed_green_blue_alpha(
-                        42.0 / 255.0,
-                        40.0 / 255.0,
-                        37.0 / 255.0,
-                        1.0,
-                    );
-                    
-                    // Use objc2 message sending to set background color
-                    let window: *mut AnyObject = ns_window_ptr as *mut AnyObject;
-                    let _: () = msg_send![window, setBackgroundColor: &*bg_color];
-                }
+                let mut handle = TRAY_HANDLE.lock().unwrap();
+                *handle = Some(_tray.clone());
             }
 
+            // Keep re-running the network tests in the background so the tray
+            // stays accurate while the window is hidden
+            start_network_monitor(app.handle().clone());
+
+            // Watch for the system going online/offline
+            start_connectivity_monitor(app.handle().clone());
+
+            // set background color only when building for macOS
+            #[cfg(target_os = "macos")]
+            apply_macos_background_color(&window, theme_background_rgb("dark"));
+
             Ok(())
         })
         .run(tauri::generate_context!())
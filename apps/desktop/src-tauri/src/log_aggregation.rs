@@ -0,0 +1,271 @@
+//! Structured aggregation queries over the log store
+//!
+//! One spec-driven `aggregate_logs` command — group-by (function, level,
+//! identity, time bucket), metrics (counts, durations including
+//! percentiles, error rate), and having-filters — compiled to a single SQL
+//! query, so each dashboard widget doesn't need its own bespoke command.
+
+use rusqlite::types::Value as SqlValue;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tauri::State;
+
+use crate::log_store::DbConnection;
+
+/// Guard against pathological group counts before Rust-side post-processing
+const MAX_GROUPS: usize = 10_000;
+
+/// A filter on an aggregated metric, e.g. `{"metric": "error_rate",
+/// "op": ">", "value": 0.05}`
+#[derive(Debug, Clone, Deserialize)]
+pub struct HavingFilter {
+    pub metric: String,
+    pub op: String,
+    pub value: f64,
+}
+
+/// What to aggregate. `group_by` entries are "function", "level",
+/// "identity", or "time_bucket"; metrics are "count", "error_count",
+/// "error_rate", "avg_duration", "min_duration", "max_duration", or
+/// "p50_duration"/"p90_duration"/"p99_duration".
+#[derive(Debug, Clone, Deserialize)]
+pub struct AggregationSpec {
+    pub deployment: String,
+    pub start_ts: i64,
+    pub end_ts: i64,
+    #[serde(default)]
+    pub group_by: Vec<String>,
+    /// Bucket width for "time_bucket" grouping; defaults to 5 minutes
+    pub bucket_minutes: Option<i64>,
+    pub metrics: Vec<String>,
+    #[serde(default)]
+    pub having: Vec<HavingFilter>,
+    /// Metric to sort by, descending; defaults to the first metric
+    pub order_by: Option<String>,
+    pub limit: Option<usize>,
+}
+
+/// One aggregated group with its computed metrics
+#[derive(Debug, Clone, Serialize)]
+pub struct AggregationRow {
+    pub group: HashMap<String, serde_json::Value>,
+    pub metrics: HashMap<String, f64>,
+}
+
+/// SQL expression for a group-by key
+fn group_expr(key: &str, bucket_ms: i64) -> Result<String, String> {
+    match key {
+        "function" => Ok("COALESCE(function_path, '')".to_string()),
+        "level" => Ok("COALESCE(level, '')".to_string()),
+        "identity" => Ok(crate::usage_analytics::IDENTITY_SQL.to_string()),
+        "time_bucket" => Ok(format!("(ts / {bucket_ms}) * {bucket_ms}")),
+        other => Err(format!("Unknown group key: {}", other)),
+    }
+}
+
+const ERROR_EXPR: &str = "SUM(CASE WHEN success = 0 OR level = 'ERROR' THEN 1 ELSE 0 END)";
+
+/// SQL expression for a metric, or None for metrics computed in Rust
+/// (percentiles)
+fn metric_expr(metric: &str) -> Result<Option<String>, String> {
+    match metric {
+        "count" => Ok(Some("COUNT(*)".to_string())),
+        "error_count" => Ok(Some(ERROR_EXPR.to_string())),
+        "error_rate" => Ok(Some(format!(
+            "CAST({ERROR_EXPR} AS REAL) / COUNT(*)"
+        ))),
+        "avg_duration" => Ok(Some("COALESCE(AVG(duration_ms), 0)".to_string())),
+        "min_duration" => Ok(Some("COALESCE(MIN(duration_ms), 0)".to_string())),
+        "max_duration" => Ok(Some("COALESCE(MAX(duration_ms), 0)".to_string())),
+        "p50_duration" | "p90_duration" | "p99_duration" => Ok(None),
+        other => Err(format!("Unknown metric: {}", other)),
+    }
+}
+
+fn percentile(sorted: &[f64], fraction: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let index = ((sorted.len() - 1) as f64 * fraction).round() as usize;
+    sorted[index.min(sorted.len() - 1)]
+}
+
+fn matches_op(value: f64, op: &str, threshold: f64) -> Result<bool, String> {
+    match op {
+        ">" => Ok(value > threshold),
+        ">=" => Ok(value >= threshold),
+        "<" => Ok(value < threshold),
+        "<=" => Ok(value <= threshold),
+        "=" => Ok((value - threshold).abs() < f64::EPSILON),
+        other => Err(format!("Unknown having operator: {}", other)),
+    }
+}
+
+/// Run a structured aggregation over the log store. Having-filters on
+/// SQL-computable metrics are compiled into the query's HAVING clause;
+/// percentile filters are applied after the percentiles are computed.
+#[tauri::command]
+pub async fn aggregate_logs(
+    db: State<'_, DbConnection>,
+    spec: AggregationSpec,
+) -> Result<Vec<AggregationRow>, String> {
+    if spec.metrics.is_empty() {
+        return Err("At least one metric is required".to_string());
+    }
+    let bucket_ms = spec.bucket_minutes.unwrap_or(5).max(1) * 60 * 1000;
+
+    let mut select: Vec<String> = Vec::new();
+    for (i, key) in spec.group_by.iter().enumerate() {
+        select.push(format!("{} AS g{}", group_expr(key, bucket_ms)?, i));
+    }
+
+    let mut sql_metrics: Vec<&str> = Vec::new();
+    let mut needs_durations = false;
+    for metric in &spec.metrics {
+        match metric_expr(metric)? {
+            Some(expr) => {
+                select.push(format!("{} AS m{}", expr, sql_metrics.len()));
+                sql_metrics.push(metric);
+            }
+            None => needs_durations = true,
+        }
+    }
+    if needs_durations {
+        // Percentiles need the raw distribution; computed per group in Rust
+        select.push("json_group_array(COALESCE(duration_ms, 0)) AS durations".to_string());
+    }
+
+    let mut sql = format!(
+        "SELECT {} FROM logs WHERE deployment = ?1 AND ts >= ?2 AND ts <= ?3",
+        select.join(", ")
+    );
+    if !spec.group_by.is_empty() {
+        let keys: Vec<String> = (0..spec.group_by.len()).map(|i| format!("g{}", i)).collect();
+        sql.push_str(&format!(" GROUP BY {}", keys.join(", ")));
+    }
+
+    // Compile having-filters on SQL metrics into the query
+    let mut params: Vec<SqlValue> = vec![
+        SqlValue::Text(spec.deployment.clone()),
+        SqlValue::Integer(spec.start_ts),
+        SqlValue::Integer(spec.end_ts),
+    ];
+    let mut sql_having: Vec<String> = Vec::new();
+    for filter in &spec.having {
+        if !matches!(filter.op.as_str(), ">" | ">=" | "<" | "<=" | "=") {
+            return Err(format!("Unknown having operator: {}", filter.op));
+        }
+        if let Some(expr) = metric_expr(&filter.metric)? {
+            params.push(SqlValue::Real(filter.value));
+            sql_having.push(format!("{} {} ?{}", expr, filter.op, params.len()));
+        }
+    }
+    if !sql_having.is_empty() {
+        sql.push_str(&format!(" HAVING {}", sql_having.join(" AND ")));
+    }
+    sql.push_str(&format!(" LIMIT {}", MAX_GROUPS));
+
+    let conn = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+    let mut stmt = conn
+        .prepare(&sql)
+        .map_err(|e| format!("Query error: {}", e))?;
+
+    let group_count = spec.group_by.len();
+    let metric_names = sql_metrics.clone();
+    let rows = stmt
+        .query_map(rusqlite::params_from_iter(params), |row| {
+            let mut group = HashMap::new();
+            for (i, key) in spec.group_by.iter().enumerate() {
+                let value: SqlValue = row.get(i)?;
+                let value = match value {
+                    SqlValue::Integer(n) => serde_json::json!(n),
+                    SqlValue::Real(f) => serde_json::json!(f),
+                    SqlValue::Text(s) => serde_json::json!(s),
+                    _ => serde_json::Value::Null,
+                };
+                group.insert(key.clone(), value);
+            }
+
+            let mut metrics = HashMap::new();
+            for (i, name) in metric_names.iter().enumerate() {
+                metrics.insert(name.to_string(), row.get::<_, f64>(group_count + i)?);
+            }
+
+            let durations: Option<String> = if needs_durations {
+                row.get(group_count + metric_names.len())?
+            } else {
+                None
+            };
+            Ok((group, metrics, durations))
+        })
+        .map_err(|e| format!("Query error: {}", e))?;
+
+    let mut results: Vec<AggregationRow> = Vec::new();
+    for row in rows {
+        let (group, mut metrics, durations) = row.map_err(|e| format!("Query error: {}", e))?;
+
+        if let Some(durations) = durations {
+            let mut values: Vec<f64> =
+                serde_json::from_str(&durations).unwrap_or_default();
+            values.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+            for metric in &spec.metrics {
+                let fraction = match metric.as_str() {
+                    "p50_duration" => 0.50,
+                    "p90_duration" => 0.90,
+                    "p99_duration" => 0.99,
+                    _ => continue,
+                };
+                metrics.insert(metric.clone(), percentile(&values, fraction));
+            }
+        }
+
+        // Percentile having-filters couldn't be compiled to SQL
+        let mut keep = true;
+        for filter in &spec.having {
+            if metric_expr(&filter.metric)?.is_none() {
+                let value = metrics.get(&filter.metric).copied().unwrap_or(0.0);
+                if !matches_op(value, &filter.op, filter.value)? {
+                    keep = false;
+                    break;
+                }
+            }
+        }
+        if keep {
+            results.push(AggregationRow { group, metrics });
+        }
+    }
+
+    let order_by = spec
+        .order_by
+        .clone()
+        .unwrap_or_else(|| spec.metrics[0].clone());
+    results.sort_by(|a, b| {
+        let a = a.metrics.get(&order_by).copied().unwrap_or(0.0);
+        let b = b.metrics.get(&order_by).copied().unwrap_or(0.0);
+        b.partial_cmp(&a).unwrap_or(std::cmp::Ordering::Equal)
+    });
+    results.truncate(spec.limit.unwrap_or(100).min(MAX_GROUPS));
+
+    Ok(results)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percentile() {
+        let sorted = vec![1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0, 8.0, 9.0, 10.0];
+        assert_eq!(percentile(&sorted, 0.50), 6.0);
+        assert_eq!(percentile(&sorted, 0.99), 10.0);
+        assert_eq!(percentile(&[], 0.50), 0.0);
+    }
+
+    #[test]
+    fn test_matches_op() {
+        assert!(matches_op(2.0, ">", 1.0).unwrap());
+        assert!(matches_op(2.0, "=", 2.0).unwrap());
+        assert!(!matches_op(2.0, "<", 1.0).unwrap());
+        assert!(matches_op(2.0, "!", 1.0).is_err());
+    }
+}
@@ -0,0 +1,369 @@
+//! Alert rules evaluated at ingest time
+//!
+//! Users define rules in SQLite (level match, regex on message, error-rate
+//! threshold per function); every ingested batch is checked against them and
+//! matches fire a native notification plus an `alert-fired` window event.
+//! This makes the panel a local monitor instead of a passive viewer.
+
+use once_cell::sync::Lazy;
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+use tauri_plugin_notification::NotificationExt;
+
+use super::db::DbConnection;
+use super::subscriptions::NewLogEntry;
+
+/// Minimum rows in the window before an error-rate rule can fire, so a
+/// single failing call out of two doesn't page anyone
+const MIN_RATE_SAMPLE: i64 = 5;
+
+/// An alert rule as stored. `kind` is one of:
+/// - `level`: fire when an entry's level equals `level`
+/// - `message`: fire when `pattern` (a regex) matches an entry's message
+/// - `error_rate`: fire when the error percentage for `function_path` over
+///   the last `window_minutes` exceeds `threshold_pct`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertRule {
+    pub id: i64,
+    pub name: String,
+    pub enabled: bool,
+    pub kind: String,
+    pub level: Option<String>,
+    pub pattern: Option<String>,
+    /// Scopes level/message rules; required for error_rate rules
+    pub function_path: Option<String>,
+    pub threshold_pct: Option<f64>,
+    pub window_minutes: Option<i64>,
+    pub cooldown_minutes: i64,
+    /// When the rule last fired this session, for the UI
+    pub last_fired_ms: Option<i64>,
+}
+
+struct CachedRule {
+    rule: AlertRule,
+    regex: Option<regex::Regex>,
+}
+
+/// Enabled rules with compiled patterns; None means reload from the DB
+static RULES_CACHE: Lazy<Mutex<Option<Vec<CachedRule>>>> = Lazy::new(|| Mutex::new(None));
+
+/// Last fire time per rule id. In-memory only: a restart resets cooldowns,
+/// which errs on the side of not missing alerts.
+static LAST_FIRED: Lazy<Mutex<HashMap<i64, i64>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+fn invalidate_cache() {
+    *RULES_CACHE.lock().unwrap() = None;
+}
+
+fn rule_from_row(row: &rusqlite::Row) -> rusqlite::Result<AlertRule> {
+    Ok(AlertRule {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        enabled: row.get::<_, i64>(2)? != 0,
+        kind: row.get(3)?,
+        level: row.get(4)?,
+        pattern: row.get(5)?,
+        function_path: row.get(6)?,
+        threshold_pct: row.get(7)?,
+        window_minutes: row.get(8)?,
+        cooldown_minutes: row.get(9)?,
+        last_fired_ms: None,
+    })
+}
+
+const RULE_COLUMNS: &str = "id, name, enabled, kind, level, pattern, function_path,
+                            threshold_pct, window_minutes, cooldown_minutes";
+
+fn load_enabled_rules(conn: &rusqlite::Connection) -> Vec<CachedRule> {
+    let query = format!(
+        "SELECT {} FROM alert_rules WHERE enabled = 1",
+        RULE_COLUMNS
+    );
+    let Ok(mut stmt) = conn.prepare(&query) else {
+        return Vec::new();
+    };
+    let Ok(rules_iter) = stmt.query_map([], rule_from_row) else {
+        return Vec::new();
+    };
+
+    rules_iter
+        .filter_map(|rule| rule.ok())
+        .map(|rule| {
+            let regex = rule
+                .pattern
+                .as_deref()
+                .and_then(|p| regex::Regex::new(p).ok());
+            CachedRule { rule, regex }
+        })
+        .collect()
+}
+
+fn in_cooldown(rule: &AlertRule, now: i64) -> bool {
+    LAST_FIRED
+        .lock()
+        .unwrap()
+        .get(&rule.id)
+        .map(|fired| now - fired < rule.cooldown_minutes.max(0) * 60_000)
+        .unwrap_or(false)
+}
+
+fn fire(app: &AppHandle, rule: &AlertRule, body: String, now: i64) {
+    LAST_FIRED.lock().unwrap().insert(rule.id, now);
+
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.emit(
+            "alert-fired",
+            serde_json::json!({
+                "ruleId": rule.id,
+                "name": rule.name,
+                "body": body,
+                "ts": now,
+            }),
+        );
+    }
+
+    if !crate::notifications::muted() {
+        let _ = app
+            .notification()
+            .builder()
+            .title(format!("Alert: {}", rule.name))
+            .body(body)
+            .show();
+    }
+}
+
+fn entry_matches(cached: &CachedRule, entry: &NewLogEntry) -> bool {
+    let rule = &cached.rule;
+    if let Some(function_path) = &rule.function_path {
+        if entry.function_path.as_ref() != Some(function_path) {
+            return false;
+        }
+    }
+    match rule.kind.as_str() {
+        "level" => rule.level.is_some() && entry.level == rule.level,
+        "message" => cached
+            .regex
+            .as_ref()
+            .map(|re| re.is_match(&entry.message))
+            .unwrap_or(false),
+        _ => false,
+    }
+}
+
+/// Error percentage for a function over the trailing window, with the
+/// number of rows it was computed from
+fn error_rate(
+    conn: &rusqlite::Connection,
+    function_path: &str,
+    window_minutes: i64,
+    now: i64,
+) -> Option<(f64, i64)> {
+    let since = now - window_minutes.max(1) * 60_000;
+    conn.query_row(
+        "SELECT COUNT(*),
+                SUM(CASE WHEN success = 0 OR level = 'ERROR' THEN 1 ELSE 0 END)
+         FROM logs WHERE function_path = ?1 AND ts >= ?2",
+        params![function_path, since],
+        |row| {
+            let total: i64 = row.get(0)?;
+            let errors: i64 = row.get::<_, Option<i64>>(1)?.unwrap_or(0);
+            Ok((
+                if total > 0 {
+                    errors as f64 / total as f64 * 100.0
+                } else {
+                    0.0
+                },
+                total,
+            ))
+        },
+    )
+    .ok()
+}
+
+/// Check a freshly ingested batch against the enabled rules. Called from
+/// both ingest paths after subscriptions are notified.
+pub fn evaluate(app: &AppHandle, db: &DbConnection, entries: &[NewLogEntry]) {
+    if entries.is_empty() {
+        return;
+    }
+
+    let Ok(conn) = db.lock() else {
+        return;
+    };
+
+    let mut cache = RULES_CACHE.lock().unwrap();
+    let rules = cache.get_or_insert_with(|| load_enabled_rules(&conn));
+    if rules.is_empty() {
+        return;
+    }
+
+    let now = chrono::Utc::now().timestamp_millis();
+
+    for cached in rules.iter() {
+        let rule = &cached.rule;
+        if in_cooldown(rule, now) {
+            continue;
+        }
+
+        match rule.kind.as_str() {
+            "level" | "message" => {
+                if let Some(entry) = entries.iter().find(|e| entry_matches(cached, e)) {
+                    fire(app, rule, entry.message.clone(), now);
+                }
+            }
+            "error_rate" => {
+                let (Some(function_path), Some(threshold_pct)) =
+                    (&rule.function_path, rule.threshold_pct)
+                else {
+                    continue;
+                };
+                // Only worth recomputing when the batch touched the function
+                if !entries
+                    .iter()
+                    .any(|e| e.function_path.as_ref() == Some(function_path))
+                {
+                    continue;
+                }
+                let window_minutes = rule.window_minutes.unwrap_or(5);
+                if let Some((rate, total)) = error_rate(&conn, function_path, window_minutes, now)
+                {
+                    if total >= MIN_RATE_SAMPLE && rate > threshold_pct {
+                        fire(
+                            app,
+                            rule,
+                            format!(
+                                "{} error rate {:.1}% over the last {} min ({} calls)",
+                                function_path, rate, window_minutes, total
+                            ),
+                            now,
+                        );
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Create an alert rule. The id on the input is ignored.
+#[tauri::command]
+pub async fn create_alert_rule(
+    db: tauri::State<'_, DbConnection>,
+    rule: AlertRule,
+) -> Result<AlertRule, String> {
+    let name = rule.name.trim().to_string();
+    if name.is_empty() {
+        return Err("Rule name cannot be empty".to_string());
+    }
+
+    match rule.kind.as_str() {
+        "level" => {
+            if rule.level.is_none() {
+                return Err("Level rules need a level".to_string());
+            }
+        }
+        "message" => {
+            let Some(pattern) = &rule.pattern else {
+                return Err("Message rules need a pattern".to_string());
+            };
+            regex::Regex::new(pattern).map_err(|e| format!("Invalid pattern: {}", e))?;
+        }
+        "error_rate" => {
+            if rule.function_path.is_none() || rule.threshold_pct.is_none() {
+                return Err("Error-rate rules need a function path and threshold".to_string());
+            }
+        }
+        other => return Err(format!("Unknown rule kind: {}", other)),
+    }
+
+    let conn = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+    conn.execute(
+        "INSERT INTO alert_rules (
+            name, enabled, kind, level, pattern, function_path,
+            threshold_pct, window_minutes, cooldown_minutes
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
+        params![
+            name,
+            if rule.enabled { 1 } else { 0 },
+            rule.kind,
+            rule.level,
+            rule.pattern,
+            rule.function_path,
+            rule.threshold_pct,
+            rule.window_minutes,
+            rule.cooldown_minutes.max(0),
+        ],
+    )
+    .map_err(|e| format!("Insert error: {}", e))?;
+
+    let id = conn.last_insert_rowid();
+    invalidate_cache();
+
+    Ok(AlertRule {
+        id,
+        name,
+        ..rule
+    })
+}
+
+/// All alert rules, with session-local last-fired times attached
+#[tauri::command]
+pub async fn list_alert_rules(
+    db: tauri::State<'_, DbConnection>,
+) -> Result<Vec<AlertRule>, String> {
+    let conn = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+    let query = format!("SELECT {} FROM alert_rules ORDER BY name ASC", RULE_COLUMNS);
+    let mut stmt = conn
+        .prepare(&query)
+        .map_err(|e| format!("Prepare error: {}", e))?;
+
+    let rules_iter = stmt
+        .query_map([], rule_from_row)
+        .map_err(|e| format!("Query error: {}", e))?;
+
+    let last_fired = LAST_FIRED.lock().unwrap();
+    let mut rules = Vec::new();
+    for rule in rules_iter {
+        let mut rule = rule.map_err(|e| format!("Collect error: {}", e))?;
+        rule.last_fired_ms = last_fired.get(&rule.id).copied();
+        rules.push(rule);
+    }
+
+    Ok(rules)
+}
+
+/// Enable or disable an alert rule
+#[tauri::command]
+pub async fn set_alert_rule_enabled(
+    db: tauri::State<'_, DbConnection>,
+    rule_id: i64,
+    enabled: bool,
+) -> Result<bool, String> {
+    let conn = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+    let updated = conn
+        .execute(
+            "UPDATE alert_rules SET enabled = ?1 WHERE id = ?2",
+            params![if enabled { 1 } else { 0 }, rule_id],
+        )
+        .map_err(|e| format!("Update error: {}", e))?;
+    invalidate_cache();
+    Ok(updated > 0)
+}
+
+/// Delete an alert rule
+#[tauri::command]
+pub async fn delete_alert_rule(
+    db: tauri::State<'_, DbConnection>,
+    rule_id: i64,
+) -> Result<bool, String> {
+    let conn = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+    let deleted = conn
+        .execute("DELETE FROM alert_rules WHERE id = ?1", params![rule_id])
+        .map_err(|e| format!("Delete error: {}", e))?;
+    invalidate_cache();
+    LAST_FIRED.lock().unwrap().remove(&rule_id);
+    Ok(deleted > 0)
+}
@@ -0,0 +1,258 @@
+//! Cold archive for expired logs
+//!
+//! When an archive directory is configured, retention writes rows to
+//! zstd-compressed NDJSON files before deleting them, so aging out of the
+//! live database doesn't mean losing old production logs. Archives round-trip:
+//! each line is a full `LogEntry` row and re-import restores it verbatim.
+
+use rusqlite::params;
+use serde::Serialize;
+use std::io::{BufRead, Write};
+use std::path::Path;
+use tauri::State;
+
+use super::db::DbConnection;
+use super::models::LogEntry;
+
+const ARCHIVE_EXTENSION: &str = ".ndjson.zst";
+
+/// Write every row the retention pass is about to delete into one
+/// compressed NDJSON file. Returns how many rows were archived.
+pub(crate) fn archive_expired(
+    conn: &rusqlite::Connection,
+    archive_dir: &Path,
+    global_cutoff_ts: i64,
+    overrides: &[(String, i64)],
+) -> Result<usize, String> {
+    // Same shape as the retention deletes: overridden deployments use their
+    // own cutoff, everything else the global one
+    let mut clauses = Vec::new();
+    let mut params_vec: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
+
+    let mut global = "ts < ?".to_string();
+    params_vec.push(Box::new(global_cutoff_ts));
+    if !overrides.is_empty() {
+        let placeholders = overrides.iter().map(|_| "?").collect::<Vec<_>>().join(",");
+        global.push_str(&format!(" AND deployment NOT IN ({})", placeholders));
+        for (deployment, _) in overrides {
+            params_vec.push(Box::new(deployment.clone()));
+        }
+    }
+    clauses.push(format!("({})", global));
+
+    for (deployment, cutoff_ts) in overrides {
+        clauses.push("(deployment = ? AND ts < ?)".to_string());
+        params_vec.push(Box::new(deployment.clone()));
+        params_vec.push(Box::new(*cutoff_ts));
+    }
+
+    let sql = format!(
+        "SELECT id, ts, deployment, request_id, execution_id, topic, level,
+                function_path, function_name, udf_type, success, duration_ms,
+                message, json_blob, created_at
+         FROM logs
+         WHERE {}
+         ORDER BY ts ASC",
+        clauses.join(" OR ")
+    );
+
+    let mut stmt = conn
+        .prepare(&sql)
+        .map_err(|e| format!("Prepare error: {}", e))?;
+    let params_refs: Vec<&dyn rusqlite::ToSql> = params_vec.iter().map(|b| b.as_ref()).collect();
+    let rows = stmt
+        .query_map(params_refs.as_slice(), |row| {
+            Ok(LogEntry {
+                id: row.get(0)?,
+                ts: row.get(1)?,
+                deployment: row.get(2)?,
+                request_id: row.get(3)?,
+                execution_id: row.get(4)?,
+                topic: row.get(5)?,
+                level: row.get(6)?,
+                function_path: row.get(7)?,
+                function_name: row.get(8)?,
+                udf_type: row.get(9)?,
+                success: row.get::<_, Option<i32>>(10)?.map(|v| v != 0),
+                duration_ms: row.get(11)?,
+                message: row.get(12)?,
+                json_blob: row.get(13)?,
+                created_at: row.get(14)?,
+                // Annotations live in log_notes and aren't archived
+                note: None,
+            })
+        })
+        .map_err(|e| format!("Query error: {}", e))?;
+
+    std::fs::create_dir_all(archive_dir)
+        .map_err(|e| format!("Failed to create archive directory: {}", e))?;
+
+    let file_name = format!(
+        "logs-{}{}",
+        chrono::Utc::now().format("%Y%m%d-%H%M%S"),
+        ARCHIVE_EXTENSION
+    );
+    let final_path = archive_dir.join(&file_name);
+    let tmp_path = archive_dir.join(format!("{}.tmp", file_name));
+
+    let file = std::fs::File::create(&tmp_path)
+        .map_err(|e| format!("Failed to create archive file: {}", e))?;
+    let mut encoder = zstd::Encoder::new(file, 3)
+        .map_err(|e| format!("Failed to start compression: {}", e))?;
+
+    let mut archived = 0;
+    for row in rows {
+        let entry = row.map_err(|e| format!("Collect error: {}", e))?;
+        let line = serde_json::to_string(&entry)
+            .map_err(|e| format!("Failed to serialize log entry: {}", e))?;
+        encoder
+            .write_all(line.as_bytes())
+            .and_then(|_| encoder.write_all(b"\n"))
+            .map_err(|e| format!("Failed to write archive: {}", e))?;
+        archived += 1;
+    }
+
+    encoder
+        .finish()
+        .map_err(|e| format!("Failed to finish compression: {}", e))?;
+
+    if archived == 0 {
+        // Nothing expired; don't leave an empty file behind
+        let _ = std::fs::remove_file(&tmp_path);
+        return Ok(0);
+    }
+
+    std::fs::rename(&tmp_path, &final_path)
+        .map_err(|e| format!("Failed to finalize archive: {}", e))?;
+
+    println!(
+        "[log_store] Archived {} expiring logs to {}",
+        archived,
+        final_path.display()
+    );
+    Ok(archived)
+}
+
+/// One archive file on disk
+#[derive(Debug, Clone, Serialize)]
+pub struct ArchiveFile {
+    pub path: String,
+    pub file_name: String,
+    pub size_bytes: u64,
+    pub modified_ms: Option<i64>,
+}
+
+/// Archive files in the configured directory, newest first
+#[tauri::command]
+pub async fn list_log_archives(db: State<'_, DbConnection>) -> Result<Vec<ArchiveFile>, String> {
+    let Some(dir) = archive_dir(&db) else {
+        return Ok(Vec::new());
+    };
+    let Ok(entries) = std::fs::read_dir(&dir) else {
+        return Ok(Vec::new());
+    };
+
+    let mut archives = Vec::new();
+    for entry in entries.flatten() {
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        if !file_name.ends_with(ARCHIVE_EXTENSION) {
+            continue;
+        }
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        let modified_ms = metadata
+            .modified()
+            .ok()
+            .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+            .map(|d| d.as_millis() as i64);
+        archives.push(ArchiveFile {
+            path: entry.path().to_string_lossy().to_string(),
+            file_name,
+            size_bytes: metadata.len(),
+            modified_ms,
+        });
+    }
+
+    archives.sort_by(|a, b| b.file_name.cmp(&a.file_name));
+    Ok(archives)
+}
+
+/// Restore an archive file into the live database. Rows keep their original
+/// ids, so re-importing the same archive twice is a no-op.
+#[tauri::command]
+pub async fn import_log_archive(
+    db: State<'_, DbConnection>,
+    file_path: String,
+) -> Result<usize, String> {
+    let file = std::fs::File::open(&file_path)
+        .map_err(|e| format!("Failed to open archive: {}", e))?;
+    let decoder = zstd::Decoder::new(file)
+        .map_err(|e| format!("Failed to start decompression: {}", e))?;
+    let reader = std::io::BufReader::new(decoder);
+
+    let conn = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+    let tx = conn
+        .unchecked_transaction()
+        .map_err(|e| format!("Failed to start transaction: {}", e))?;
+
+    let mut imported = 0;
+    {
+        let mut stmt = tx
+            .prepare_cached(
+                "INSERT OR IGNORE INTO logs (
+                    id, ts, deployment, request_id, execution_id,
+                    topic, level, function_path, function_name, udf_type,
+                    success, duration_ms, message, json_blob, created_at
+                ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)",
+            )
+            .map_err(|e| format!("Prepare error: {}", e))?;
+
+        for line in reader.lines() {
+            let line = line.map_err(|e| format!("Failed to read archive: {}", e))?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let entry: LogEntry = serde_json::from_str(&line)
+                .map_err(|e| format!("Malformed archive line: {}", e))?;
+            let inserted = stmt
+                .execute(params![
+                    entry.id,
+                    entry.ts,
+                    entry.deployment,
+                    entry.request_id,
+                    entry.execution_id,
+                    entry.topic,
+                    entry.level,
+                    entry.function_path,
+                    entry.function_name,
+                    entry.udf_type,
+                    entry.success.map(|s| if s { 1 } else { 0 }),
+                    entry.duration_ms,
+                    entry.message,
+                    entry.json_blob,
+                    entry.created_at,
+                ])
+                .map_err(|e| format!("Insert error: {}", e))?;
+            imported += inserted;
+        }
+    }
+
+    tx.commit()
+        .map_err(|e| format!("Failed to commit import: {}", e))?;
+    Ok(imported)
+}
+
+/// The configured archive directory, if archiving is enabled
+pub(crate) fn archive_dir(conn: &DbConnection) -> Option<std::path::PathBuf> {
+    let conn_guard = conn.lock().ok()?;
+    conn_guard
+        .query_row(
+            "SELECT value FROM settings WHERE key = 'archive_dir'",
+            [],
+            |row| row.get::<_, String>(0),
+        )
+        .ok()
+        .filter(|dir| !dir.trim().is_empty())
+        .map(std::path::PathBuf::from)
+}
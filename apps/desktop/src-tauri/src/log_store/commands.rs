@@ -3,75 +3,197 @@ use tauri::State;
 
 use super::db::DbConnection;
 use super::models::*;
-use super::utils::{compute_log_id, extract_message, infer_level, infer_topic};
+use super::utils::{
+    compute_error_fingerprint, compute_log_id, extract_message, infer_level, infer_topic,
+    split_line_level,
+};
+
+/// Reserved tag backing `star_log`; not usable via `tag_log`
+const STARRED_TAG: &str = "__starred__";
 
 /// Ingest a batch of logs into the database
 #[tauri::command]
 pub async fn ingest_logs(
+    app: tauri::AppHandle,
     db: State<'_, DbConnection>,
     logs: Vec<IngestLogEntry>,
     deployment: String,
 ) -> Result<IngestResult, String> {
-    let conn = db.lock().map_err(|e| format!("Lock error: {}", e))?;
-    
+    let (result, new_entries) = {
+        let conn = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+        ingest_batch_tracked(&conn, logs, &deployment)
+    };
+    super::subscriptions::notify(&app, &new_entries);
+    super::alerts::evaluate(&app, &db, &new_entries);
+    Ok(result)
+}
+
+/// Shared ingest path, used by the `ingest_logs` command and native log
+/// streaming
+pub fn ingest_batch(
+    conn: &rusqlite::Connection,
+    logs: Vec<IngestLogEntry>,
+    deployment: &str,
+) -> IngestResult {
+    ingest_batch_tracked(conn, logs, deployment).0
+}
+
+/// Ingest that also returns the rows it actually inserted, so live tail
+/// subscriptions can be notified
+pub(crate) fn ingest_batch_tracked(
+    conn: &rusqlite::Connection,
+    logs: Vec<IngestLogEntry>,
+    deployment: &str,
+) -> (IngestResult, Vec<super::subscriptions::NewLogEntry>) {
     let mut inserted = 0;
     let mut duplicates = 0;
     let mut errors = 0;
-    
+    let mut new_entries = Vec::new();
+
     let now = chrono::Utc::now().timestamp_millis();
-    
+
+    // One transaction for the whole batch: per-row autocommit fsyncs made
+    // 5k-entry batches from a busy deployment stall the shared connection
+    let tx = match conn.unchecked_transaction() {
+        Ok(tx) => tx,
+        Err(e) => {
+            eprintln!("Failed to start ingest transaction: {}", e);
+            return (
+                IngestResult {
+                    inserted: 0,
+                    duplicates: 0,
+                    errors: logs.len(),
+                },
+                new_entries,
+            );
+        }
+    };
+
+    let mut stmt = match tx.prepare_cached(
+        "INSERT OR IGNORE INTO logs (
+            id, ts, deployment, request_id, execution_id,
+            topic, level, function_path, function_name, udf_type,
+            success, duration_ms, message, json_blob, created_at,
+            error_fingerprint
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)",
+    ) {
+        Ok(stmt) => stmt,
+        Err(e) => {
+            eprintln!("Failed to prepare ingest statement: {}", e);
+            return (
+                IngestResult {
+                    inserted: 0,
+                    duplicates: 0,
+                    errors: logs.len(),
+                },
+                new_entries,
+            );
+        }
+    };
+
+    let mut line_stmt = match tx.prepare_cached(
+        "INSERT OR IGNORE INTO log_lines (log_id, line_index, level, ts, message)
+         VALUES (?1, ?2, ?3, ?4, ?5)",
+    ) {
+        Ok(stmt) => stmt,
+        Err(e) => {
+            eprintln!("Failed to prepare log line statement: {}", e);
+            return (
+                IngestResult {
+                    inserted: 0,
+                    duplicates: 0,
+                    errors: logs.len(),
+                },
+                new_entries,
+            );
+        }
+    };
+
     for entry in logs {
-        // Compute stable ID
-        let message = extract_message(&entry);
+        // Redact before the ID is computed so dedup sees the stored text
+        let message = super::redaction::redact(&tx, extract_message(&entry));
         let level = infer_level(&entry);
         let topic = infer_topic(entry.udf_type.as_deref());
         
         let id = compute_log_id(
             entry.timestamp,
-            &deployment,
+            deployment,
             entry.request_id.as_deref(),
             entry.function_identifier.as_deref(),
             level.as_deref(),
             &message,
         );
         
+        // Errors get a fingerprint for the grouped-errors view
+        let error_fingerprint = if level.as_deref() == Some("ERROR") {
+            Some(compute_error_fingerprint(
+                entry.function_identifier.as_deref(),
+                &message,
+            ))
+        } else {
+            None
+        };
+
         // Serialize raw data to JSON
         let json_blob = if let Some(raw) = &entry.raw {
             serde_json::to_string(raw).unwrap_or_else(|_| "{}".to_string())
         } else {
             serde_json::to_string(&entry).unwrap_or_else(|_| "{}".to_string())
         };
+        let json_blob = super::redaction::redact(&tx, json_blob);
         
         // Try to insert (will fail silently on duplicate primary key)
-        let result: SqliteResult<usize> = conn.execute(
-            "INSERT OR IGNORE INTO logs (
-                id, ts, deployment, request_id, execution_id,
-                topic, level, function_path, function_name, udf_type,
-                success, duration_ms, message, json_blob, created_at
-            ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)",
-            params![
-                id,
-                entry.timestamp,
-                deployment,
-                entry.request_id,
-                entry.execution_id,
-                topic,
-                level,
-                entry.function_identifier,
-                entry.function_name,
-                entry.udf_type,
-                entry.success.map(|s| if s { 1 } else { 0 }),
-                entry.duration_ms,
-                message,
-                json_blob,
-                now,
-            ],
-        );
-        
+        let result: SqliteResult<usize> = stmt.execute(params![
+            id,
+            entry.timestamp,
+            deployment,
+            entry.request_id,
+            entry.execution_id,
+            topic,
+            level,
+            entry.function_identifier,
+            entry.function_name,
+            entry.udf_type,
+            entry.success.map(|s| if s { 1 } else { 0 }),
+            entry.duration_ms,
+            message,
+            json_blob,
+            now,
+            error_fingerprint,
+        ]);
+
         match result {
             Ok(rows) => {
                 if rows > 0 {
                     inserted += 1;
+
+                    // Each console line gets its own row with its own level
+                    if let Some(lines) = &entry.log_lines {
+                        for (index, line) in lines.iter().enumerate() {
+                            let (line_level, text) = split_line_level(line);
+                            let text = super::redaction::redact(&tx, text.to_string());
+                            if let Err(e) = line_stmt.execute(params![
+                                id,
+                                index as i64,
+                                line_level,
+                                entry.timestamp,
+                                text,
+                            ]) {
+                                eprintln!("Failed to insert log line: {}", e);
+                            }
+                        }
+                    }
+
+                    new_entries.push(super::subscriptions::NewLogEntry {
+                        id,
+                        ts: entry.timestamp,
+                        deployment: deployment.to_string(),
+                        level,
+                        function_path: entry.function_identifier,
+                        request_id: entry.request_id,
+                        success: entry.success,
+                        message,
+                    });
                 } else {
                     duplicates += 1;
                 }
@@ -82,12 +204,26 @@ pub async fn ingest_logs(
             }
         }
     }
-    
-    Ok(IngestResult {
-        inserted,
-        duplicates,
-        errors,
-    })
+
+    drop(line_stmt);
+    drop(stmt);
+    if let Err(e) = tx.commit() {
+        eprintln!("Failed to commit ingest transaction: {}", e);
+        // Nothing landed; report the rows we thought we inserted as errors
+        errors += inserted + duplicates;
+        inserted = 0;
+        duplicates = 0;
+        new_entries.clear();
+    }
+
+    (
+        IngestResult {
+            inserted,
+            duplicates,
+            errors,
+        },
+        new_entries,
+    )
 }
 
 /// Query logs with filters and pagination
@@ -98,7 +234,7 @@ pub async fn query_logs(
     limit: Option<i32>,
     cursor: Option<String>,
 ) -> Result<LogQueryResult, String> {
-    let conn = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+    let conn = db.read()?;
     
     let limit = limit.unwrap_or(100).min(1000); // Cap at 1000
     
@@ -160,7 +296,32 @@ pub async fn query_logs(
             }
         }
     }
-    
+
+    if let Some(ref tags) = filters.tags {
+        if !tags.is_empty() {
+            let placeholders = tags.iter().map(|_| "?").collect::<Vec<_>>().join(",");
+            where_clauses.push(format!(
+                "EXISTS (SELECT 1 FROM log_tags
+                         WHERE log_tags.log_id = logs.id AND log_tags.tag IN ({}))",
+                placeholders
+            ));
+            for tag in tags {
+                params_vec.push(Box::new(tag.clone()));
+            }
+        }
+    }
+
+    if let Some(starred) = filters.starred {
+        let exists = "EXISTS (SELECT 1 FROM log_tags
+                      WHERE log_tags.log_id = logs.id AND log_tags.tag = ?)";
+        if starred {
+            where_clauses.push(exists.to_string());
+        } else {
+            where_clauses.push(format!("NOT {}", exists));
+        }
+        params_vec.push(Box::new(STARRED_TAG.to_string()));
+    }
+
     // Cursor pagination
     if let (Some(ts), Some(id)) = (cursor_ts, cursor_id) {
         where_clauses.push("(ts < ? OR (ts = ? AND id < ?))".to_string());
@@ -176,10 +337,11 @@ pub async fn query_logs(
     };
     
     let query = format!(
-        "SELECT id, ts, deployment, request_id, execution_id, topic, level, 
-                function_path, function_name, udf_type, success, duration_ms, 
-                message, json_blob, created_at
+        "SELECT id, ts, deployment, request_id, execution_id, topic, level,
+                function_path, function_name, udf_type, success, duration_ms,
+                message, json_blob, created_at, log_notes.note
          FROM logs
+         LEFT JOIN log_notes ON log_notes.log_id = logs.id
          {}
          ORDER BY ts DESC, id DESC
          LIMIT {}",
@@ -211,6 +373,7 @@ pub async fn query_logs(
                 message: row.get(12)?,
                 json_blob: row.get(13)?,
                 created_at: row.get(14)?,
+                note: row.get(15)?,
             })
         })
         .map_err(|e| format!("Query error: {}", e))?;
@@ -251,66 +414,104 @@ pub async fn search_logs(
     query: String,
     filters: LogFilters,
     limit: Option<i32>,
-    _cursor: Option<String>, // TODO: Implement cursor for search
+    cursor: Option<String>,
 ) -> Result<LogQueryResult, String> {
-    let conn = db.lock().map_err(|e| format!("Lock error: {}", e))?;
-    
+    let conn = db.read()?;
+
     let limit = limit.unwrap_or(100).min(1000);
-    
+
     // Sanitize FTS query (basic escaping)
     let fts_query = query
         .replace('"', "\"\"")
         .trim()
         .to_string();
-    
+
     if fts_query.is_empty() {
         return Err("Empty search query".to_string());
     }
-    
+
+    // Parse cursor (format: "ts:id", same as query_logs)
+    let (cursor_ts, cursor_id) = if let Some(c) = cursor {
+        let parts: Vec<&str> = c.split(':').collect();
+        if parts.len() == 2 {
+            (
+                parts[0].parse::<i64>().ok(),
+                Some(parts[1].to_string()),
+            )
+        } else {
+            (None, None)
+        }
+    } else {
+        (None, None)
+    };
+
     // Build WHERE clause for additional filters
     let mut where_clauses = Vec::new();
     let mut params_vec: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
-    
+
     if let Some(ref deployment) = filters.deployment {
         where_clauses.push("logs.deployment = ?".to_string());
         params_vec.push(Box::new(deployment.clone()));
     }
-    
+
     if let Some(start_ts) = filters.start_ts {
         where_clauses.push("logs.ts >= ?".to_string());
         params_vec.push(Box::new(start_ts));
     }
-    
+
     if let Some(end_ts) = filters.end_ts {
         where_clauses.push("logs.ts <= ?".to_string());
         params_vec.push(Box::new(end_ts));
     }
-    
+
     let additional_where = if where_clauses.is_empty() {
         String::new()
     } else {
         format!("AND {}", where_clauses.join(" AND "))
     };
-    
+
+    // Total matches, counted before the cursor narrows the page
+    let count_sql = format!(
+        "SELECT COUNT(*)
+         FROM logs_fts
+         JOIN logs ON logs.rowid = logs_fts.rowid
+         WHERE logs_fts MATCH ?
+         {}",
+        additional_where
+    );
+
+    // Cursor keyset clause matches query_logs' (ts DESC, id DESC) ordering
+    let filter_param_count = params_vec.len();
+    let mut page_where = additional_where.clone();
+    if let (Some(ts), Some(ref id)) = (cursor_ts, &cursor_id) {
+        page_where.push_str(" AND (logs.ts < ? OR (logs.ts = ? AND logs.id < ?))");
+        params_vec.push(Box::new(ts));
+        params_vec.push(Box::new(ts));
+        params_vec.push(Box::new(id.clone()));
+    }
+
     let sql = format!(
         "SELECT logs.id, logs.ts, logs.deployment, logs.request_id, logs.execution_id,
                 logs.topic, logs.level, logs.function_path, logs.function_name, logs.udf_type,
-                logs.success, logs.duration_ms, logs.message, logs.json_blob, logs.created_at
+                logs.success, logs.duration_ms, logs.message, logs.json_blob, logs.created_at,
+                log_notes.note
          FROM logs_fts
          JOIN logs ON logs.rowid = logs_fts.rowid
+         LEFT JOIN log_notes ON log_notes.log_id = logs.id
          WHERE logs_fts MATCH ?
          {}
-         ORDER BY logs.ts DESC
+         ORDER BY logs.ts DESC, logs.id DESC
          LIMIT {}",
-        additional_where, limit
+        page_where,
+        limit + 1 // Fetch one extra to check if there's more
     );
-    
+
     // Prepend FTS query to params
     let mut all_params: Vec<Box<dyn rusqlite::ToSql>> = vec![Box::new(fts_query)];
     all_params.extend(params_vec);
-    
+
     let params_refs: Vec<&dyn rusqlite::ToSql> = all_params.iter().map(|b| b.as_ref()).collect();
-    
+
     let mut stmt = conn
         .prepare(&sql)
         .map_err(|e| format!("Prepare error: {}", e))?;
@@ -333,21 +534,160 @@ pub async fn search_logs(
                 message: row.get(12)?,
                 json_blob: row.get(13)?,
                 created_at: row.get(14)?,
+                note: row.get(15)?,
             })
         })
         .map_err(|e| format!("Query error: {}", e))?;
     
-    let logs: Vec<LogEntry> = logs_iter
+    let mut logs: Vec<LogEntry> = logs_iter
         .collect::<SqliteResult<Vec<_>>>()
         .map_err(|e| format!("Collect error: {}", e))?;
-    
+    drop(stmt);
+
+    // Check if there are more results
+    let has_more = logs.len() > limit as usize;
+    if has_more {
+        logs.pop(); // Remove the extra item
+    }
+
+    // Create next cursor from last item
+    let next_cursor = logs.last().map(|log| format!("{}:{}", log.ts, log.id));
+
+    // Total matches, using only the fts + filter params (no cursor)
+    let total_count: i64 = conn
+        .query_row(
+            &count_sql,
+            &params_refs[..1 + filter_param_count],
+            |row| row.get(0),
+        )
+        .unwrap_or(0);
+
+    Ok(LogQueryResult {
+        logs,
+        total_count,
+        has_more,
+        cursor: next_cursor,
+    })
+}
+
+/// Search logs with a regex over `message` and `json_blob`, for the
+/// punctuation-heavy patterns (request ids, JSON keys) that FTS
+/// tokenization drops. Slower than `search_logs`: this scans rather than
+/// using an index.
+#[tauri::command]
+pub async fn search_logs_regex(
+    db: State<'_, DbConnection>,
+    pattern: String,
+    filters: LogFilters,
+    limit: Option<i32>,
+    cursor: Option<String>,
+) -> Result<LogQueryResult, String> {
+    let limit = limit.unwrap_or(100).min(1000);
+
+    // Fail with the regex error up front instead of mid-query
+    regex::Regex::new(&pattern).map_err(|e| format!("Invalid pattern: {}", e))?;
+
+    // Parse cursor (format: "ts:id", same as query_logs)
+    let (cursor_ts, cursor_id) = if let Some(c) = cursor {
+        let parts: Vec<&str> = c.split(':').collect();
+        if parts.len() == 2 {
+            (
+                parts[0].parse::<i64>().ok(),
+                Some(parts[1].to_string()),
+            )
+        } else {
+            (None, None)
+        }
+    } else {
+        (None, None)
+    };
+
+    let mut where_clauses = vec!["(message REGEXP ? OR json_blob REGEXP ?)".to_string()];
+    let mut params_vec: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
+    params_vec.push(Box::new(pattern.clone()));
+    params_vec.push(Box::new(pattern));
+
+    if let Some(ref deployment) = filters.deployment {
+        where_clauses.push("deployment = ?".to_string());
+        params_vec.push(Box::new(deployment.clone()));
+    }
+    if let Some(start_ts) = filters.start_ts {
+        where_clauses.push("ts >= ?".to_string());
+        params_vec.push(Box::new(start_ts));
+    }
+    if let Some(end_ts) = filters.end_ts {
+        where_clauses.push("ts <= ?".to_string());
+        params_vec.push(Box::new(end_ts));
+    }
+    if let (Some(ts), Some(ref id)) = (cursor_ts, &cursor_id) {
+        where_clauses.push("(ts < ? OR (ts = ? AND id < ?))".to_string());
+        params_vec.push(Box::new(ts));
+        params_vec.push(Box::new(ts));
+        params_vec.push(Box::new(id.clone()));
+    }
+
+    let sql = format!(
+        "SELECT id, ts, deployment, request_id, execution_id, topic, level,
+                function_path, function_name, udf_type, success, duration_ms,
+                message, json_blob, created_at, log_notes.note
+         FROM logs
+         LEFT JOIN log_notes ON log_notes.log_id = logs.id
+         WHERE {}
+         ORDER BY ts DESC, id DESC
+         LIMIT {}",
+        where_clauses.join(" AND "),
+        limit + 1 // Fetch one extra to check if there's more
+    );
+
+    let conn = db.read()?;
+    let mut stmt = conn
+        .prepare(&sql)
+        .map_err(|e| format!("Prepare error: {}", e))?;
+
+    let params_refs: Vec<&dyn rusqlite::ToSql> = params_vec.iter().map(|b| b.as_ref()).collect();
+    let logs_iter = stmt
+        .query_map(params_refs.as_slice(), |row| {
+            Ok(LogEntry {
+                id: row.get(0)?,
+                ts: row.get(1)?,
+                deployment: row.get(2)?,
+                request_id: row.get(3)?,
+                execution_id: row.get(4)?,
+                topic: row.get(5)?,
+                level: row.get(6)?,
+                function_path: row.get(7)?,
+                function_name: row.get(8)?,
+                udf_type: row.get(9)?,
+                success: row.get::<_, Option<i32>>(10)?.map(|v| v != 0),
+                duration_ms: row.get(11)?,
+                message: row.get(12)?,
+                json_blob: row.get(13)?,
+                created_at: row.get(14)?,
+                note: row.get(15)?,
+            })
+        })
+        .map_err(|e| format!("Query error: {}", e))?;
+
+    let mut logs: Vec<LogEntry> = logs_iter
+        .collect::<SqliteResult<Vec<_>>>()
+        .map_err(|e| format!("Collect error: {}", e))?;
+
+    let has_more = logs.len() > limit as usize;
+    if has_more {
+        logs.pop(); // Remove the extra item
+    }
+
+    let next_cursor = logs.last().map(|log| format!("{}:{}", log.ts, log.id));
+
+    // A full count would re-run the regex over every row; the page count is
+    // enough for the search UI
     let total_count = logs.len() as i64;
-    
+
     Ok(LogQueryResult {
         logs,
         total_count,
-        has_more: false,
-        cursor: None,
+        has_more,
+        cursor: next_cursor,
     })
 }
 
@@ -357,13 +697,15 @@ pub async fn get_log_by_id(
     db: State<'_, DbConnection>,
     id: String,
 ) -> Result<Option<LogEntry>, String> {
-    let conn = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+    let conn = db.read()?;
     
     let result = conn.query_row(
         "SELECT id, ts, deployment, request_id, execution_id, topic, level,
                 function_path, function_name, udf_type, success, duration_ms,
-                message, json_blob, created_at
-         FROM logs WHERE id = ?",
+                message, json_blob, created_at, log_notes.note
+         FROM logs
+         LEFT JOIN log_notes ON log_notes.log_id = logs.id
+         WHERE id = ?",
         params![id],
         |row| {
             Ok(LogEntry {
@@ -382,6 +724,7 @@ pub async fn get_log_by_id(
                 message: row.get(12)?,
                 json_blob: row.get(13)?,
                 created_at: row.get(14)?,
+                note: row.get(15)?,
             })
         },
     );
@@ -393,41 +736,176 @@ pub async fn get_log_by_id(
     }
 }
 
-/// Delete logs older than N days
+/// The logs immediately before and after one entry in its deployment, so
+/// the detail panel can show what led up to an error without paging through
+/// a filtered query. Ties on ts break on id, matching the query ordering.
 #[tauri::command]
-pub async fn delete_logs_older_than(
+pub async fn get_log_context(
     db: State<'_, DbConnection>,
-    days: i32,
-) -> Result<i64, String> {
-    let conn = db.lock().map_err(|e| format!("Lock error: {}", e))?;
-    
-    let cutoff_ts = chrono::Utc::now().timestamp_millis() - (days as i64 * 24 * 60 * 60 * 1000);
-    
-    let deleted = conn
-        .execute("DELETE FROM logs WHERE ts < ?", params![cutoff_ts])
-        .map_err(|e| format!("Delete error: {}", e))?;
-    
-    // Checkpoint WAL to reclaim space (query_row because it returns results)
-    let _ = conn
-        .query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))
-        .map_err(|e| format!("Checkpoint error: {}", e))?;
-    
-    Ok(deleted as i64)
-}
+    id: String,
+    before: Option<i32>,
+    after: Option<i32>,
+) -> Result<Option<LogContext>, String> {
+    let before = before.unwrap_or(10).clamp(0, 100);
+    let after = after.unwrap_or(10).clamp(0, 100);
 
-/// Get log statistics
-#[tauri::command]
-pub async fn get_log_stats(
-    db: State<'_, DbConnection>,
-    app_handle: tauri::AppHandle,
-) -> Result<LogStats, String> {
-    let conn = db.lock().map_err(|e| format!("Lock error: {}", e))?;
-    
-    // Total logs
-    let total_logs: i64 = conn
-        .query_row("SELECT COUNT(*) FROM logs", [], |row| row.get(0))
-        .unwrap_or(0);
-    
+    const COLUMNS: &str = "id, ts, deployment, request_id, execution_id, topic, level,
+                           function_path, function_name, udf_type, success, duration_ms,
+                           message, json_blob, created_at, log_notes.note";
+    const JOIN: &str = "LEFT JOIN log_notes ON log_notes.log_id = logs.id";
+
+    fn map_entry(row: &rusqlite::Row) -> SqliteResult<LogEntry> {
+        Ok(LogEntry {
+            id: row.get(0)?,
+            ts: row.get(1)?,
+            deployment: row.get(2)?,
+            request_id: row.get(3)?,
+            execution_id: row.get(4)?,
+            topic: row.get(5)?,
+            level: row.get(6)?,
+            function_path: row.get(7)?,
+            function_name: row.get(8)?,
+            udf_type: row.get(9)?,
+            success: row.get::<_, Option<i32>>(10)?.map(|v| v != 0),
+            duration_ms: row.get(11)?,
+            message: row.get(12)?,
+            json_blob: row.get(13)?,
+            created_at: row.get(14)?,
+            note: row.get(15)?,
+        })
+    }
+
+    let conn = db.read()?;
+
+    let anchor = match conn.query_row(
+        &format!("SELECT {} FROM logs {} WHERE id = ?", COLUMNS, JOIN),
+        params![id],
+        map_entry,
+    ) {
+        Ok(entry) => entry,
+        Err(rusqlite::Error::QueryReturnedNoRows) => return Ok(None),
+        Err(e) => return Err(format!("Query error: {}", e)),
+    };
+
+    let mut before_entries = {
+        let sql = format!(
+            "SELECT {} FROM logs {}
+             WHERE deployment = ?1 AND (ts < ?2 OR (ts = ?2 AND id < ?3))
+             ORDER BY ts DESC, id DESC LIMIT {}",
+            COLUMNS, JOIN, before
+        );
+        let mut stmt = conn
+            .prepare(&sql)
+            .map_err(|e| format!("Prepare error: {}", e))?;
+        let rows = stmt
+            .query_map(params![anchor.deployment, anchor.ts, anchor.id], map_entry)
+            .map_err(|e| format!("Query error: {}", e))?;
+        rows.collect::<SqliteResult<Vec<_>>>()
+            .map_err(|e| format!("Collect error: {}", e))?
+    };
+    // Queried newest-first to use the index; present oldest first
+    before_entries.reverse();
+
+    let after_entries = {
+        let sql = format!(
+            "SELECT {} FROM logs {}
+             WHERE deployment = ?1 AND (ts > ?2 OR (ts = ?2 AND id > ?3))
+             ORDER BY ts ASC, id ASC LIMIT {}",
+            COLUMNS, JOIN, after
+        );
+        let mut stmt = conn
+            .prepare(&sql)
+            .map_err(|e| format!("Prepare error: {}", e))?;
+        let rows = stmt
+            .query_map(params![anchor.deployment, anchor.ts, anchor.id], map_entry)
+            .map_err(|e| format!("Query error: {}", e))?;
+        rows.collect::<SqliteResult<Vec<_>>>()
+            .map_err(|e| format!("Collect error: {}", e))?
+    };
+
+    Ok(Some(LogContext {
+        before: before_entries,
+        entry: anchor,
+        after: after_entries,
+    }))
+}
+
+/// Console lines for a single log, in order. Optionally narrowed to one
+/// level so the UI can show only e.g. console.warn output.
+#[tauri::command]
+pub async fn get_log_lines(
+    db: State<'_, DbConnection>,
+    log_id: String,
+    level: Option<String>,
+) -> Result<Vec<LogLine>, String> {
+    let conn = db.read()?;
+
+    let mut sql = "SELECT log_id, line_index, level, ts, message
+                   FROM log_lines WHERE log_id = ?"
+        .to_string();
+    let mut params_vec: Vec<Box<dyn rusqlite::ToSql>> = vec![Box::new(log_id)];
+    if let Some(level) = level {
+        sql.push_str(" AND level = ?");
+        params_vec.push(Box::new(level));
+    }
+    sql.push_str(" ORDER BY line_index ASC");
+
+    let mut stmt = conn
+        .prepare(&sql)
+        .map_err(|e| format!("Prepare error: {}", e))?;
+    let params_refs: Vec<&dyn rusqlite::ToSql> = params_vec.iter().map(|b| b.as_ref()).collect();
+    let lines_iter = stmt
+        .query_map(params_refs.as_slice(), |row| {
+            Ok(LogLine {
+                log_id: row.get(0)?,
+                line_index: row.get(1)?,
+                level: row.get(2)?,
+                ts: row.get(3)?,
+                message: row.get(4)?,
+            })
+        })
+        .map_err(|e| format!("Query error: {}", e))?;
+
+    lines_iter
+        .collect::<SqliteResult<Vec<_>>>()
+        .map_err(|e| format!("Collect error: {}", e))
+}
+
+/// Delete logs older than N days
+#[tauri::command]
+pub async fn delete_logs_older_than(
+    db: State<'_, DbConnection>,
+    days: i32,
+) -> Result<i64, String> {
+    let conn = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+    
+    let cutoff_ts = chrono::Utc::now().timestamp_millis() - (days as i64 * 24 * 60 * 60 * 1000);
+    
+    let deleted = conn
+        .execute("DELETE FROM logs WHERE ts < ?", params![cutoff_ts])
+        .map_err(|e| format!("Delete error: {}", e))?;
+    
+    // Checkpoint WAL to reclaim space (query_row because it returns results)
+    let _ = conn
+        .query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))
+        .map_err(|e| format!("Checkpoint error: {}", e))?;
+    
+    Ok(deleted as i64)
+}
+
+/// Get log statistics
+#[tauri::command]
+pub async fn get_log_stats(
+    db: State<'_, DbConnection>,
+    app_handle: tauri::AppHandle,
+) -> Result<LogStats, String> {
+    let conn = db.read()?;
+    
+    // Total logs
+    let total_logs: i64 = conn
+        .query_row("SELECT COUNT(*) FROM logs", [], |row| row.get(0))
+        .unwrap_or(0);
+    
     // Oldest and newest timestamps
     let oldest_ts: Option<i64> = conn
         .query_row("SELECT MIN(ts) FROM logs", [], |row| row.get(0))
@@ -460,6 +938,699 @@ pub async fn get_log_stats(
     })
 }
 
+/// Error clusters by fingerprint, most frequent first, for the grouped
+/// errors view. Only rows ingested since fingerprinting shipped have one.
+#[tauri::command]
+pub async fn get_error_groups(
+    db: State<'_, DbConnection>,
+    deployment: Option<String>,
+    start_ts: Option<i64>,
+    end_ts: Option<i64>,
+    limit: Option<i32>,
+) -> Result<Vec<ErrorGroup>, String> {
+    let limit = limit.unwrap_or(50).min(500);
+
+    let mut where_clauses = vec!["error_fingerprint IS NOT NULL".to_string()];
+    let mut params_vec: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
+
+    if let Some(deployment) = deployment {
+        where_clauses.push("deployment = ?".to_string());
+        params_vec.push(Box::new(deployment));
+    }
+    if let Some(start_ts) = start_ts {
+        where_clauses.push("ts >= ?".to_string());
+        params_vec.push(Box::new(start_ts));
+    }
+    if let Some(end_ts) = end_ts {
+        where_clauses.push("ts <= ?".to_string());
+        params_vec.push(Box::new(end_ts));
+    }
+
+    let query = format!(
+        "SELECT error_fingerprint, COUNT(*), MIN(ts), MAX(ts)
+         FROM logs
+         WHERE {}
+         GROUP BY error_fingerprint
+         ORDER BY COUNT(*) DESC
+         LIMIT {}",
+        where_clauses.join(" AND "),
+        limit
+    );
+
+    let conn = db.read()?;
+    let mut stmt = conn
+        .prepare(&query)
+        .map_err(|e| format!("Prepare error: {}", e))?;
+
+    let params_refs: Vec<&dyn rusqlite::ToSql> = params_vec.iter().map(|b| b.as_ref()).collect();
+    let groups_iter = stmt
+        .query_map(params_refs.as_slice(), |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, i64>(1)?,
+                row.get::<_, i64>(2)?,
+                row.get::<_, i64>(3)?,
+            ))
+        })
+        .map_err(|e| format!("Query error: {}", e))?;
+
+    let rows = groups_iter
+        .collect::<SqliteResult<Vec<_>>>()
+        .map_err(|e| format!("Collect error: {}", e))?;
+    drop(stmt);
+
+    // Attach the most recent occurrence as the sample for each group
+    let mut sample_stmt = conn
+        .prepare(
+            "SELECT message, function_path FROM logs
+             WHERE error_fingerprint = ?1
+             ORDER BY ts DESC LIMIT 1",
+        )
+        .map_err(|e| format!("Prepare error: {}", e))?;
+
+    let mut groups = Vec::with_capacity(rows.len());
+    for (fingerprint, count, first_seen_ts, last_seen_ts) in rows {
+        let (sample_message, function_path) = sample_stmt
+            .query_row(params![fingerprint], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, Option<String>>(1)?))
+            })
+            .map_err(|e| format!("Query error: {}", e))?;
+
+        groups.push(ErrorGroup {
+            fingerprint,
+            count,
+            first_seen_ts,
+            last_seen_ts,
+            sample_message,
+            function_path,
+        });
+    }
+
+    Ok(groups)
+}
+
+/// Time-bucketed counts for the log volume chart. Aggregating in SQL keeps
+/// the chart fast where pulling raw rows and bucketing in JS wasn't.
+#[tauri::command]
+pub async fn histogram_logs(
+    db: State<'_, DbConnection>,
+    filters: LogFilters,
+    bucket_size_ms: i64,
+    group_by: Option<String>,
+) -> Result<Vec<HistogramBucket>, String> {
+    let bucket_size_ms = bucket_size_ms.max(1000);
+    let group_expr = match group_by.as_deref() {
+        None => None,
+        Some("level") => Some("COALESCE(level, '')"),
+        Some("function_path") => Some("COALESCE(function_path, '')"),
+        Some(other) => return Err(format!("Unknown group_by: {}", other)),
+    };
+
+    // Same filter compilation as query_logs, minus pagination
+    let mut where_clauses = Vec::new();
+    let mut params_vec: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
+
+    if let Some(ref deployment) = filters.deployment {
+        where_clauses.push("deployment = ?".to_string());
+        params_vec.push(Box::new(deployment.clone()));
+    }
+    if let Some(start_ts) = filters.start_ts {
+        where_clauses.push("ts >= ?".to_string());
+        params_vec.push(Box::new(start_ts));
+    }
+    if let Some(end_ts) = filters.end_ts {
+        where_clauses.push("ts <= ?".to_string());
+        params_vec.push(Box::new(end_ts));
+    }
+    if let Some(ref function_path) = filters.function_path {
+        where_clauses.push("function_path = ?".to_string());
+        params_vec.push(Box::new(function_path.clone()));
+    }
+    if let Some(ref levels) = filters.levels {
+        if !levels.is_empty() {
+            let placeholders = levels.iter().map(|_| "?").collect::<Vec<_>>().join(",");
+            where_clauses.push(format!("level IN ({})", placeholders));
+            for level in levels {
+                params_vec.push(Box::new(level.clone()));
+            }
+        }
+    }
+
+    let where_sql = if where_clauses.is_empty() {
+        String::new()
+    } else {
+        format!("WHERE {}", where_clauses.join(" AND "))
+    };
+
+    let select_group = group_expr.unwrap_or("NULL");
+    let sql = format!(
+        "SELECT (ts / {bucket}) * {bucket} AS bucket_ts, {group} AS grp,
+                COUNT(*),
+                SUM(CASE WHEN success = 0 OR level = 'ERROR' THEN 1 ELSE 0 END),
+                COALESCE(AVG(duration_ms), 0)
+         FROM logs {where_sql}
+         GROUP BY bucket_ts{group_key}
+         ORDER BY bucket_ts ASC",
+        bucket = bucket_size_ms,
+        group = select_group,
+        where_sql = where_sql,
+        group_key = if group_expr.is_some() { ", grp" } else { "" },
+    );
+
+    let conn = db.read()?;
+    let mut stmt = conn
+        .prepare(&sql)
+        .map_err(|e| format!("Prepare error: {}", e))?;
+
+    let params_refs: Vec<&dyn rusqlite::ToSql> = params_vec.iter().map(|b| b.as_ref()).collect();
+    let rows = stmt
+        .query_map(params_refs.as_slice(), |row| {
+            Ok(HistogramBucket {
+                bucket_ts: row.get(0)?,
+                group: row.get(1)?,
+                count: row.get(2)?,
+                error_count: row.get(3)?,
+                avg_duration_ms: row.get(4)?,
+            })
+        })
+        .map_err(|e| format!("Query error: {}", e))?;
+
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Query error: {}", e))
+}
+
+/// Counts of executions per duration bucket, for latency distribution
+/// charts. `bucket_edges` are ascending millisecond boundaries; the last
+/// bucket is open-ended. Bucketing in SQL avoids pulling every row's
+/// json_blob to the frontend.
+#[tauri::command]
+pub async fn get_duration_histogram(
+    db: State<'_, DbConnection>,
+    filters: LogFilters,
+    bucket_edges: Vec<i64>,
+) -> Result<Vec<DurationBucket>, String> {
+    if bucket_edges.is_empty() {
+        return Err("bucket_edges must not be empty".to_string());
+    }
+    if bucket_edges.windows(2).any(|w| w[0] >= w[1]) || bucket_edges[0] < 0 {
+        return Err("bucket_edges must be non-negative and strictly ascending".to_string());
+    }
+
+    // Map each row to a bucket index with a CASE ladder; edges are validated
+    // integers so interpolating them into the SQL is safe
+    let mut case_arms = String::new();
+    for (i, edge) in bucket_edges.iter().enumerate() {
+        case_arms.push_str(&format!("WHEN duration_ms < {} THEN {} ", edge, i));
+    }
+
+    let mut where_clauses = vec!["duration_ms IS NOT NULL".to_string()];
+    let mut params_vec: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
+
+    if let Some(ref deployment) = filters.deployment {
+        where_clauses.push("deployment = ?".to_string());
+        params_vec.push(Box::new(deployment.clone()));
+    }
+    if let Some(start_ts) = filters.start_ts {
+        where_clauses.push("ts >= ?".to_string());
+        params_vec.push(Box::new(start_ts));
+    }
+    if let Some(end_ts) = filters.end_ts {
+        where_clauses.push("ts <= ?".to_string());
+        params_vec.push(Box::new(end_ts));
+    }
+    if let Some(ref function_path) = filters.function_path {
+        where_clauses.push("function_path = ?".to_string());
+        params_vec.push(Box::new(function_path.clone()));
+    }
+    if let Some(success) = filters.success {
+        where_clauses.push("success = ?".to_string());
+        params_vec.push(Box::new(success as i64));
+    }
+
+    let sql = format!(
+        "SELECT CASE {}ELSE {} END AS bucket_idx, COUNT(*)
+         FROM logs
+         WHERE {}
+         GROUP BY bucket_idx",
+        case_arms,
+        bucket_edges.len(),
+        where_clauses.join(" AND "),
+    );
+
+    let conn = db.read()?;
+    let mut stmt = conn
+        .prepare(&sql)
+        .map_err(|e| format!("Prepare error: {}", e))?;
+
+    let params_refs: Vec<&dyn rusqlite::ToSql> = params_vec.iter().map(|b| b.as_ref()).collect();
+    let rows = stmt
+        .query_map(params_refs.as_slice(), |row| {
+            Ok((row.get::<_, usize>(0)?, row.get::<_, i64>(1)?))
+        })
+        .map_err(|e| format!("Query error: {}", e))?;
+
+    // Emit every bucket, including empty ones, so the chart's x-axis is
+    // stable regardless of the data
+    let mut counts = vec![0i64; bucket_edges.len() + 1];
+    for row in rows {
+        let (idx, count) = row.map_err(|e| format!("Collect error: {}", e))?;
+        if let Some(slot) = counts.get_mut(idx) {
+            *slot = count;
+        }
+    }
+
+    let mut buckets = Vec::with_capacity(counts.len());
+    let mut lower = 0i64;
+    for (i, count) in counts.into_iter().enumerate() {
+        let upper = bucket_edges.get(i).copied();
+        buckets.push(DurationBucket {
+            lower_ms: lower,
+            upper_ms: upper,
+            count,
+        });
+        if let Some(upper) = upper {
+            lower = upper;
+        }
+    }
+
+    Ok(buckets)
+}
+
+/// Ranked function lists (most called, most failing, slowest) for the
+/// Insights tab. One grouped scan feeds all three rankings.
+#[tauri::command]
+pub async fn get_insights(
+    db: State<'_, DbConnection>,
+    filters: LogFilters,
+    n: Option<i32>,
+) -> Result<Insights, String> {
+    let n = n.unwrap_or(10).clamp(1, 100) as usize;
+
+    let mut where_clauses = vec!["function_path IS NOT NULL".to_string()];
+    let mut params_vec: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
+
+    if let Some(ref deployment) = filters.deployment {
+        where_clauses.push("deployment = ?".to_string());
+        params_vec.push(Box::new(deployment.clone()));
+    }
+    if let Some(start_ts) = filters.start_ts {
+        where_clauses.push("ts >= ?".to_string());
+        params_vec.push(Box::new(start_ts));
+    }
+    if let Some(end_ts) = filters.end_ts {
+        where_clauses.push("ts <= ?".to_string());
+        params_vec.push(Box::new(end_ts));
+    }
+
+    let sql = format!(
+        "SELECT function_path,
+                COUNT(*),
+                SUM(CASE WHEN success = 0 OR level = 'ERROR' THEN 1 ELSE 0 END),
+                COALESCE(AVG(duration_ms), 0),
+                COALESCE(MAX(duration_ms), 0)
+         FROM logs
+         WHERE {}
+         GROUP BY function_path",
+        where_clauses.join(" AND "),
+    );
+
+    let conn = db.read()?;
+    let mut stmt = conn
+        .prepare(&sql)
+        .map_err(|e| format!("Prepare error: {}", e))?;
+
+    let params_refs: Vec<&dyn rusqlite::ToSql> = params_vec.iter().map(|b| b.as_ref()).collect();
+    let rows = stmt
+        .query_map(params_refs.as_slice(), |row| {
+            let calls: i64 = row.get(1)?;
+            let errors: i64 = row.get(2)?;
+            Ok(FunctionInsight {
+                function_path: row.get(0)?,
+                calls,
+                errors,
+                failure_rate_pct: if calls > 0 {
+                    errors as f64 * 100.0 / calls as f64
+                } else {
+                    0.0
+                },
+                avg_duration_ms: row.get(3)?,
+                max_duration_ms: row.get(4)?,
+            })
+        })
+        .map_err(|e| format!("Query error: {}", e))?;
+
+    let all = rows
+        .collect::<SqliteResult<Vec<_>>>()
+        .map_err(|e| format!("Collect error: {}", e))?;
+
+    let mut most_called = all.clone();
+    most_called.sort_by(|a, b| b.calls.cmp(&a.calls));
+    most_called.truncate(n);
+
+    let mut most_failing: Vec<FunctionInsight> =
+        all.iter().filter(|f| f.errors > 0).cloned().collect();
+    most_failing.sort_by(|a, b| {
+        b.failure_rate_pct
+            .partial_cmp(&a.failure_rate_pct)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then(b.errors.cmp(&a.errors))
+    });
+    most_failing.truncate(n);
+
+    let mut slowest: Vec<FunctionInsight> = all
+        .into_iter()
+        .filter(|f| f.avg_duration_ms > 0.0)
+        .collect();
+    slowest.sort_by(|a, b| {
+        b.avg_duration_ms
+            .partial_cmp(&a.avg_duration_ms)
+            .unwrap_or(std::cmp::Ordering::Equal)
+    });
+    slowest.truncate(n);
+
+    Ok(Insights {
+        most_called,
+        most_failing,
+        slowest,
+    })
+}
+
+/// Save a named search so complex filters survive restarts. Saving under an
+/// existing name replaces it.
+#[tauri::command]
+pub async fn create_saved_search(
+    db: State<'_, DbConnection>,
+    name: String,
+    filters: LogFilters,
+    fts_query: Option<String>,
+) -> Result<SavedSearch, String> {
+    let name = name.trim().to_string();
+    if name.is_empty() {
+        return Err("Search name cannot be empty".to_string());
+    }
+
+    let filters_json = serde_json::to_string(&filters)
+        .map_err(|e| format!("Failed to serialize filters: {}", e))?;
+    let created_at = chrono::Utc::now().timestamp_millis();
+
+    let conn = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+    conn.execute(
+        "INSERT INTO saved_searches (name, filters_json, fts_query, created_at)
+         VALUES (?1, ?2, ?3, ?4)
+         ON CONFLICT(name) DO UPDATE SET
+            filters_json = excluded.filters_json,
+            fts_query = excluded.fts_query",
+        params![name, filters_json, fts_query, created_at],
+    )
+    .map_err(|e| format!("Insert error: {}", e))?;
+
+    let (id, created_at) = conn
+        .query_row(
+            "SELECT id, created_at FROM saved_searches WHERE name = ?1",
+            params![name],
+            |row| Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)?)),
+        )
+        .map_err(|e| format!("Query error: {}", e))?;
+
+    Ok(SavedSearch {
+        id,
+        name,
+        filters,
+        fts_query,
+        created_at,
+    })
+}
+
+/// All saved searches, alphabetically by name
+#[tauri::command]
+pub async fn list_saved_searches(
+    db: State<'_, DbConnection>,
+) -> Result<Vec<SavedSearch>, String> {
+    let conn = db.read()?;
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, name, filters_json, fts_query, created_at
+             FROM saved_searches ORDER BY name ASC",
+        )
+        .map_err(|e| format!("Prepare error: {}", e))?;
+
+    let searches_iter = stmt
+        .query_map([], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+                row.get::<_, Option<String>>(3)?,
+                row.get::<_, i64>(4)?,
+            ))
+        })
+        .map_err(|e| format!("Query error: {}", e))?;
+
+    let mut searches = Vec::new();
+    for row in searches_iter {
+        let (id, name, filters_json, fts_query, created_at) =
+            row.map_err(|e| format!("Collect error: {}", e))?;
+        searches.push(SavedSearch {
+            id,
+            name,
+            filters: serde_json::from_str(&filters_json).unwrap_or_default(),
+            fts_query,
+            created_at,
+        });
+    }
+
+    Ok(searches)
+}
+
+/// Delete a saved search by id
+#[tauri::command]
+pub async fn delete_saved_search(
+    db: State<'_, DbConnection>,
+    search_id: i64,
+) -> Result<bool, String> {
+    let conn = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+    let deleted = conn
+        .execute("DELETE FROM saved_searches WHERE id = ?1", params![search_id])
+        .map_err(|e| format!("Delete error: {}", e))?;
+    Ok(deleted > 0)
+}
+
+/// Attach a tag to a log entry. Idempotent; tagging twice is a no-op.
+#[tauri::command]
+pub async fn tag_log(
+    db: State<'_, DbConnection>,
+    log_id: String,
+    tag: String,
+) -> Result<(), String> {
+    let tag = tag.trim().to_string();
+    if tag.is_empty() {
+        return Err("Tag must not be empty".to_string());
+    }
+    if tag.starts_with("__") {
+        return Err("Tags starting with '__' are reserved".to_string());
+    }
+
+    let conn = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+    let now = chrono::Utc::now().timestamp_millis();
+    conn.execute(
+        "INSERT OR IGNORE INTO log_tags (log_id, tag, created_at) VALUES (?1, ?2, ?3)",
+        params![log_id, tag, now],
+    )
+    .map_err(|e| format!("Insert error: {}", e))?;
+    Ok(())
+}
+
+/// Remove a tag from a log entry
+#[tauri::command]
+pub async fn untag_log(
+    db: State<'_, DbConnection>,
+    log_id: String,
+    tag: String,
+) -> Result<bool, String> {
+    let conn = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+    let deleted = conn
+        .execute(
+            "DELETE FROM log_tags WHERE log_id = ?1 AND tag = ?2",
+            params![log_id, tag],
+        )
+        .map_err(|e| format!("Delete error: {}", e))?;
+    Ok(deleted > 0)
+}
+
+/// Star or unstar a log entry, backed by the reserved star tag
+#[tauri::command]
+pub async fn star_log(
+    db: State<'_, DbConnection>,
+    log_id: String,
+    starred: bool,
+) -> Result<(), String> {
+    let conn = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+    if starred {
+        let now = chrono::Utc::now().timestamp_millis();
+        conn.execute(
+            "INSERT OR IGNORE INTO log_tags (log_id, tag, created_at) VALUES (?1, ?2, ?3)",
+            params![log_id, STARRED_TAG, now],
+        )
+        .map_err(|e| format!("Insert error: {}", e))?;
+    } else {
+        conn.execute(
+            "DELETE FROM log_tags WHERE log_id = ?1 AND tag = ?2",
+            params![log_id, STARRED_TAG],
+        )
+        .map_err(|e| format!("Delete error: {}", e))?;
+    }
+    Ok(())
+}
+
+/// Tags on a single log entry (excluding the reserved star tag), plus
+/// whether it's starred
+#[tauri::command]
+pub async fn get_log_tags(
+    db: State<'_, DbConnection>,
+    log_id: String,
+) -> Result<(Vec<String>, bool), String> {
+    let conn = db.read()?;
+    let mut stmt = conn
+        .prepare("SELECT tag FROM log_tags WHERE log_id = ?1 ORDER BY tag")
+        .map_err(|e| format!("Prepare error: {}", e))?;
+    let tags_iter = stmt
+        .query_map(params![log_id], |row| row.get::<_, String>(0))
+        .map_err(|e| format!("Query error: {}", e))?;
+
+    let all = tags_iter
+        .collect::<SqliteResult<Vec<_>>>()
+        .map_err(|e| format!("Collect error: {}", e))?;
+
+    let starred = all.iter().any(|t| t == STARRED_TAG);
+    let tags = all.into_iter().filter(|t| t != STARRED_TAG).collect();
+    Ok((tags, starred))
+}
+
+/// Set (or clear, with empty text) the annotation on a log entry. Notes
+/// come back on query results via the log_notes join.
+#[tauri::command]
+pub async fn set_log_note(
+    db: State<'_, DbConnection>,
+    log_id: String,
+    note: String,
+) -> Result<(), String> {
+    let conn = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+
+    if note.trim().is_empty() {
+        conn.execute("DELETE FROM log_notes WHERE log_id = ?1", params![log_id])
+            .map_err(|e| format!("Delete error: {}", e))?;
+        return Ok(());
+    }
+
+    let now = chrono::Utc::now().timestamp_millis();
+    conn.execute(
+        "INSERT INTO log_notes (log_id, note, updated_at) VALUES (?1, ?2, ?3)
+         ON CONFLICT(log_id) DO UPDATE SET note = excluded.note, updated_at = excluded.updated_at",
+        params![log_id, note, now],
+    )
+    .map_err(|e| format!("Insert error: {}", e))?;
+    Ok(())
+}
+
+/// All annotations, newest first, optionally narrowed to one deployment
+#[tauri::command]
+pub async fn get_log_notes(
+    db: State<'_, DbConnection>,
+    deployment: Option<String>,
+) -> Result<Vec<LogNote>, String> {
+    let conn = db.read()?;
+
+    let mut sql = "SELECT log_notes.log_id, log_notes.note, log_notes.updated_at
+                   FROM log_notes
+                   JOIN logs ON logs.id = log_notes.log_id"
+        .to_string();
+    let mut params_vec: Vec<Box<dyn rusqlite::ToSql>> = Vec::new();
+    if let Some(deployment) = deployment {
+        sql.push_str(" WHERE logs.deployment = ?");
+        params_vec.push(Box::new(deployment));
+    }
+    sql.push_str(" ORDER BY log_notes.updated_at DESC");
+
+    let mut stmt = conn
+        .prepare(&sql)
+        .map_err(|e| format!("Prepare error: {}", e))?;
+    let params_refs: Vec<&dyn rusqlite::ToSql> = params_vec.iter().map(|b| b.as_ref()).collect();
+    let notes_iter = stmt
+        .query_map(params_refs.as_slice(), |row| {
+            Ok(LogNote {
+                log_id: row.get(0)?,
+                note: row.get(1)?,
+                updated_at: row.get(2)?,
+            })
+        })
+        .map_err(|e| format!("Query error: {}", e))?;
+
+    notes_iter
+        .collect::<SqliteResult<Vec<_>>>()
+        .map_err(|e| format!("Collect error: {}", e))
+}
+
+/// Record a batch of network test results for connection-quality trends
+#[tauri::command]
+pub async fn record_network_samples(
+    db: State<'_, DbConnection>,
+    samples: Vec<NetworkSample>,
+) -> Result<(), String> {
+    let conn = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+
+    for sample in samples {
+        conn.execute(
+            "INSERT INTO network_history (ts, check_name, success, latency_ms)
+             VALUES (?1, ?2, ?3, ?4)",
+            params![
+                sample.ts,
+                sample.check_name,
+                if sample.success { 1 } else { 0 },
+                sample.latency_ms,
+            ],
+        )
+        .map_err(|e| format!("Insert error: {}", e))?;
+    }
+
+    Ok(())
+}
+
+/// Get network samples from the last `range_ms` milliseconds, oldest first,
+/// so the UI can chart connection quality over time
+#[tauri::command]
+pub async fn get_network_history(
+    db: State<'_, DbConnection>,
+    range_ms: i64,
+) -> Result<Vec<NetworkSample>, String> {
+    let conn = db.read()?;
+
+    let cutoff_ts = chrono::Utc::now().timestamp_millis() - range_ms;
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT ts, check_name, success, latency_ms
+             FROM network_history
+             WHERE ts >= ?
+             ORDER BY ts ASC",
+        )
+        .map_err(|e| format!("Prepare error: {}", e))?;
+
+    let samples = stmt
+        .query_map(params![cutoff_ts], |row| {
+            Ok(NetworkSample {
+                ts: row.get(0)?,
+                check_name: row.get(1)?,
+                success: row.get::<_, i32>(2)? != 0,
+                latency_ms: row.get(3)?,
+            })
+        })
+        .map_err(|e| format!("Query error: {}", e))?
+        .collect::<SqliteResult<Vec<_>>>()
+        .map_err(|e| format!("Collect error: {}", e))?;
+
+    Ok(samples)
+}
+
 /// Get log store settings
 #[tauri::command]
 pub async fn get_log_store_settings(
@@ -485,9 +1656,31 @@ pub async fn get_log_store_settings(
         })
         .unwrap_or(true);
     
+    let max_db_size_mb: Option<i64> = conn
+        .query_row(
+            "SELECT value FROM settings WHERE key = 'max_db_size_mb'",
+            [],
+            |row| {
+                let val: String = row.get(0)?;
+                Ok(val.parse().ok().filter(|mb| *mb > 0))
+            },
+        )
+        .unwrap_or(None);
+
+    let archive_dir: Option<String> = conn
+        .query_row(
+            "SELECT value FROM settings WHERE key = 'archive_dir'",
+            [],
+            |row| row.get::<_, String>(0),
+        )
+        .ok()
+        .filter(|dir| !dir.trim().is_empty());
+
     Ok(LogStoreSettings {
         retention_days,
         enabled,
+        max_db_size_mb,
+        archive_dir,
     })
 }
 
@@ -510,10 +1703,59 @@ pub async fn set_log_store_settings(
         params![if settings.enabled { "true" } else { "false" }],
     )
     .map_err(|e| format!("Update error: {}", e))?;
-    
+
+    // 0 means no size limit
+    conn.execute(
+        "INSERT OR REPLACE INTO settings (key, value) VALUES ('max_db_size_mb', ?)",
+        params![settings.max_db_size_mb.unwrap_or(0).to_string()],
+    )
+    .map_err(|e| format!("Update error: {}", e))?;
+
+    // Empty means archiving off
+    conn.execute(
+        "INSERT OR REPLACE INTO settings (key, value) VALUES ('archive_dir', ?)",
+        params![settings.archive_dir.unwrap_or_default()],
+    )
+    .map_err(|e| format!("Update error: {}", e))?;
+
     Ok(())
 }
 
+/// Set or clear a per-deployment retention override (None removes it)
+#[tauri::command]
+pub async fn set_deployment_retention(
+    db: State<'_, DbConnection>,
+    deployment: String,
+    days: Option<i32>,
+) -> Result<(), String> {
+    let key = format!("retention_days:{}", deployment);
+    let conn = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+
+    match days {
+        Some(days) if days > 0 => {
+            conn.execute(
+                "INSERT OR REPLACE INTO settings (key, value) VALUES (?, ?)",
+                params![key, days.to_string()],
+            )
+            .map_err(|e| format!("Update error: {}", e))?;
+        }
+        _ => {
+            conn.execute("DELETE FROM settings WHERE key = ?", params![key])
+                .map_err(|e| format!("Delete error: {}", e))?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Per-deployment retention overrides as (deployment, days) pairs
+#[tauri::command]
+pub async fn list_deployment_retention(
+    db: State<'_, DbConnection>,
+) -> Result<Vec<(String, i32)>, String> {
+    Ok(super::retention::get_retention_overrides(&db))
+}
+
 /// Clear all logs
 #[tauri::command]
 pub async fn clear_all_logs(db: State<'_, DbConnection>) -> Result<(), String> {
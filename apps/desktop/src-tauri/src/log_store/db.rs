@@ -1,23 +1,129 @@
-use rusqlite::{Connection, Result};
+use rusqlite::{Connection, OpenFlags, Result};
+use std::ops::Deref;
 use std::path::PathBuf;
 use std::sync::{Arc, Mutex};
 use tauri::{AppHandle, Manager};
 
-/// Thread-safe database connection wrapper
-pub type DbConnection = Arc<Mutex<Connection>>;
+/// How many idle read-only connections the pool keeps around
+const MAX_IDLE_READERS: usize = 4;
+
+/// The log database handle: one writer connection plus a small pool of
+/// read-only connections. WAL mode lets readers run while a long ingest
+/// batch or VACUUM holds the writer, so `query_logs`/`search_logs` no
+/// longer block behind them.
+pub struct DbPool {
+    writer: Mutex<Connection>,
+    readers: Mutex<Vec<Connection>>,
+    db_path: PathBuf,
+}
+
+pub type DbConnection = Arc<DbPool>;
+
+impl DbPool {
+    /// The writer connection. Keeps the `lock` name from the plain-mutex
+    /// days so write paths read the same as before the pool existed.
+    pub fn lock(&self) -> std::sync::LockResult<std::sync::MutexGuard<'_, Connection>> {
+        self.writer.lock()
+    }
+
+    /// A read-only connection, reused from the pool or opened on demand
+    pub fn read(&self) -> std::result::Result<ReadConnection<'_>, String> {
+        let idle = self.readers.lock().unwrap().pop();
+        let conn = match idle {
+            Some(conn) => conn,
+            None => open_reader(&self.db_path)
+                .map_err(|e| format!("Failed to open read connection: {}", e))?,
+        };
+        Ok(ReadConnection {
+            pool: self,
+            conn: Some(conn),
+        })
+    }
+}
+
+/// A pooled read-only connection; returns to the pool on drop
+pub struct ReadConnection<'a> {
+    pool: &'a DbPool,
+    conn: Option<Connection>,
+}
+
+impl Deref for ReadConnection<'_> {
+    type Target = Connection;
+
+    fn deref(&self) -> &Connection {
+        self.conn.as_ref().expect("connection taken")
+    }
+}
+
+impl Drop for ReadConnection<'_> {
+    fn drop(&mut self) {
+        if let Some(conn) = self.conn.take() {
+            let mut readers = self.pool.readers.lock().unwrap();
+            if readers.len() < MAX_IDLE_READERS {
+                readers.push(conn);
+            }
+        }
+    }
+}
+
+fn open_reader(db_path: &std::path::Path) -> Result<Connection> {
+    let conn = Connection::open_with_flags(
+        db_path,
+        OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_URI | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    )?;
+    conn.execute_batch(
+        "
+        PRAGMA temp_store=MEMORY;
+        PRAGMA cache_size=-16000;
+        PRAGMA busy_timeout=5000;
+        ",
+    )?;
+    register_regexp(&conn)?;
+    Ok(conn)
+}
+
+/// Register a `REGEXP` implementation backed by the regex crate, so exact
+/// patterns (request ids, JSON keys) survive where FTS tokenization drops
+/// them. The compiled regex is cached on the statement via aux data.
+fn register_regexp(conn: &Connection) -> Result<()> {
+    use rusqlite::functions::FunctionFlags;
+
+    conn.create_scalar_function(
+        "regexp",
+        2,
+        FunctionFlags::SQLITE_UTF8 | FunctionFlags::SQLITE_DETERMINISTIC,
+        |ctx| {
+            let pattern: std::sync::Arc<regex::Regex> = ctx.get_or_create_aux(
+                0,
+                |vr| -> std::result::Result<_, Box<dyn std::error::Error + Send + Sync>> {
+                    Ok(regex::Regex::new(vr.as_str()?)?)
+                },
+            )?;
+            let text = ctx
+                .get_raw(1)
+                .as_str()
+                .map_err(|e| rusqlite::Error::UserFunctionError(e.into()))?;
+            Ok(pattern.is_match(text))
+        },
+    )
+}
 
 /// Initialize database at the given path and run migrations
 pub fn init_db(app_handle: &AppHandle) -> Result<DbConnection> {
-    let db_path = get_db_path(app_handle);
-    
+    open_db_at(&get_db_path(app_handle))
+}
+
+/// Open (or create) the log database at an explicit path. Also used by the
+/// companion CLI, which has no Tauri app handle.
+pub fn open_db_at(db_path: &std::path::Path) -> Result<DbConnection> {
     // Ensure parent directory exists
     if let Some(parent) = db_path.parent() {
         std::fs::create_dir_all(parent)
             .map_err(|e| rusqlite::Error::ToSqlConversionFailure(Box::new(e)))?;
     }
 
-    let conn = Connection::open(&db_path)?;
-    
+    let conn = Connection::open(db_path)?;
+
     // Set pragmas for performance and safety
     conn.execute_batch(
         "
@@ -26,13 +132,19 @@ pub fn init_db(app_handle: &AppHandle) -> Result<DbConnection> {
         PRAGMA temp_store=MEMORY;
         PRAGMA foreign_keys=ON;
         PRAGMA cache_size=-64000;
+        PRAGMA busy_timeout=5000;
         ",
     )?;
 
     // Run migrations
     run_migrations(&conn)?;
+    register_regexp(&conn)?;
 
-    Ok(Arc::new(Mutex::new(conn)))
+    Ok(Arc::new(DbPool {
+        writer: Mutex::new(conn),
+        readers: Mutex::new(Vec::new()),
+        db_path: db_path.to_path_buf(),
+    }))
 }
 
 /// Get the path to the database file
@@ -45,60 +157,261 @@ fn get_db_path(app_handle: &AppHandle) -> PathBuf {
     app_data_dir.join("convex-logs.db")
 }
 
-/// Run database migrations
-fn run_migrations(conn: &Connection) -> Result<()> {
-    // Create logs table
-    conn.execute_batch(
-        "
-        CREATE TABLE IF NOT EXISTS logs (
-            id TEXT PRIMARY KEY,
-            ts INTEGER NOT NULL,
-            deployment TEXT NOT NULL,
-            request_id TEXT,
-            execution_id TEXT,
-            topic TEXT,
-            level TEXT,
-            function_path TEXT,
-            function_name TEXT,
-            udf_type TEXT,
-            success INTEGER,
-            duration_ms INTEGER,
-            message TEXT NOT NULL,
-            json_blob TEXT NOT NULL,
-            created_at INTEGER NOT NULL
-        );
+/// One schema migration step, applied when the database's `user_version`
+/// is below `version`
+struct Migration {
+    version: i32,
+    name: &'static str,
+    step: MigrationStep,
+}
 
-        CREATE INDEX IF NOT EXISTS idx_logs_ts ON logs(ts DESC);
-        CREATE INDEX IF NOT EXISTS idx_logs_deployment_ts ON logs(deployment, ts DESC);
-        CREATE INDEX IF NOT EXISTS idx_logs_request_id ON logs(request_id) WHERE request_id IS NOT NULL;
-        CREATE INDEX IF NOT EXISTS idx_logs_function_ts ON logs(function_path, ts DESC) WHERE function_path IS NOT NULL;
-        CREATE INDEX IF NOT EXISTS idx_logs_level_ts ON logs(level, ts DESC) WHERE level IS NOT NULL;
-        CREATE INDEX IF NOT EXISTS idx_logs_success_ts ON logs(success, ts DESC) WHERE success IS NOT NULL;
-
-        -- FTS5 table for full-text search
-        CREATE VIRTUAL TABLE IF NOT EXISTS logs_fts USING fts5(
-            message,
-            function_path,
-            function_name,
-            request_id,
-            content='logs',
-            content_rowid='rowid',
-            tokenize='porter unicode61'
-        );
+enum MigrationStep {
+    /// Plain DDL, executed as a batch
+    Sql(&'static str),
+    /// Steps that must inspect the schema first (ALTER TABLE has no
+    /// IF NOT EXISTS, triggers error on re-creation)
+    Apply(fn(&Connection) -> Result<()>),
+}
 
-        -- Settings table
-        CREATE TABLE IF NOT EXISTS settings (
-            key TEXT PRIMARY KEY,
-            value TEXT NOT NULL
-        );
+/// Ordered migration history. Append-only: never edit or reorder shipped
+/// entries — add a new version instead. The early steps keep IF NOT EXISTS
+/// and catalog guards because databases created before versioning report
+/// `user_version` 0 and replay the whole list.
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        name: "baseline schema",
+        step: MigrationStep::Sql(
+            "
+            CREATE TABLE IF NOT EXISTS logs (
+                id TEXT PRIMARY KEY,
+                ts INTEGER NOT NULL,
+                deployment TEXT NOT NULL,
+                request_id TEXT,
+                execution_id TEXT,
+                topic TEXT,
+                level TEXT,
+                function_path TEXT,
+                function_name TEXT,
+                udf_type TEXT,
+                success INTEGER,
+                duration_ms INTEGER,
+                message TEXT NOT NULL,
+                json_blob TEXT NOT NULL,
+                created_at INTEGER NOT NULL
+            );
 
-        INSERT OR IGNORE INTO settings (key, value) VALUES ('retention_days', '30');
-        INSERT OR IGNORE INTO settings (key, value) VALUES ('enabled', 'true');
-        ",
-    )?;
+            CREATE INDEX IF NOT EXISTS idx_logs_ts ON logs(ts DESC);
+            CREATE INDEX IF NOT EXISTS idx_logs_deployment_ts ON logs(deployment, ts DESC);
+            CREATE INDEX IF NOT EXISTS idx_logs_request_id ON logs(request_id) WHERE request_id IS NOT NULL;
+            CREATE INDEX IF NOT EXISTS idx_logs_function_ts ON logs(function_path, ts DESC) WHERE function_path IS NOT NULL;
+            CREATE INDEX IF NOT EXISTS idx_logs_level_ts ON logs(level, ts DESC) WHERE level IS NOT NULL;
+            CREATE INDEX IF NOT EXISTS idx_logs_success_ts ON logs(success, ts DESC) WHERE success IS NOT NULL;
+
+            -- FTS5 table for full-text search
+            CREATE VIRTUAL TABLE IF NOT EXISTS logs_fts USING fts5(
+                message,
+                function_path,
+                function_name,
+                request_id,
+                content='logs',
+                content_rowid='rowid',
+                tokenize='porter unicode61'
+            );
+
+            -- Settings table
+            CREATE TABLE IF NOT EXISTS settings (
+                key TEXT PRIMARY KEY,
+                value TEXT NOT NULL
+            );
+
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('retention_days', '30');
+            INSERT OR IGNORE INTO settings (key, value) VALUES ('enabled', 'true');
+            ",
+        ),
+    },
+    Migration {
+        version: 2,
+        name: "FTS triggers",
+        step: MigrationStep::Apply(create_fts_triggers),
+    },
+    Migration {
+        version: 3,
+        name: "network history",
+        step: MigrationStep::Sql(
+            "
+            -- Network test history for connection-quality trends
+            CREATE TABLE IF NOT EXISTS network_history (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                ts INTEGER NOT NULL,
+                check_name TEXT NOT NULL,
+                success INTEGER NOT NULL,
+                latency_ms INTEGER
+            );
 
-    // Create FTS triggers if they don't exist
-    // We need to check if triggers exist first to avoid errors on re-creation
+            CREATE INDEX IF NOT EXISTS idx_network_history_ts ON network_history(ts DESC);
+            ",
+        ),
+    },
+    Migration {
+        version: 4,
+        name: "function metric rollups",
+        step: MigrationStep::Sql(
+            "
+            -- Per-minute function metric rollups (see metrics_store)
+            CREATE TABLE IF NOT EXISTS function_metrics (
+                deployment TEXT NOT NULL,
+                function_path TEXT NOT NULL,
+                minute_ts INTEGER NOT NULL,
+                invocations INTEGER NOT NULL DEFAULT 0,
+                errors INTEGER NOT NULL DEFAULT 0,
+                total_duration_ms INTEGER NOT NULL DEFAULT 0,
+                max_duration_ms INTEGER NOT NULL DEFAULT 0,
+                PRIMARY KEY (deployment, function_path, minute_ts)
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_function_metrics_ts ON function_metrics(minute_ts DESC);
+            ",
+        ),
+    },
+    Migration {
+        version: 5,
+        name: "schema snapshots",
+        step: MigrationStep::Sql(
+            "
+            -- Versioned deployment schema snapshots (see schema_store)
+            CREATE TABLE IF NOT EXISTS schema_snapshots (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                deployment TEXT NOT NULL,
+                ts INTEGER NOT NULL,
+                hash TEXT NOT NULL,
+                schema_json TEXT NOT NULL
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_schema_snapshots_deployment_ts
+                ON schema_snapshots(deployment, ts DESC);
+            ",
+        ),
+    },
+    Migration {
+        version: 6,
+        name: "cron runs",
+        step: MigrationStep::Sql(
+            "
+            -- Expected vs actual cron executions (see cron_monitor)
+            CREATE TABLE IF NOT EXISTS cron_runs (
+                deployment TEXT NOT NULL,
+                name TEXT NOT NULL,
+                scheduled_ts INTEGER NOT NULL,
+                actual_ts INTEGER,
+                status TEXT NOT NULL DEFAULT 'pending',
+                PRIMARY KEY (deployment, name, scheduled_ts)
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_cron_runs_deployment_ts
+                ON cron_runs(deployment, scheduled_ts DESC);
+            ",
+        ),
+    },
+    Migration {
+        version: 7,
+        name: "error fingerprint column",
+        step: MigrationStep::Apply(add_error_fingerprint),
+    },
+    Migration {
+        version: 8,
+        name: "alert rules",
+        step: MigrationStep::Sql(
+            "
+            -- Alert rules evaluated at ingest (see alerts)
+            CREATE TABLE IF NOT EXISTS alert_rules (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL,
+                enabled INTEGER NOT NULL DEFAULT 1,
+                kind TEXT NOT NULL,
+                level TEXT,
+                pattern TEXT,
+                function_path TEXT,
+                threshold_pct REAL,
+                window_minutes INTEGER,
+                cooldown_minutes INTEGER NOT NULL DEFAULT 5
+            );
+            ",
+        ),
+    },
+    Migration {
+        version: 9,
+        name: "saved searches",
+        step: MigrationStep::Sql(
+            "
+            -- Saved log searches (name + filters + FTS query)
+            CREATE TABLE IF NOT EXISTS saved_searches (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL UNIQUE,
+                filters_json TEXT NOT NULL,
+                fts_query TEXT,
+                created_at INTEGER NOT NULL
+            );
+            ",
+        ),
+    },
+    Migration {
+        version: 10,
+        name: "log lines child table",
+        step: MigrationStep::Sql(
+            "
+            -- One row per console line of a function execution, so individual
+            -- lines keep their own level instead of being flattened into the
+            -- parent's message string
+            CREATE TABLE IF NOT EXISTS log_lines (
+                log_id TEXT NOT NULL REFERENCES logs(id) ON DELETE CASCADE,
+                line_index INTEGER NOT NULL,
+                level TEXT,
+                ts INTEGER NOT NULL,
+                message TEXT NOT NULL,
+                PRIMARY KEY (log_id, line_index)
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_log_lines_level ON log_lines(level) WHERE level IS NOT NULL;
+            ",
+        ),
+    },
+    Migration {
+        version: 11,
+        name: "log tags",
+        step: MigrationStep::Sql(
+            "
+            -- User-applied tags on log entries; starring is the reserved
+            -- '__starred__' tag (see commands)
+            CREATE TABLE IF NOT EXISTS log_tags (
+                log_id TEXT NOT NULL REFERENCES logs(id) ON DELETE CASCADE,
+                tag TEXT NOT NULL,
+                created_at INTEGER NOT NULL,
+                PRIMARY KEY (log_id, tag)
+            );
+
+            CREATE INDEX IF NOT EXISTS idx_log_tags_tag ON log_tags(tag);
+            ",
+        ),
+    },
+    Migration {
+        version: 12,
+        name: "log notes",
+        step: MigrationStep::Sql(
+            "
+            -- One free-form annotation per log entry (see set_log_note)
+            CREATE TABLE IF NOT EXISTS log_notes (
+                log_id TEXT PRIMARY KEY REFERENCES logs(id) ON DELETE CASCADE,
+                note TEXT NOT NULL,
+                updated_at INTEGER NOT NULL
+            );
+            ",
+        ),
+    },
+];
+
+fn create_fts_triggers(conn: &Connection) -> Result<()> {
+    // Guarded because pre-versioning databases already have the triggers
     let trigger_exists: bool = conn
         .query_row(
             "SELECT COUNT(*) > 0 FROM sqlite_master WHERE type='trigger' AND name='logs_ai'",
@@ -107,27 +420,77 @@ fn run_migrations(conn: &Connection) -> Result<()> {
         )
         .unwrap_or(false);
 
-    if !trigger_exists {
-        conn.execute_batch(
-            "
-            CREATE TRIGGER logs_ai AFTER INSERT ON logs BEGIN
-                INSERT INTO logs_fts(rowid, message, function_path, function_name, request_id)
-                VALUES (new.rowid, new.message, new.function_path, new.function_name, new.request_id);
-            END;
-
-            CREATE TRIGGER logs_ad AFTER DELETE ON logs BEGIN
-                INSERT INTO logs_fts(logs_fts, rowid, message, function_path, function_name, request_id)
-                VALUES ('delete', old.rowid, old.message, old.function_path, old.function_name, old.request_id);
-            END;
-
-            CREATE TRIGGER logs_au AFTER UPDATE ON logs BEGIN
-                INSERT INTO logs_fts(logs_fts, rowid, message, function_path, function_name, request_id)
-                VALUES ('delete', old.rowid, old.message, old.function_path, old.function_name, old.request_id);
-                INSERT INTO logs_fts(rowid, message, function_path, function_name, request_id)
-                VALUES (new.rowid, new.message, new.function_path, new.function_name, new.request_id);
-            END;
-            ",
-        )?;
+    if trigger_exists {
+        return Ok(());
+    }
+
+    conn.execute_batch(
+        "
+        CREATE TRIGGER logs_ai AFTER INSERT ON logs BEGIN
+            INSERT INTO logs_fts(rowid, message, function_path, function_name, request_id)
+            VALUES (new.rowid, new.message, new.function_path, new.function_name, new.request_id);
+        END;
+
+        CREATE TRIGGER logs_ad AFTER DELETE ON logs BEGIN
+            INSERT INTO logs_fts(logs_fts, rowid, message, function_path, function_name, request_id)
+            VALUES ('delete', old.rowid, old.message, old.function_path, old.function_name, old.request_id);
+        END;
+
+        CREATE TRIGGER logs_au AFTER UPDATE ON logs BEGIN
+            INSERT INTO logs_fts(logs_fts, rowid, message, function_path, function_name, request_id)
+            VALUES ('delete', old.rowid, old.message, old.function_path, old.function_name, old.request_id);
+            INSERT INTO logs_fts(rowid, message, function_path, function_name, request_id)
+            VALUES (new.rowid, new.message, new.function_path, new.function_name, new.request_id);
+        END;
+        ",
+    )
+}
+
+fn add_error_fingerprint(conn: &Connection) -> Result<()> {
+    // CREATE TABLE IF NOT EXISTS won't add columns and ALTER TABLE has no
+    // IF NOT EXISTS, so check pragma_table_info first
+    let has_fingerprint: bool = conn
+        .query_row(
+            "SELECT COUNT(*) > 0 FROM pragma_table_info('logs') WHERE name = 'error_fingerprint'",
+            [],
+            |row| row.get(0),
+        )
+        .unwrap_or(false);
+
+    if !has_fingerprint {
+        conn.execute_batch("ALTER TABLE logs ADD COLUMN error_fingerprint TEXT;")?;
+    }
+
+    conn.execute_batch(
+        "CREATE INDEX IF NOT EXISTS idx_logs_fingerprint
+            ON logs(error_fingerprint, ts DESC) WHERE error_fingerprint IS NOT NULL;",
+    )
+}
+
+/// Apply every migration the database hasn't seen yet, each in its own
+/// transaction. `PRAGMA user_version` is bumped inside the transaction, so
+/// a failed step rolls back both the DDL and the version and is retried on
+/// the next startup.
+fn run_migrations(conn: &Connection) -> Result<()> {
+    let current: i32 = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+
+    for migration in MIGRATIONS {
+        if migration.version <= current {
+            continue;
+        }
+
+        let tx = conn.unchecked_transaction()?;
+        match migration.step {
+            MigrationStep::Sql(sql) => tx.execute_batch(sql)?,
+            MigrationStep::Apply(apply) => apply(&tx)?,
+        }
+        tx.execute_batch(&format!("PRAGMA user_version = {}", migration.version))?;
+        tx.commit()?;
+
+        println!(
+            "[log_store] Applied schema migration {} ({})",
+            migration.version, migration.name
+        );
     }
 
     Ok(())
@@ -0,0 +1,234 @@
+//! NDJSON log import
+//!
+//! Loads logs captured elsewhere — this app's own exports, archived rows,
+//! or `npx convex logs` style JSON lines — and runs them through the
+//! standard ingest path, so dedup and fingerprinting behave exactly as if
+//! the entries had been streamed live.
+
+use tauri::State;
+
+use super::db::DbConnection;
+use super::models::{IngestLogEntry, IngestResult};
+
+/// Batch size for feeding parsed lines through ingest
+const IMPORT_BATCH: usize = 1000;
+
+/// Milliseconds vs seconds heuristic: timestamps under this are seconds
+const MS_THRESHOLD: f64 = 1e12;
+
+fn normalize_timestamp(raw: f64) -> i64 {
+    if raw < MS_THRESHOLD {
+        (raw * 1000.0) as i64
+    } else {
+        raw as i64
+    }
+}
+
+/// Best-effort conversion of one parsed JSON line into an ingest entry.
+/// Handles three shapes: our exported `LogEntry` rows (have `json_blob`),
+/// native `IngestLogEntry` objects, and generic Convex log records.
+fn entry_from_value(value: serde_json::Value) -> Option<IngestLogEntry> {
+    let obj = value.as_object()?;
+
+    // Our own export/archive rows: snake_case columns with json_blob
+    if obj.contains_key("json_blob") {
+        let raw = obj
+            .get("json_blob")
+            .and_then(|blob| blob.as_str())
+            .and_then(|blob| serde_json::from_str(blob).ok());
+        return Some(IngestLogEntry {
+            id: String::new(), // Ingest recomputes a stable id
+            timestamp: obj.get("ts")?.as_i64()?,
+            function_identifier: obj
+                .get("function_path")
+                .and_then(|v| v.as_str())
+                .map(String::from),
+            function_name: obj
+                .get("function_name")
+                .and_then(|v| v.as_str())
+                .map(String::from),
+            udf_type: obj.get("udf_type").and_then(|v| v.as_str()).map(String::from),
+            request_id: obj
+                .get("request_id")
+                .and_then(|v| v.as_str())
+                .map(String::from),
+            execution_id: obj
+                .get("execution_id")
+                .and_then(|v| v.as_str())
+                .map(String::from),
+            success: obj.get("success").and_then(|v| v.as_bool()),
+            duration_ms: obj.get("duration_ms").and_then(|v| v.as_i64()),
+            error: None,
+            log_lines: obj
+                .get("message")
+                .and_then(|v| v.as_str())
+                .map(|message| vec![message.to_string()]),
+            raw,
+        });
+    }
+
+    // Native ingest shape (camelCase), as the frontend and stream send it
+    if let Ok(entry) = serde_json::from_value::<IngestLogEntry>(value.clone()) {
+        return Some(entry);
+    }
+
+    // Generic Convex log record (`npx convex logs`, raw stream dumps)
+    let timestamp = obj
+        .get("timestamp")
+        .or_else(|| obj.get("ts"))
+        .and_then(|v| v.as_f64())
+        .map(normalize_timestamp)?;
+
+    let function_identifier = obj
+        .get("identifier")
+        .and_then(|v| v.as_str())
+        .map(String::from)
+        .or_else(|| {
+            obj.get("function")
+                .and_then(|f| f.get("path"))
+                .and_then(|v| v.as_str())
+                .map(String::from)
+        });
+
+    let error = obj.get("error").and_then(|v| v.as_str()).map(String::from);
+    let success = obj
+        .get("success")
+        .and_then(|v| v.as_bool())
+        .or(match &error {
+            Some(_) => Some(false),
+            None => None,
+        });
+
+    let log_lines = obj.get("logLines").and_then(|lines| lines.as_array()).map(|lines| {
+        lines
+            .iter()
+            .filter_map(|line| {
+                line.as_str().map(String::from).or_else(|| {
+                    line.get("message")
+                        .and_then(|v| v.as_str())
+                        .map(String::from)
+                })
+            })
+            .collect()
+    });
+
+    Some(IngestLogEntry {
+        id: String::new(),
+        timestamp,
+        function_identifier,
+        function_name: None,
+        udf_type: obj.get("udfType").and_then(|v| v.as_str()).map(String::from),
+        request_id: obj
+            .get("requestId")
+            .and_then(|v| v.as_str())
+            .map(String::from),
+        execution_id: obj
+            .get("executionId")
+            .and_then(|v| v.as_str())
+            .map(String::from),
+        success,
+        duration_ms: obj
+            .get("executionTime")
+            .and_then(|v| v.as_f64())
+            .map(|seconds| (seconds * 1000.0) as i64),
+        error,
+        log_lines,
+        raw: Some(value),
+    })
+}
+
+/// Import an NDJSON file into the log store under the given deployment.
+/// Unparseable lines are counted as errors rather than aborting the import.
+#[tauri::command]
+pub async fn import_logs(
+    db: State<'_, DbConnection>,
+    file_path: String,
+    deployment: String,
+) -> Result<IngestResult, String> {
+    let content = std::fs::read_to_string(&file_path)
+        .map_err(|e| format!("Failed to read file: {}", e))?;
+
+    let mut total = IngestResult {
+        inserted: 0,
+        duplicates: 0,
+        errors: 0,
+    };
+    let mut batch: Vec<IngestLogEntry> = Vec::with_capacity(IMPORT_BATCH);
+
+    let conn = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+
+    for line in content.lines() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let parsed = serde_json::from_str::<serde_json::Value>(line)
+            .ok()
+            .and_then(entry_from_value);
+        match parsed {
+            Some(entry) => batch.push(entry),
+            None => total.errors += 1,
+        }
+
+        if batch.len() >= IMPORT_BATCH {
+            let result = super::commands::ingest_batch(&conn, std::mem::take(&mut batch), &deployment);
+            total.inserted += result.inserted;
+            total.duplicates += result.duplicates;
+            total.errors += result.errors;
+        }
+    }
+
+    if !batch.is_empty() {
+        let result = super::commands::ingest_batch(&conn, batch, &deployment);
+        total.inserted += result.inserted;
+        total.duplicates += result.duplicates;
+        total.errors += result.errors;
+    }
+
+    Ok(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entry_from_exported_row() {
+        let entry = entry_from_value(serde_json::json!({
+            "id": "abc",
+            "ts": 1700000000000i64,
+            "deployment": "https://x.convex.cloud",
+            "function_path": "messages:send",
+            "success": false,
+            "duration_ms": 42,
+            "message": "Error: boom",
+            "json_blob": "{\"k\":1}",
+            "created_at": 1700000000000i64
+        }))
+        .unwrap();
+
+        assert_eq!(entry.timestamp, 1700000000000);
+        assert_eq!(entry.function_identifier.as_deref(), Some("messages:send"));
+        assert_eq!(entry.success, Some(false));
+        assert!(entry.raw.is_some());
+    }
+
+    #[test]
+    fn test_entry_from_convex_record_seconds() {
+        let entry = entry_from_value(serde_json::json!({
+            "timestamp": 1700000000.5,
+            "identifier": "messages:send",
+            "udfType": "Mutation",
+            "executionTime": 0.25,
+            "logLines": ["hello", {"message": "world"}]
+        }))
+        .unwrap();
+
+        assert_eq!(entry.timestamp, 1700000000500);
+        assert_eq!(entry.duration_ms, Some(250));
+        assert_eq!(
+            entry.log_lines,
+            Some(vec!["hello".to_string(), "world".to_string()])
+        );
+    }
+}
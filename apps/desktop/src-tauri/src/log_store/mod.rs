@@ -1,11 +1,26 @@
+mod alerts;
+mod archive;
 mod db;
+mod import;
 mod models;
 mod commands;
+mod redaction;
 mod retention;
+mod subscriptions;
 mod utils;
 
+pub use alerts::{
+    create_alert_rule, delete_alert_rule, list_alert_rules, set_alert_rule_enabled,
+};
+pub use archive::{import_log_archive, list_log_archives};
+pub use import::import_logs;
+pub use redaction::{get_redaction_patterns, set_redaction_patterns};
+pub(crate) use alerts::evaluate as evaluate_alerts;
 pub use commands::*;
-pub use db::init_db;
+pub use subscriptions::{list_log_subscriptions, subscribe_logs, unsubscribe_logs};
+pub(crate) use subscriptions::notify as notify_subscriptions;
+pub use db::{init_db, open_db_at};
+pub use models::IngestLogEntry;
 pub use retention::start_retention_scheduler;
 
 // Re-export DbConnection for use in app state management
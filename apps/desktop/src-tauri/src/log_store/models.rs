@@ -18,6 +18,9 @@ pub struct LogEntry {
     pub message: String,
     pub json_blob: String,
     pub created_at: i64,
+    /// User annotation from log_notes, joined in by the query commands
+    #[serde(default)]
+    pub note: Option<String>,
 }
 
 /// Incoming log entry from frontend (pre-processing)
@@ -44,6 +47,17 @@ pub struct IngestLogEntry {
     pub raw: Option<serde_json::Value>,
 }
 
+/// One console line of a function execution, from the log_lines child table
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogLine {
+    pub log_id: String,
+    pub line_index: i64,
+    /// Console level (LOG, DEBUG, INFO, WARN, ERROR) when the line had one
+    pub level: Option<String>,
+    pub ts: i64,
+    pub message: String,
+}
+
 /// Filter parameters for querying logs
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct LogFilters {
@@ -55,6 +69,10 @@ pub struct LogFilters {
     pub function_path: Option<String>,
     pub request_id: Option<String>,
     pub success: Option<bool>,
+    /// Match entries carrying any of these tags
+    pub tags: Option<Vec<String>>,
+    /// true: starred entries only; false: unstarred only
+    pub starred: Option<bool>,
 }
 
 /// Query result with logs and pagination cursor
@@ -84,11 +102,112 @@ pub struct LogStats {
     pub logs_by_deployment: Vec<(String, i64)>,
 }
 
+/// A named filter + FTS query combination saved for reuse
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SavedSearch {
+    pub id: i64,
+    pub name: String,
+    pub filters: LogFilters,
+    pub fts_query: Option<String>,
+    pub created_at: i64,
+}
+
+/// One error cluster from `get_error_groups`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ErrorGroup {
+    pub fingerprint: String,
+    pub count: i64,
+    pub first_seen_ts: i64,
+    pub last_seen_ts: i64,
+    /// Message of the most recent occurrence
+    pub sample_message: String,
+    pub function_path: Option<String>,
+}
+
+/// One time bucket (optionally per group) from `histogram_logs`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistogramBucket {
+    pub bucket_ts: i64,
+    /// Level or function path when grouping was requested
+    pub group: Option<String>,
+    pub count: i64,
+    pub error_count: i64,
+    pub avg_duration_ms: f64,
+}
+
+/// One duration bucket from `get_duration_histogram`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DurationBucket {
+    /// Inclusive lower edge in milliseconds
+    pub lower_ms: i64,
+    /// Exclusive upper edge; None for the open-ended last bucket
+    pub upper_ms: Option<i64>,
+    pub count: i64,
+}
+
+/// One annotation, from `get_log_notes`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogNote {
+    pub log_id: String,
+    pub note: String,
+    pub updated_at: i64,
+}
+
+/// Surrounding entries for one log, from `get_log_context`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogContext {
+    /// Entries preceding the anchor, oldest first
+    pub before: Vec<LogEntry>,
+    pub entry: LogEntry,
+    /// Entries following the anchor, oldest first
+    pub after: Vec<LogEntry>,
+}
+
+/// Per-function aggregate used by the `get_insights` rankings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FunctionInsight {
+    pub function_path: String,
+    pub calls: i64,
+    pub errors: i64,
+    pub failure_rate_pct: f64,
+    pub avg_duration_ms: f64,
+    pub max_duration_ms: i64,
+}
+
+/// Ranked function lists for the Insights tab, from `get_insights`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Insights {
+    /// By call volume, descending
+    pub most_called: Vec<FunctionInsight>,
+    /// By failure rate, descending; only functions with at least one error
+    pub most_failing: Vec<FunctionInsight>,
+    /// By average duration, descending; only functions with duration data
+    pub slowest: Vec<FunctionInsight>,
+}
+
+/// One recorded network test result, for connection-quality trends
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetworkSample {
+    pub ts: i64,
+    /// Which check produced the sample ("websocket", "http", "sse", ...)
+    pub check_name: String,
+    pub success: bool,
+    pub latency_ms: Option<i64>,
+}
+
 /// Configuration settings for log store
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LogStoreSettings {
     pub retention_days: i32,
     pub enabled: bool,
+    /// Delete oldest logs once the database exceeds this size; None means
+    /// age-based retention only
+    #[serde(default)]
+    pub max_db_size_mb: Option<i64>,
+    /// Write expiring rows to compressed NDJSON here before deleting them;
+    /// None disables cold archiving
+    #[serde(default)]
+    pub archive_dir: Option<String>,
 }
 
 impl Default for LogStoreSettings {
@@ -96,6 +215,8 @@ impl Default for LogStoreSettings {
         Self {
             retention_days: 30,
             enabled: true,
+            max_db_size_mb: None,
+            archive_dir: None,
         }
     }
 }
@@ -0,0 +1,114 @@
+//! PII redaction applied at ingest
+//!
+//! Users configure regexes (emails, bearer tokens, API keys) stored in the
+//! settings table; matches in an entry's message, json_blob, and console
+//! lines are masked before anything is written, so local retention never
+//! persists customer PII to disk unencrypted. Rows ingested while a pattern
+//! was configured stay redacted — this is deliberately not reversible.
+
+use once_cell::sync::Lazy;
+use rusqlite::params;
+use std::sync::Mutex;
+use tauri::State;
+
+use super::db::DbConnection;
+
+const SETTINGS_KEY: &str = "redaction_patterns";
+
+/// What matched spans are replaced with
+const MASK: &str = "[REDACTED]";
+
+/// Compiled patterns; None means reload from the DB
+static PATTERNS_CACHE: Lazy<Mutex<Option<Vec<regex::Regex>>>> = Lazy::new(|| Mutex::new(None));
+
+fn invalidate_cache() {
+    *PATTERNS_CACHE.lock().unwrap() = None;
+}
+
+fn load_patterns(conn: &rusqlite::Connection) -> Vec<regex::Regex> {
+    let raw: Option<String> = conn
+        .query_row(
+            "SELECT value FROM settings WHERE key = ?1",
+            params![SETTINGS_KEY],
+            |row| row.get(0),
+        )
+        .ok();
+
+    let Some(raw) = raw else {
+        return Vec::new();
+    };
+    let patterns: Vec<String> = serde_json::from_str(&raw).unwrap_or_default();
+
+    // Invalid entries are skipped rather than blocking ingest; set_redaction_patterns
+    // validates, so these only appear if the settings row was edited by hand
+    patterns
+        .iter()
+        .filter_map(|p| regex::Regex::new(p).ok())
+        .collect()
+}
+
+/// Mask every configured pattern's matches in `text`. Returns the input
+/// unchanged when no patterns are configured.
+pub(crate) fn redact(conn: &rusqlite::Connection, text: String) -> String {
+    let mut cache = PATTERNS_CACHE.lock().unwrap();
+    let patterns = cache.get_or_insert_with(|| load_patterns(conn));
+
+    if patterns.is_empty() {
+        return text;
+    }
+
+    let mut out = text;
+    for pattern in patterns.iter() {
+        if pattern.is_match(&out) {
+            out = pattern.replace_all(&out, MASK).into_owned();
+        }
+    }
+    out
+}
+
+/// The configured redaction patterns, as stored
+#[tauri::command]
+pub async fn get_redaction_patterns(
+    db: State<'_, DbConnection>,
+) -> Result<Vec<String>, String> {
+    let conn = db.read()?;
+    let raw: Option<String> = conn
+        .query_row(
+            "SELECT value FROM settings WHERE key = ?1",
+            params![SETTINGS_KEY],
+            |row| row.get(0),
+        )
+        .ok();
+
+    match raw {
+        Some(raw) => serde_json::from_str(&raw)
+            .map_err(|e| format!("Failed to parse redaction patterns: {}", e)),
+        None => Ok(Vec::new()),
+    }
+}
+
+/// Replace the redaction pattern list. Every pattern must be a valid regex;
+/// an empty list turns redaction off. Only affects entries ingested from
+/// now on — existing rows are not rewritten.
+#[tauri::command]
+pub async fn set_redaction_patterns(
+    db: State<'_, DbConnection>,
+    patterns: Vec<String>,
+) -> Result<(), String> {
+    for pattern in &patterns {
+        regex::Regex::new(pattern).map_err(|e| format!("Invalid pattern '{}': {}", pattern, e))?;
+    }
+
+    let json = serde_json::to_string(&patterns)
+        .map_err(|e| format!("Failed to serialize patterns: {}", e))?;
+
+    let conn = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+    conn.execute(
+        "INSERT OR REPLACE INTO settings (key, value) VALUES (?1, ?2)",
+        params![SETTINGS_KEY, json],
+    )
+    .map_err(|e| format!("Failed to save redaction patterns: {}", e))?;
+
+    invalidate_cache();
+    Ok(())
+}
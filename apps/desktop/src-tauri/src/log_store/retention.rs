@@ -1,64 +1,237 @@
-use std::sync::{Arc, Mutex};
+use std::sync::Arc;
 use std::time::Duration;
-use rusqlite::{params, Connection};
+use rusqlite::params;
 
-/// Run retention job immediately (synchronous version)
+use super::db::DbConnection;
+
+/// Run retention job immediately (synchronous version). Deployments with a
+/// `retention_days:<deployment>` override get their own cutoff; everything
+/// else ages out on the global one.
 pub fn run_retention_once(
-    conn: Arc<Mutex<Connection>>,
+    conn: DbConnection,
     retention_days: i32,
+    overrides: &[(String, i32)],
+    archive_dir: Option<&std::path::Path>,
 ) -> Result<i64, String> {
     let conn_guard = conn.lock().unwrap();
-    
-    let cutoff_ts = chrono::Utc::now().timestamp_millis() 
-        - (retention_days as i64 * 24 * 60 * 60 * 1000);
-    
-    let deleted = conn_guard
-        .execute("DELETE FROM logs WHERE ts < ?", params![cutoff_ts])
+
+    let now = chrono::Utc::now().timestamp_millis();
+    let cutoff_for = |days: i32| now - (days as i64 * 24 * 60 * 60 * 1000);
+    let cutoff_ts = cutoff_for(retention_days);
+
+    // Archive-before-delete: if the archive can't be written, keep the rows
+    if let Some(dir) = archive_dir {
+        let override_cutoffs: Vec<(String, i64)> = overrides
+            .iter()
+            .map(|(deployment, days)| (deployment.clone(), cutoff_for(*days)))
+            .collect();
+        super::archive::archive_expired(&conn_guard, dir, cutoff_ts, &override_cutoffs)?;
+    }
+
+    let mut deleted = 0;
+
+    // Overridden deployments first, each with its own cutoff
+    for (deployment, days) in overrides {
+        deleted += conn_guard
+            .execute(
+                "DELETE FROM logs WHERE deployment = ? AND ts < ?",
+                params![deployment, cutoff_for(*days)],
+            )
+            .map_err(|e| format!("Delete error: {}", e))?;
+    }
+
+    // Global cutoff for everything without an override
+    let mut sql = "DELETE FROM logs WHERE ts < ?".to_string();
+    let mut global_params: Vec<Box<dyn rusqlite::ToSql>> = vec![Box::new(cutoff_ts)];
+    if !overrides.is_empty() {
+        let placeholders = overrides.iter().map(|_| "?").collect::<Vec<_>>().join(",");
+        sql.push_str(&format!(" AND deployment NOT IN ({})", placeholders));
+        for (deployment, _) in overrides {
+            global_params.push(Box::new(deployment.clone()));
+        }
+    }
+    let params_refs: Vec<&dyn rusqlite::ToSql> =
+        global_params.iter().map(|b| b.as_ref()).collect();
+    deleted += conn_guard
+        .execute(&sql, params_refs.as_slice())
         .map_err(|e| format!("Delete error: {}", e))?;
-    
+
+    // Network history ages out on the same schedule as logs
+    let _ = conn_guard
+        .execute("DELETE FROM network_history WHERE ts < ?", params![cutoff_ts])
+        .map_err(|e| format!("Delete error: {}", e))?;
+
     // Checkpoint WAL to reclaim space (query_row because it returns results)
     let _ = conn_guard
         .query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))
         .map_err(|e| format!("Checkpoint error: {}", e))?;
     
     drop(conn_guard); // Release lock
-    
+
     println!("[log_store] Retention job: deleted {} old logs", deleted);
-    
+
     Ok(deleted as i64)
 }
 
+/// How many of the oldest rows to drop per pass while over the size limit
+const SIZE_TRIM_CHUNK: i64 = 5000;
+
+/// Pages actually holding data, in bytes. Freed pages stay in the file
+/// until a VACUUM, so raw file size would never shrink below the limit.
+fn used_bytes(conn: &rusqlite::Connection) -> i64 {
+    conn.query_row(
+        "SELECT (pc.page_count - fc.freelist_count) * ps.page_size
+         FROM pragma_page_count() pc, pragma_freelist_count() fc, pragma_page_size() ps",
+        [],
+        |row| row.get(0),
+    )
+    .unwrap_or(0)
+}
+
+/// Delete the oldest logs until the database is under `max_db_size_mb`,
+/// then VACUUM and checkpoint to give the space back to the filesystem.
+/// Honors the same archive-before-delete guarantee as age-based retention.
+pub fn run_size_retention_once(
+    conn: DbConnection,
+    max_db_size_mb: i64,
+    archive_dir: Option<&std::path::Path>,
+) -> Result<i64, String> {
+    let max_bytes = max_db_size_mb * 1024 * 1024;
+    let conn_guard = conn.lock().unwrap();
+
+    let mut deleted_total: i64 = 0;
+    while used_bytes(&conn_guard) > max_bytes {
+        // Upper timestamp of the oldest chunk. Deleting by ts keeps the
+        // delete in lockstep with what was archived: rows tied on that ts
+        // land in both or neither.
+        let chunk_cutoff: Option<i64> = conn_guard
+            .query_row(
+                "SELECT MAX(ts) FROM (SELECT ts FROM logs ORDER BY ts ASC LIMIT ?)",
+                params![SIZE_TRIM_CHUNK],
+                |row| row.get(0),
+            )
+            .map_err(|e| format!("Query error: {}", e))?;
+        let Some(chunk_cutoff) = chunk_cutoff else {
+            break; // Logs are gone; whatever remains isn't ours to trim
+        };
+
+        // Archive-before-delete: if the archive can't be written, keep the rows
+        if let Some(dir) = archive_dir {
+            super::archive::archive_expired(&conn_guard, dir, chunk_cutoff + 1, &[])?;
+        }
+
+        let deleted = conn_guard
+            .execute("DELETE FROM logs WHERE ts <= ?", params![chunk_cutoff])
+            .map_err(|e| format!("Delete error: {}", e))?;
+        if deleted == 0 {
+            break;
+        }
+        deleted_total += deleted as i64;
+    }
+
+    if deleted_total > 0 {
+        conn_guard
+            .execute("VACUUM", [])
+            .map_err(|e| format!("Vacuum error: {}", e))?;
+        let _ = conn_guard
+            .query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))
+            .map_err(|e| format!("Checkpoint error: {}", e))?;
+        println!(
+            "[log_store] Size retention: deleted {} logs to get under {} MB",
+            deleted_total, max_db_size_mb
+        );
+    }
+
+    Ok(deleted_total)
+}
+
 /// Start background retention scheduler using Tauri's async runtime
 /// Runs on startup and then every 24 hours
-pub fn start_retention_scheduler(conn: Arc<Mutex<Connection>>, _handle: tauri::AppHandle) {
+pub fn start_retention_scheduler(conn: DbConnection, _handle: tauri::AppHandle) {
     // Use Tauri's async runtime instead of tokio::spawn
     tauri::async_runtime::spawn(async move {
         // Run immediately on startup
-        let retention_days = get_retention_days(&conn);
-        if let Err(e) = run_retention_once(Arc::clone(&conn), retention_days) {
-            eprintln!("[log_store] Retention job failed on startup: {}", e);
-        }
-        
+        run_retention_pass(&conn);
+
         // Then run every 24 hours
         loop {
             tokio::time::sleep(Duration::from_secs(24 * 60 * 60)).await;
-            
-            let retention_days = get_retention_days(&conn);
-            
-            match run_retention_once(Arc::clone(&conn), retention_days) {
-                Ok(deleted) => {
-                    println!("[log_store] Scheduled retention: deleted {} logs", deleted);
-                }
-                Err(e) => {
-                    eprintln!("[log_store] Scheduled retention failed: {}", e);
-                }
-            }
+            run_retention_pass(&conn);
         }
     });
 }
 
+/// One scheduler pass: age-based retention, then the size cap if one is set
+fn run_retention_pass(conn: &DbConnection) {
+    let retention_days = get_retention_days(conn);
+    let overrides = get_retention_overrides(conn);
+    let archive_dir = super::archive::archive_dir(conn);
+    match run_retention_once(
+        Arc::clone(conn),
+        retention_days,
+        &overrides,
+        archive_dir.as_deref(),
+    ) {
+        Ok(deleted) => {
+            println!("[log_store] Scheduled retention: deleted {} logs", deleted);
+        }
+        Err(e) => {
+            eprintln!("[log_store] Scheduled retention failed: {}", e);
+        }
+    }
+
+    if let Some(max_db_size_mb) = get_max_db_size_mb(conn) {
+        if let Err(e) =
+            run_size_retention_once(Arc::clone(conn), max_db_size_mb, archive_dir.as_deref())
+        {
+            eprintln!("[log_store] Size retention failed: {}", e);
+        }
+    }
+}
+
+/// Per-deployment retention overrides, stored in the settings table under
+/// `retention_days:<deployment>` keys
+pub(crate) fn get_retention_overrides(conn: &DbConnection) -> Vec<(String, i32)> {
+    let conn_guard = conn.lock().unwrap();
+
+    let Ok(mut stmt) = conn_guard.prepare(
+        "SELECT key, value FROM settings WHERE key LIKE 'retention_days:%'",
+    ) else {
+        return Vec::new();
+    };
+    let Ok(rows) = stmt.query_map([], |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+    }) else {
+        return Vec::new();
+    };
+
+    rows.filter_map(|row| row.ok())
+        .filter_map(|(key, value)| {
+            let deployment = key.strip_prefix("retention_days:")?.to_string();
+            let days: i32 = value.parse().ok()?;
+            (days > 0).then_some((deployment, days))
+        })
+        .collect()
+}
+
+/// Get max_db_size_mb setting from database; 0 or missing means no limit
+fn get_max_db_size_mb(conn: &DbConnection) -> Option<i64> {
+    let conn_guard = conn.lock().unwrap();
+
+    conn_guard
+        .query_row(
+            "SELECT value FROM settings WHERE key = 'max_db_size_mb'",
+            [],
+            |row| {
+                let val: String = row.get(0)?;
+                Ok(val.parse().ok().filter(|mb| *mb > 0))
+            },
+        )
+        .unwrap_or(None)
+}
+
 /// Get retention_days setting from database (synchronous)
-fn get_retention_days(conn: &Arc<Mutex<Connection>>) -> i32 {
+fn get_retention_days(conn: &DbConnection) -> i32 {
     let conn_guard = conn.lock().unwrap();
     
     conn_guard
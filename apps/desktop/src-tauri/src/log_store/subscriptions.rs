@@ -0,0 +1,202 @@
+//! Live tail subscriptions
+//!
+//! Lets the frontend register a filter and get a `log-store:new-entries`
+//! event whenever ingest inserts matching rows, instead of re-running
+//! `query_logs` on a timer for the live log view.
+
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+
+use super::models::LogFilters;
+
+/// Active subscriptions by id
+static SUBSCRIPTIONS: Lazy<Mutex<HashMap<String, LogFilters>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+static NEXT_ID: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(1);
+
+/// The slice of an inserted row that subscriptions match against and the
+/// event carries
+#[derive(Debug, Clone, Serialize)]
+pub struct NewLogEntry {
+    pub id: String,
+    pub ts: i64,
+    pub deployment: String,
+    pub level: Option<String>,
+    pub function_path: Option<String>,
+    pub request_id: Option<String>,
+    pub success: Option<bool>,
+    pub message: String,
+}
+
+fn matches(filters: &LogFilters, entry: &NewLogEntry) -> bool {
+    if let Some(deployment) = &filters.deployment {
+        if &entry.deployment != deployment {
+            return false;
+        }
+    }
+    if let Some(start_ts) = filters.start_ts {
+        if entry.ts < start_ts {
+            return false;
+        }
+    }
+    if let Some(end_ts) = filters.end_ts {
+        if entry.ts > end_ts {
+            return false;
+        }
+    }
+    if let Some(function_path) = &filters.function_path {
+        if entry.function_path.as_ref() != Some(function_path) {
+            return false;
+        }
+    }
+    if let Some(request_id) = &filters.request_id {
+        if entry.request_id.as_ref() != Some(request_id) {
+            return false;
+        }
+    }
+    if let Some(success) = filters.success {
+        if entry.success != Some(success) {
+            return false;
+        }
+    }
+    if let Some(levels) = &filters.levels {
+        if !levels.is_empty() {
+            let Some(level) = &entry.level else {
+                return false;
+            };
+            if !levels.contains(level) {
+                return false;
+            }
+        }
+    }
+    true
+}
+
+/// Fan freshly inserted rows out to matching subscriptions. Called from the
+/// ingest paths after a batch lands.
+pub fn notify(app: &AppHandle, entries: &[NewLogEntry]) {
+    if entries.is_empty() {
+        return;
+    }
+
+    let subscriptions = SUBSCRIPTIONS.lock().unwrap();
+    if subscriptions.is_empty() {
+        return;
+    }
+    let Some(window) = app.get_webview_window("main") else {
+        return;
+    };
+
+    for (id, filters) in subscriptions.iter() {
+        let matching: Vec<&NewLogEntry> =
+            entries.iter().filter(|entry| matches(filters, entry)).collect();
+        if !matching.is_empty() {
+            let _ = window.emit(
+                "log-store:new-entries",
+                serde_json::json!({ "subscription": id, "entries": matching }),
+            );
+        }
+    }
+}
+
+/// Register a live tail subscription and get its id. Tag and starred
+/// filters are rejected: entries arrive here at ingest time, before anyone
+/// could have tagged or starred them, so such a subscription would only
+/// ever be silently empty.
+#[tauri::command]
+pub fn subscribe_logs(filters: LogFilters) -> Result<String, String> {
+    if filters.tags.as_ref().is_some_and(|tags| !tags.is_empty()) || filters.starred.is_some() {
+        return Err(
+            "Tag and starred filters aren't supported for live tail subscriptions".to_string(),
+        );
+    }
+
+    let id = format!(
+        "sub-{}",
+        NEXT_ID.fetch_add(1, std::sync::atomic::Ordering::Relaxed)
+    );
+    SUBSCRIPTIONS.lock().unwrap().insert(id.clone(), filters);
+    Ok(id)
+}
+
+/// Drop a live tail subscription
+#[tauri::command]
+pub fn unsubscribe_logs(subscription_id: String) -> bool {
+    SUBSCRIPTIONS
+        .lock()
+        .unwrap()
+        .remove(&subscription_id)
+        .is_some()
+}
+
+/// Ids of active subscriptions
+#[tauri::command]
+pub fn list_log_subscriptions() -> Vec<String> {
+    let mut ids: Vec<String> = SUBSCRIPTIONS.lock().unwrap().keys().cloned().collect();
+    ids.sort();
+    ids
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(level: &str, function_path: &str) -> NewLogEntry {
+        NewLogEntry {
+            id: "log-1".to_string(),
+            ts: 1000,
+            deployment: "https://x.convex.cloud".to_string(),
+            level: Some(level.to_string()),
+            function_path: Some(function_path.to_string()),
+            request_id: None,
+            success: Some(true),
+            message: "ok".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_matches_filters() {
+        let entry = entry("ERROR", "messages:send");
+
+        assert!(matches(&LogFilters::default(), &entry));
+        assert!(matches(
+            &LogFilters {
+                levels: Some(vec!["ERROR".to_string()]),
+                ..Default::default()
+            },
+            &entry
+        ));
+        assert!(!matches(
+            &LogFilters {
+                levels: Some(vec!["INFO".to_string()]),
+                ..Default::default()
+            },
+            &entry
+        ));
+        assert!(!matches(
+            &LogFilters {
+                function_path: Some("other:fn".to_string()),
+                ..Default::default()
+            },
+            &entry
+        ));
+    }
+
+    #[test]
+    fn test_subscribe_rejects_tag_filters() {
+        assert!(subscribe_logs(LogFilters {
+            tags: Some(vec!["interesting".to_string()]),
+            ..Default::default()
+        })
+        .is_err());
+        assert!(subscribe_logs(LogFilters {
+            starred: Some(true),
+            ..Default::default()
+        })
+        .is_err());
+    }
+}
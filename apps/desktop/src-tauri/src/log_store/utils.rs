@@ -31,19 +31,91 @@ pub fn compute_log_id(
     encode(hasher.finalize())
 }
 
-/// Extract a summary message from a log entry
+/// Collapse the parts of an error message that vary between occurrences
+/// (numbers, document/request ids) so identical failures hash the same
+fn normalize_error_message(message: &str) -> String {
+    // Only the first line matters; stack frames restate what it says
+    let first_line = message.lines().next().unwrap_or("");
+    let mut out = String::with_capacity(first_line.len());
+
+    for token in first_line.split_whitespace() {
+        // Long alphanumeric tokens are almost always ids (document ids,
+        // request ids, hashes); replace them wholesale
+        let is_id = token.len() >= 16
+            && token
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+            && token.chars().any(|c| c.is_ascii_digit());
+
+        if !out.is_empty() {
+            out.push(' ');
+        }
+        if is_id {
+            out.push('#');
+            continue;
+        }
+
+        // Collapse digit runs so "retry 3" and "retry 12" group together
+        let mut prev_digit = false;
+        for c in token.chars() {
+            if c.is_ascii_digit() {
+                if !prev_digit {
+                    out.push('#');
+                }
+                prev_digit = true;
+            } else {
+                out.push(c);
+                prev_digit = false;
+            }
+        }
+    }
+
+    out.to_lowercase()
+}
+
+/// Fingerprint for grouping identical errors, keyed on the function and the
+/// normalized message
+pub fn compute_error_fingerprint(function_path: Option<&str>, message: &str) -> String {
+    let mut hasher = Sha256::new();
+    if let Some(fp) = function_path {
+        hasher.update(fp.as_bytes());
+    }
+    hasher.update(normalize_error_message(message).as_bytes());
+    encode(&hasher.finalize()[..8])
+}
+
+/// Split a console line's `[LEVEL]` prefix from its text, when present
+pub fn split_line_level(line: &str) -> (Option<&str>, &str) {
+    let trimmed = line.trim_start();
+    if let Some(rest) = trimmed.strip_prefix('[') {
+        if let Some(end) = rest.find(']') {
+            let level = &rest[..end];
+            if matches!(level, "LOG" | "DEBUG" | "INFO" | "WARN" | "ERROR") {
+                return (Some(level), rest[end + 1..].trim_start());
+            }
+        }
+    }
+    (None, trimmed)
+}
+
+/// Extract a summary message from a log entry. Individual console lines
+/// live in the log_lines child table; the summary is just the first one.
 pub fn extract_message(entry: &IngestLogEntry) -> String {
     // Priority: error > log lines > function name
     if let Some(ref error) = entry.error {
         return format!("Error: {}", error);
     }
-    
+
     if let Some(ref lines) = entry.log_lines {
-        if !lines.is_empty() {
-            return lines.join(" | ");
+        if let Some(first) = lines.first() {
+            let (_, text) = split_line_level(first);
+            if lines.len() > 1 {
+                return format!("{} (+{} more lines)", text, lines.len() - 1);
+            }
+            return text.to_string();
         }
     }
-    
+
     if let Some(ref name) = entry.function_name {
         if entry.success.unwrap_or(true) {
             return format!("Function '{}' executed", name);
@@ -127,4 +199,31 @@ mod tests {
         
         assert_ne!(id1, id2, "IDs should differ for different messages");
     }
+
+    #[test]
+    fn test_split_line_level() {
+        assert_eq!(split_line_level("[LOG] hello"), (Some("LOG"), "hello"));
+        assert_eq!(split_line_level("[ERROR] boom"), (Some("ERROR"), "boom"));
+        assert_eq!(split_line_level("plain text"), (None, "plain text"));
+        assert_eq!(split_line_level("[weird] tag"), (None, "[weird] tag"));
+    }
+
+    #[test]
+    fn test_error_fingerprint_groups_variants() {
+        let fp1 = compute_error_fingerprint(
+            Some("messages:send"),
+            "Error: Document jd7abc123def456ghi not found (attempt 3)",
+        );
+        let fp2 = compute_error_fingerprint(
+            Some("messages:send"),
+            "Error: Document jd7zzz999xyz888qqq not found (attempt 12)",
+        );
+        assert_eq!(fp1, fp2, "Only ids and numbers differ");
+
+        let fp3 = compute_error_fingerprint(Some("messages:send"), "Error: Unauthorized");
+        assert_ne!(fp1, fp3);
+
+        let fp4 = compute_error_fingerprint(Some("other:fn"), "Error: Unauthorized");
+        assert_ne!(fp3, fp4, "Same message in a different function is a different group");
+    }
 }
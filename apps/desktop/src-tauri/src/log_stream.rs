@@ -0,0 +1,228 @@
+//! Native WebSocket log streaming
+//!
+//! Connects to a deployment's log stream directly from Rust and feeds
+//! entries into the log store's ingest pipeline, so logs keep flowing even
+//! when the logs tab isn't open. Each deployment gets one stream task with
+//! reconnect/backoff; stopping a stream bumps its generation so the old
+//! task winds down on its own.
+
+use futures_util::StreamExt;
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager};
+use tokio_tungstenite::connect_async;
+use tokio_tungstenite::tungstenite::client::IntoClientRequest;
+use tokio_tungstenite::tungstenite::Message;
+
+use crate::log_store::{self, IngestLogEntry};
+
+/// Active stream generations per deployment URL. A task only keeps running
+/// while its generation matches the map entry.
+static STREAMS: Lazy<Mutex<HashMap<String, u64>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+const INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+fn stream_active(deployment_url: &str, generation: u64) -> bool {
+    STREAMS
+        .lock()
+        .unwrap()
+        .get(deployment_url)
+        .map(|g| *g == generation)
+        .unwrap_or(false)
+}
+
+/// WebSocket endpoint for a deployment's log stream
+fn stream_url(deployment_url: &str) -> String {
+    let base = deployment_url.trim_end_matches('/');
+    let ws_base = if let Some(rest) = base.strip_prefix("https://") {
+        format!("wss://{}", rest)
+    } else if let Some(rest) = base.strip_prefix("http://") {
+        format!("ws://{}", rest)
+    } else {
+        format!("wss://{}", base)
+    };
+    format!("{}/api/logs/ws", ws_base)
+}
+
+/// Entries from one stream message. The stream sends either a bare array or
+/// an object with an `entries` field.
+fn parse_stream_message(text: &str) -> Vec<IngestLogEntry> {
+    let value: serde_json::Value = match serde_json::from_str(text) {
+        Ok(v) => v,
+        Err(_) => return Vec::new(),
+    };
+
+    let entries = match &value {
+        serde_json::Value::Array(_) => value.clone(),
+        serde_json::Value::Object(obj) => match obj.get("entries") {
+            Some(entries) => entries.clone(),
+            None => return Vec::new(),
+        },
+        _ => return Vec::new(),
+    };
+
+    serde_json::from_value(entries).unwrap_or_default()
+}
+
+fn emit_status(app: &AppHandle, deployment_url: &str, connected: bool) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.emit(
+            "log-stream-status",
+            serde_json::json!({ "deployment": deployment_url, "connected": connected }),
+        );
+    }
+}
+
+fn ingest_entries(app: &AppHandle, deployment_url: &str, entries: Vec<IngestLogEntry>) {
+    if entries.is_empty() {
+        return;
+    }
+
+    // Log-sink plugins see every streamed batch
+    crate::plugins::forward_event(
+        "log-sink",
+        serde_json::json!({
+            "type": "logs",
+            "deployment": deployment_url,
+            "entries": entries,
+        }),
+    );
+
+    let db = app.state::<log_store::DbConnection>();
+    let conn = match db.lock() {
+        Ok(conn) => conn,
+        Err(e) => {
+            eprintln!("[log_stream] Lock error: {}", e);
+            return;
+        }
+    };
+
+    let (result, new_entries) = log_store::ingest_batch_tracked(&conn, entries, deployment_url);
+    drop(conn);
+
+    log_store::notify_subscriptions(app, &new_entries);
+    log_store::evaluate_alerts(app, &db, &new_entries);
+
+    if result.inserted > 0 {
+        if let Some(window) = app.get_webview_window("main") {
+            let _ = window.emit(
+                "log-stream-ingested",
+                serde_json::json!({
+                    "deployment": deployment_url,
+                    "inserted": result.inserted,
+                }),
+            );
+        }
+    }
+}
+
+async fn run_stream(app: AppHandle, deployment_url: String, admin_key: String, generation: u64) {
+    let mut backoff = INITIAL_BACKOFF;
+    crate::background_jobs::set_running("log-stream", true);
+
+    while stream_active(&deployment_url, generation) {
+        // Paused via the background job supervisor: stay off the wire but
+        // keep the task alive so resuming reconnects
+        if !crate::background_jobs::should_run("log-stream") {
+            tokio::time::sleep(INITIAL_BACKOFF).await;
+            continue;
+        }
+
+        let mut request = match stream_url(&deployment_url).into_client_request() {
+            Ok(request) => request,
+            Err(e) => {
+                eprintln!("[log_stream] Invalid stream URL: {}", e);
+                break;
+            }
+        };
+        if let Ok(value) = format!("Convex {}", admin_key).parse() {
+            request.headers_mut().insert("Authorization", value);
+        }
+
+        match connect_async(request).await {
+            Ok((mut socket, _)) => {
+                emit_status(&app, &deployment_url, true);
+                crate::background_jobs::report("log-stream", &Ok(()));
+
+                while let Some(message) = socket.next().await {
+                    if !stream_active(&deployment_url, generation) {
+                        let _ = socket.close(None).await;
+                        break;
+                    }
+
+                    match message {
+                        Ok(Message::Text(text)) => {
+                            // A delivered message means the connection is
+                            // healthy; reset the backoff
+                            backoff = INITIAL_BACKOFF;
+                            ingest_entries(&app, &deployment_url, parse_stream_message(&text));
+                        }
+                        Ok(Message::Ping(_)) | Ok(Message::Pong(_)) | Ok(Message::Binary(_)) => {}
+                        Ok(Message::Close(_)) | Err(_) => break,
+                        _ => {}
+                    }
+                }
+
+                emit_status(&app, &deployment_url, false);
+            }
+            Err(e) => {
+                eprintln!("[log_stream] Connect failed for {}: {}", deployment_url, e);
+                crate::background_jobs::report(
+                    "log-stream",
+                    &Err(format!("Connect failed for {}: {}", deployment_url, e)),
+                );
+            }
+        }
+
+        if !stream_active(&deployment_url, generation) {
+            break;
+        }
+
+        tokio::time::sleep(backoff).await;
+        backoff = (backoff * 2).min(MAX_BACKOFF);
+    }
+
+    if STREAMS.lock().unwrap().is_empty() {
+        crate::background_jobs::set_running("log-stream", false);
+    }
+}
+
+/// Start streaming a deployment's logs into the log store. Replaces any
+/// existing stream for the same deployment.
+#[tauri::command]
+pub fn start_log_stream(
+    app: AppHandle,
+    deployment_url: String,
+    admin_key: Option<String>,
+) -> Result<(), String> {
+    let admin_key = crate::convex_client::resolve_admin_key(&deployment_url, admin_key)?;
+    let deployment_url = deployment_url.trim_end_matches('/').to_string();
+
+    let generation = {
+        let mut streams = STREAMS.lock().unwrap();
+        let generation = streams.get(&deployment_url).map(|g| g + 1).unwrap_or(1);
+        streams.insert(deployment_url.clone(), generation);
+        generation
+    };
+
+    tauri::async_runtime::spawn(run_stream(app, deployment_url, admin_key, generation));
+    Ok(())
+}
+
+/// Stop streaming a deployment's logs
+#[tauri::command]
+pub fn stop_log_stream(deployment_url: String) -> Result<bool, String> {
+    let deployment_url = deployment_url.trim_end_matches('/').to_string();
+    Ok(STREAMS.lock().unwrap().remove(&deployment_url).is_some())
+}
+
+/// Deployment URLs with an active log stream
+#[tauri::command]
+pub fn list_log_streams() -> Vec<String> {
+    let mut urls: Vec<String> = STREAMS.lock().unwrap().keys().cloned().collect();
+    urls.sort();
+    urls
+}
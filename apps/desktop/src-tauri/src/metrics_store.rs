@@ -0,0 +1,248 @@
+//! Function metrics time series
+//!
+//! Per-minute rollups of invocation counts, durations, and error rates per
+//! function, derived from the ingested logs. Raw logs are too heavy for
+//! long-range dashboards; the `function_metrics` table stays small enough
+//! to chart weeks of history.
+
+use rusqlite::{params, Connection};
+use serde::Serialize;
+use std::time::Duration;
+use tauri::State;
+
+use crate::log_store::DbConnection;
+
+const MINUTE_MS: i64 = 60 * 1000;
+
+/// How often the background rollup runs
+const ROLLUP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// One charted point for a function (or all functions combined)
+#[derive(Debug, Clone, Serialize)]
+pub struct MetricPoint {
+    /// Bucket start (ms since epoch)
+    pub ts: i64,
+    pub invocations: i64,
+    pub errors: i64,
+    pub error_rate: f64,
+    pub avg_duration_ms: f64,
+    pub max_duration_ms: i64,
+}
+
+/// Aggregate totals for one function over a range
+#[derive(Debug, Clone, Serialize)]
+pub struct FunctionMetricSummary {
+    pub function_path: String,
+    pub invocations: i64,
+    pub errors: i64,
+    pub error_rate: f64,
+    pub avg_duration_ms: f64,
+    pub max_duration_ms: i64,
+}
+
+/// Roll logs since the last run into per-minute buckets. Buckets are
+/// re-aggregated from the logs table so re-running over the same window is
+/// idempotent.
+pub fn rollup_once(conn: &Connection) -> Result<i64, String> {
+    let now = chrono::Utc::now().timestamp_millis();
+
+    // Resume from the last rollup, re-covering the final (possibly partial)
+    // minute; first run backfills the last 24 hours. The resume point must
+    // sit on a bucket boundary: REPLACE rewrites every bucket the window
+    // touches, so starting mid-minute would rebuild that bucket from only
+    // the tail of its rows.
+    let last: Option<i64> = conn
+        .query_row(
+            "SELECT value FROM settings WHERE key = 'metrics_last_rollup_ts'",
+            [],
+            |row| {
+                let val: String = row.get(0)?;
+                Ok(val.parse().ok())
+            },
+        )
+        .unwrap_or(None);
+    let since = last
+        .map(|ts| (ts / MINUTE_MS - 1) * MINUTE_MS)
+        .unwrap_or_else(|| ((now - 24 * 60 * 60 * 1000) / MINUTE_MS) * MINUTE_MS);
+
+    let updated = conn
+        .execute(
+            "INSERT OR REPLACE INTO function_metrics
+                (deployment, function_path, minute_ts, invocations, errors,
+                 total_duration_ms, max_duration_ms)
+             SELECT
+                deployment,
+                function_path,
+                (ts / 60000) * 60000 AS minute_ts,
+                COUNT(*),
+                SUM(CASE WHEN success = 0 OR level = 'ERROR' THEN 1 ELSE 0 END),
+                COALESCE(SUM(duration_ms), 0),
+                COALESCE(MAX(duration_ms), 0)
+             FROM logs
+             WHERE ts >= ?1 AND function_path IS NOT NULL
+             GROUP BY deployment, function_path, minute_ts",
+            params![since],
+        )
+        .map_err(|e| format!("Rollup error: {}", e))?;
+
+    conn.execute(
+        "INSERT OR REPLACE INTO settings (key, value) VALUES ('metrics_last_rollup_ts', ?1)",
+        params![now.to_string()],
+    )
+    .map_err(|e| format!("Settings error: {}", e))?;
+
+    Ok(updated as i64)
+}
+
+/// Start the background rollup loop on Tauri's async runtime
+pub fn start_metrics_scheduler(conn: DbConnection) {
+    tauri::async_runtime::spawn(async move {
+        crate::background_jobs::set_running("metrics-rollup", true);
+        loop {
+            tokio::time::sleep(ROLLUP_INTERVAL).await;
+
+            if !crate::background_jobs::should_run("metrics-rollup") {
+                continue;
+            }
+
+            let result = match conn.lock() {
+                Ok(conn) => rollup_once(&conn).map(|_| ()),
+                Err(e) => Err(format!("Lock error: {}", e)),
+            };
+            if let Err(e) = &result {
+                eprintln!("[metrics_store] Rollup failed: {}", e);
+            }
+            crate::background_jobs::report("metrics-rollup", &result);
+        }
+    });
+}
+
+/// Force a rollup now (e.g. right before opening a dashboard)
+#[tauri::command]
+pub async fn rollup_function_metrics(db: State<'_, DbConnection>) -> Result<i64, String> {
+    let conn = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+    rollup_once(&conn)
+}
+
+/// Time series for charting. With `function_path` unset all functions are
+/// combined; `bucket_minutes` coarsens the per-minute data for long ranges.
+#[tauri::command]
+pub async fn get_function_metrics(
+    db: State<'_, DbConnection>,
+    deployment: String,
+    function_path: Option<String>,
+    start_ts: i64,
+    end_ts: i64,
+    bucket_minutes: Option<i64>,
+) -> Result<Vec<MetricPoint>, String> {
+    let conn = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+
+    let bucket_ms = bucket_minutes.unwrap_or(1).max(1) * MINUTE_MS;
+
+    let mut sql = String::from(
+        "SELECT (minute_ts / ?1) * ?1 AS bucket,
+                SUM(invocations), SUM(errors),
+                SUM(total_duration_ms), MAX(max_duration_ms)
+         FROM function_metrics
+         WHERE deployment = ?2 AND minute_ts >= ?3 AND minute_ts <= ?4",
+    );
+    if function_path.is_some() {
+        sql.push_str(" AND function_path = ?5");
+    }
+    sql.push_str(" GROUP BY bucket ORDER BY bucket ASC");
+
+    let mut stmt = conn
+        .prepare(&sql)
+        .map_err(|e| format!("Query error: {}", e))?;
+
+    let map_row = |row: &rusqlite::Row| {
+        let ts: i64 = row.get(0)?;
+        let invocations: i64 = row.get(1)?;
+        let errors: i64 = row.get(2)?;
+        let total_duration: i64 = row.get(3)?;
+        let max_duration: i64 = row.get(4)?;
+        Ok(MetricPoint {
+            ts,
+            invocations,
+            errors,
+            error_rate: if invocations > 0 {
+                errors as f64 / invocations as f64
+            } else {
+                0.0
+            },
+            avg_duration_ms: if invocations > 0 {
+                total_duration as f64 / invocations as f64
+            } else {
+                0.0
+            },
+            max_duration_ms: max_duration,
+        })
+    };
+
+    let rows = match &function_path {
+        Some(path) => stmt
+            .query_map(params![bucket_ms, deployment, start_ts, end_ts, path], map_row),
+        None => stmt.query_map(params![bucket_ms, deployment, start_ts, end_ts], map_row),
+    }
+    .map_err(|e| format!("Query error: {}", e))?;
+
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Query error: {}", e))
+}
+
+/// Per-function totals over a range, ordered by invocation count
+#[tauri::command]
+pub async fn get_function_metric_summaries(
+    db: State<'_, DbConnection>,
+    deployment: String,
+    start_ts: i64,
+    end_ts: i64,
+    limit: Option<i64>,
+) -> Result<Vec<FunctionMetricSummary>, String> {
+    let conn = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT function_path,
+                    SUM(invocations), SUM(errors),
+                    SUM(total_duration_ms), MAX(max_duration_ms)
+             FROM function_metrics
+             WHERE deployment = ?1 AND minute_ts >= ?2 AND minute_ts <= ?3
+             GROUP BY function_path
+             ORDER BY SUM(invocations) DESC
+             LIMIT ?4",
+        )
+        .map_err(|e| format!("Query error: {}", e))?;
+
+    let rows = stmt
+        .query_map(
+            params![deployment, start_ts, end_ts, limit.unwrap_or(50).min(500)],
+            |row| {
+                let function_path: String = row.get(0)?;
+                let invocations: i64 = row.get(1)?;
+                let errors: i64 = row.get(2)?;
+                let total_duration: i64 = row.get(3)?;
+                let max_duration: i64 = row.get(4)?;
+                Ok(FunctionMetricSummary {
+                    function_path,
+                    invocations,
+                    errors,
+                    error_rate: if invocations > 0 {
+                        errors as f64 / invocations as f64
+                    } else {
+                        0.0
+                    },
+                    avg_duration_ms: if invocations > 0 {
+                        total_duration as f64 / invocations as f64
+                    } else {
+                        0.0
+                    },
+                    max_duration_ms: max_duration,
+                })
+            },
+        )
+        .map_err(|e| format!("Query error: {}", e))?;
+
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Query error: {}", e))
+}
@@ -9,9 +9,23 @@
 //! - Windows: Full support with Tauri notification API and ms-settings deep link
 //! - Linux: TODO - Not yet implemented, contributions welcome!
 
+use std::sync::atomic::{AtomicBool, Ordering};
 use tauri::AppHandle;
 use tauri_plugin_notification::NotificationExt;
 
+/// While set, native notifications are suppressed (presentation mode)
+static MUTED: AtomicBool = AtomicBool::new(false);
+
+/// Mute or unmute all native notifications
+pub fn set_muted(muted: bool) {
+    MUTED.store(muted, Ordering::Relaxed);
+}
+
+/// Whether notifications are currently muted
+pub fn muted() -> bool {
+    MUTED.load(Ordering::Relaxed)
+}
+
 /// Send a test notification (for settings page).
 ///
 /// On macOS, uses a fallback chain:
@@ -0,0 +1,502 @@
+//! Local OAuth callback server
+//!
+//! Binds a loopback HTTP listener that identity providers can redirect back to
+//! during login. The bound port is returned to the frontend so it can build the
+//! redirect URI from whichever port was actually available.
+
+use once_cell::sync::Lazy;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use tauri::{AppHandle, Emitter};
+
+/// Port of the currently running callback server, if any.
+/// Lets `start_oauth_server` be called again (logout/login, account switch)
+/// without spawning a second listener.
+static RUNNING_PORT: Lazy<Mutex<Option<u16>>> = Lazy::new(|| Mutex::new(None));
+
+/// Default start of the port range tried for the callback listener
+const DEFAULT_PORT_RANGE_START: u16 = 14200;
+/// How many consecutive ports to try before giving up
+const DEFAULT_PORT_RANGE_LEN: u16 = 10;
+
+const SUCCESS_HTML: &str = "<html><body style=\"font-family: sans-serif; text-align: center; padding-top: 4rem;\">\
+<h2>Login successful</h2><p>You can close this window and return to Convex Panel.</p>\
+<script>setTimeout(function () { window.close(); }, 1500);</script>\
+</body></html>";
+
+const ERROR_HTML: &str = "<html><body style=\"font-family: sans-serif; text-align: center; padding-top: 4rem;\">\
+<h2>Login failed</h2><p>Something went wrong. Return to Convex Panel and try again.</p>\
+</body></html>";
+
+/// Customization for the pages served by the callback server
+#[derive(Clone, Default, serde::Deserialize)]
+pub struct OauthServerOptions {
+    /// Custom HTML served after a successful callback
+    pub success_html: Option<String>,
+    /// Custom HTML served when the callback is missing/invalid
+    pub error_html: Option<String>,
+    /// Deep link (or URL) to redirect the browser to after success,
+    /// e.g. `convex-panel://login-complete`
+    pub success_redirect: Option<String>,
+}
+
+static OPTIONS: Lazy<Mutex<OauthServerOptions>> =
+    Lazy::new(|| Mutex::new(OauthServerOptions::default()));
+
+/// Payload of the `oauth-code` event. `provider` lets the frontend route the
+/// code to the right exchange flow when multiple identities are linked.
+#[derive(Clone, serde::Serialize)]
+pub struct OauthCallback {
+    pub provider: String,
+    pub code: String,
+    pub state: Option<String>,
+}
+
+/// Payload of the `oauth-error` event, emitted when a callback carries an
+/// error or cannot be parsed, so the UI can show an actionable message
+/// instead of the user staring at a stuck login screen.
+#[derive(Clone, serde::Serialize)]
+pub struct OauthError {
+    pub error: String,
+    pub error_description: Option<String>,
+    pub provider: Option<String>,
+}
+
+/// Extract a provider error (`?error=access_denied&error_description=…`)
+/// from a callback URL, if present
+fn parse_callback_error(url: &url::Url) -> Option<OauthError> {
+    let mut error = None;
+    let mut description = None;
+    let mut provider = None;
+
+    for (key, value) in url.query_pairs() {
+        match key.as_ref() {
+            "error" => error = Some(value.into_owned()),
+            "error_description" => description = Some(value.into_owned()),
+            "provider" => provider = Some(value.into_owned()),
+            _ => {}
+        }
+    }
+
+    let provider = provider.or_else(|| {
+        url.path()
+            .trim_start_matches('/')
+            .split('/')
+            .nth(1)
+            .filter(|s| !s.is_empty())
+            .map(|s| s.to_string())
+    });
+
+    error.map(|error| OauthError {
+        error,
+        error_description: description,
+        provider,
+    })
+}
+
+/// Parse an OAuth callback URL into its provider-specific shape.
+///
+/// Accepted shapes:
+/// - `/callback?code=…` (default Convex OAuth)
+/// - `/callback/github?code=…&state=…`
+/// - `/callback/google?code=…&state=…`
+/// - `/callback?provider=…&code=…`
+/// - `/callback?token=…` (Convex dashboard access tokens)
+fn parse_callback(url: &url::Url) -> Option<OauthCallback> {
+    let mut segments = url.path().trim_start_matches('/').split('/');
+    if segments.next() != Some("callback") {
+        return None;
+    }
+    let path_provider = segments.next().filter(|s| !s.is_empty());
+
+    let mut code = None;
+    let mut token = None;
+    let mut state = None;
+    let mut query_provider = None;
+
+    for (key, value) in url.query_pairs() {
+        match key.as_ref() {
+            "code" => code = Some(value.into_owned()),
+            "token" | "accessToken" => token = Some(value.into_owned()),
+            "state" => state = Some(value.into_owned()),
+            "provider" => query_provider = Some(value.into_owned()),
+            _ => {}
+        }
+    }
+
+    let provider = path_provider
+        .map(|s| s.to_string())
+        .or(query_provider)
+        // Bare tokens only come from the Convex dashboard flow
+        .unwrap_or_else(|| {
+            if code.is_none() && token.is_some() {
+                "convex-dashboard".to_string()
+            } else {
+                "convex".to_string()
+            }
+        });
+
+    code.or(token).map(|code| OauthCallback {
+        provider,
+        code,
+        state,
+    })
+}
+
+/// Bind the first available port in [start, start + len)
+fn bind_first_available(start: u16, len: u16) -> Result<(TcpListener, u16), String> {
+    let mut last_error = None;
+
+    for offset in 0..len {
+        let port = match start.checked_add(offset) {
+            Some(p) => p,
+            None => break,
+        };
+
+        match TcpListener::bind(("127.0.0.1", port)) {
+            Ok(listener) => return Ok((listener, port)),
+            Err(e) => {
+                last_error = Some(format!("port {}: {}", port, e));
+            }
+        }
+    }
+
+    Err(format!(
+        "No available port in range {}..{}: {}",
+        start,
+        start as u32 + len as u32,
+        last_error.unwrap_or_else(|| "no ports tried".to_string())
+    ))
+}
+
+/// Handle a `convex-panel://oauth/callback?code=…` deep link used as an
+/// alternative OAuth redirect target when a local HTTP listener is blocked.
+/// Returns true if the URL was an OAuth callback.
+pub fn handle_deep_link(app_handle: &AppHandle, url: &url::Url) -> bool {
+    if url.scheme() != "convex-panel" {
+        return false;
+    }
+
+    if url.host_str() != Some("oauth") || !url.path().starts_with("/callback") {
+        return false;
+    }
+
+    // Rewrite `convex-panel://oauth/callback/...` into a path the shared
+    // parser understands (`/callback/...`)
+    let rewritten = format!(
+        "http://localhost{}{}",
+        url.path(),
+        url.query().map(|q| format!("?{}", q)).unwrap_or_default()
+    );
+
+    match url::Url::parse(&rewritten).ok().and_then(|u| parse_callback(&u)) {
+        Some(callback) => {
+            let _ = app_handle.emit("oauth-code", callback);
+            true
+        }
+        None => {
+            eprintln!("[oauth_server] Deep link callback missing code: {}", url);
+            let error = url::Url::parse(&rewritten)
+                .ok()
+                .and_then(|u| parse_callback_error(&u))
+                .unwrap_or_else(|| OauthError {
+                    error: "invalid_callback".to_string(),
+                    error_description: Some("Deep link callback did not include a code".to_string()),
+                    provider: None,
+                });
+            let _ = app_handle.emit("oauth-error", error);
+            // The URL was an OAuth callback even though it failed; report it
+            // handled so callers don't treat it as something else
+            true
+        }
+    }
+}
+
+/// Parse the callback from a request line like
+/// `GET /callback?code=abc&state=xyz HTTP/1.1`
+fn parse_callback_request(request_line: &str) -> Option<OauthCallback> {
+    let path = request_line.split_whitespace().nth(1)?;
+    let url = url::Url::parse(&format!("http://localhost{}", path)).ok()?;
+    parse_callback(&url)
+}
+
+fn respond(stream: &mut impl Write, body: &str) {
+    let response = format!(
+        "HTTP/1.1 200 OK\r\nContent-Type: text/html; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        body.len(),
+        body
+    );
+    let _ = stream.write_all(response.as_bytes());
+    let _ = stream.flush();
+}
+
+fn respond_redirect(stream: &mut impl Write, location: &str) {
+    let response = format!(
+        "HTTP/1.1 302 Found\r\nLocation: {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        location
+    );
+    let _ = stream.write_all(response.as_bytes());
+    let _ = stream.flush();
+}
+
+fn respond_empty(stream: &mut impl Write) {
+    let _ = stream.write_all(b"HTTP/1.1 204 No Content\r\nConnection: close\r\n\r\n");
+    let _ = stream.flush();
+}
+
+/// True for browser noise like `/favicon.ico` that should get a quick
+/// response rather than hanging the connection or serving the error page
+fn is_stray_request(request_line: &str) -> bool {
+    match request_line.split_whitespace().nth(1) {
+        Some(path) => {
+            let path = path.split('?').next().unwrap_or(path);
+            matches!(path, "/favicon.ico" | "/robots.txt" | "/apple-touch-icon.png")
+        }
+        None => true,
+    }
+}
+
+fn handle_connection<S: Read + Write>(app_handle: &AppHandle, mut stream: S) {
+    let mut request_line = String::new();
+    {
+        let mut reader = BufReader::new(&mut stream);
+        if reader.read_line(&mut request_line).is_err() {
+            return;
+        }
+    }
+
+    if is_stray_request(&request_line) {
+        respond_empty(&mut stream);
+        return;
+    }
+
+    let options = OPTIONS.lock().unwrap().clone();
+
+    match parse_callback_request(&request_line) {
+        Some(callback) => {
+            let _ = app_handle.emit("oauth-code", callback);
+            if let Some(ref redirect) = options.success_redirect {
+                respond_redirect(&mut stream, redirect);
+            } else {
+                respond(
+                    &mut stream,
+                    options.success_html.as_deref().unwrap_or(SUCCESS_HTML),
+                );
+            }
+        }
+        None => {
+            eprintln!("[oauth_server] Callback request missing code: {}", request_line.trim());
+            emit_callback_error(app_handle, &request_line);
+            respond(
+                &mut stream,
+                options.error_html.as_deref().unwrap_or(ERROR_HTML),
+            );
+        }
+    }
+}
+
+/// Emit a typed `oauth-error` event for a failed callback so the UI can show
+/// the provider's message and offer a retry, rather than the failure only
+/// appearing on stderr and in the served HTML page
+fn emit_callback_error(app_handle: &AppHandle, request_line: &str) {
+    let parsed_url = request_line
+        .split_whitespace()
+        .nth(1)
+        .and_then(|path| url::Url::parse(&format!("http://localhost{}", path)).ok());
+
+    let error = match parsed_url {
+        Some(ref url) => parse_callback_error(url).unwrap_or_else(|| OauthError {
+            error: "invalid_callback".to_string(),
+            error_description: Some("Callback request did not include a code".to_string()),
+            provider: None,
+        }),
+        None => OauthError {
+            error: "invalid_request".to_string(),
+            error_description: Some(format!(
+                "Could not parse callback request: {}",
+                request_line.trim()
+            )),
+            provider: None,
+        },
+    };
+
+    let _ = app_handle.emit("oauth-error", error);
+}
+
+/// Where the self-signed callback certificate and key are persisted so the
+/// user only has to trust it once
+fn callback_cert_paths() -> Result<(PathBuf, PathBuf), String> {
+    let home = std::env::var("HOME")
+        .or_else(|_| std::env::var("USERPROFILE"))
+        .map_err(|_| "Failed to get home directory")?;
+
+    let app_data = PathBuf::from(home).join(".convex-panel");
+    std::fs::create_dir_all(&app_data)
+        .map_err(|e| format!("Failed to create app data directory: {}", e))?;
+
+    Ok((
+        app_data.join("oauth-callback-cert.pem"),
+        app_data.join("oauth-callback-key.pem"),
+    ))
+}
+
+/// Load the persisted self-signed certificate, generating one on first run
+fn load_or_generate_tls_config() -> Result<Arc<rustls::ServerConfig>, String> {
+    let (cert_path, key_path) = callback_cert_paths()?;
+
+    if !cert_path.exists() || !key_path.exists() {
+        let certified = rcgen::generate_simple_self_signed(vec![
+            "localhost".to_string(),
+            "127.0.0.1".to_string(),
+        ])
+        .map_err(|e| format!("Failed to generate self-signed certificate: {}", e))?;
+
+        std::fs::write(&cert_path, certified.cert.pem())
+            .map_err(|e| format!("Failed to write certificate: {}", e))?;
+        std::fs::write(&key_path, certified.key_pair.serialize_pem())
+            .map_err(|e| format!("Failed to write private key: {}", e))?;
+
+        println!(
+            "[oauth_server] Generated self-signed callback certificate at {}. \
+             Trust it in your OS keychain to avoid browser warnings.",
+            cert_path.display()
+        );
+    }
+
+    let cert_pem = std::fs::read(&cert_path)
+        .map_err(|e| format!("Failed to read certificate: {}", e))?;
+    let key_pem = std::fs::read(&key_path)
+        .map_err(|e| format!("Failed to read private key: {}", e))?;
+
+    let certs = rustls_pemfile::certs(&mut cert_pem.as_slice())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Failed to parse certificate: {}", e))?;
+    let key = rustls_pemfile::private_key(&mut key_pem.as_slice())
+        .map_err(|e| format!("Failed to parse private key: {}", e))?
+        .ok_or_else(|| "No private key found in key file".to_string())?;
+
+    let config = rustls::ServerConfig::builder()
+        .with_no_client_auth()
+        .with_single_cert(certs, key)
+        .map_err(|e| format!("Failed to build TLS config: {}", e))?;
+
+    Ok(Arc::new(config))
+}
+
+/// Start the local OAuth callback server.
+///
+/// Tries each port in the configured range (defaulting to 14200..14210) and
+/// returns the port that was actually bound, so a stale process squatting on
+/// 14200 no longer breaks login. The frontend builds the redirect URI from the
+/// returned port.
+///
+/// Idempotent: if a server is already running its port is returned as-is, and
+/// the accept loop stays alive across logins so logging out and back in (or
+/// switching accounts) works without restarting the app.
+#[tauri::command]
+pub fn start_oauth_server(
+    app_handle: AppHandle,
+    port_range_start: Option<u16>,
+    port_range_len: Option<u16>,
+    options: Option<OauthServerOptions>,
+    use_https: Option<bool>,
+) -> Result<u16, String> {
+    // Options can be updated on every call, even when the listener is reused
+    if let Some(options) = options {
+        *OPTIONS.lock().unwrap() = options;
+    }
+
+    let mut running = RUNNING_PORT.lock().unwrap();
+
+    // Already listening - reuse the existing server
+    if let Some(port) = *running {
+        return Ok(port);
+    }
+
+    let start = port_range_start.unwrap_or(DEFAULT_PORT_RANGE_START);
+    let len = port_range_len.unwrap_or(DEFAULT_PORT_RANGE_LEN).max(1);
+
+    // Some identity providers refuse plain-HTTP redirect URIs even on
+    // loopback; serve the callback over self-signed TLS for those
+    let tls_config = if use_https.unwrap_or(false) {
+        Some(load_or_generate_tls_config()?)
+    } else {
+        None
+    };
+
+    let (listener, port) = bind_first_available(start, len)?;
+    *running = Some(port);
+
+    println!(
+        "[oauth_server] Listening for OAuth callback on {}://127.0.0.1:{}",
+        if tls_config.is_some() { "https" } else { "http" },
+        port
+    );
+
+    thread::spawn(move || {
+        // Serve callbacks until the app exits so repeated logins reuse the
+        // same listener instead of racing to re-bind the port
+        for stream in listener.incoming() {
+            match stream {
+                Ok(mut stream) => match tls_config {
+                    Some(ref config) => {
+                        let mut conn = match rustls::ServerConnection::new(config.clone()) {
+                            Ok(conn) => conn,
+                            Err(e) => {
+                                eprintln!("[oauth_server] Failed to create TLS session: {}", e);
+                                continue;
+                            }
+                        };
+                        let tls_stream = rustls::Stream::new(&mut conn, &mut stream);
+                        handle_connection(&app_handle, tls_stream);
+                    }
+                    None => handle_connection(&app_handle, stream),
+                },
+                Err(e) => {
+                    eprintln!("[oauth_server] Failed to accept connection: {}", e);
+                }
+            }
+        }
+    });
+
+    Ok(port)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_callback_code() {
+        let callback = parse_callback_request("GET /callback?code=abc123&state=xyz HTTP/1.1")
+            .expect("callback should parse");
+        assert_eq!(callback.code, "abc123");
+        assert_eq!(callback.provider, "convex");
+        assert_eq!(callback.state, Some("xyz".to_string()));
+    }
+
+    #[test]
+    fn test_parse_callback_missing_code() {
+        assert!(parse_callback_request("GET /callback?state=xyz HTTP/1.1").is_none());
+        assert!(parse_callback_request("GET / HTTP/1.1").is_none());
+    }
+
+    #[test]
+    fn test_parse_callback_provider_from_path() {
+        let callback = parse_callback_request("GET /callback/github?code=gh123 HTTP/1.1")
+            .expect("callback should parse");
+        assert_eq!(callback.provider, "github");
+        assert_eq!(callback.code, "gh123");
+    }
+
+    #[test]
+    fn test_parse_callback_dashboard_token() {
+        let callback = parse_callback_request("GET /callback?token=tok123 HTTP/1.1")
+            .expect("callback should parse");
+        assert_eq!(callback.provider, "convex-dashboard");
+        assert_eq!(callback.code, "tok123");
+    }
+}
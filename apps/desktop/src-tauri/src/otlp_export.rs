@@ -0,0 +1,246 @@
+//! OpenTelemetry trace export
+//!
+//! Reconstructs per-request traces from the log store — a root span for
+//! the request plus child spans for the function executions grouped under
+//! its request_id — and ships them to a configured OTLP/HTTP collector as
+//! JSON, so Convex activity lines up with the rest of the stack in
+//! Jaeger/Tempo. Nothing is sent without an explicitly configured endpoint.
+
+use rusqlite::params;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+use tauri::State;
+
+use crate::log_store::DbConnection;
+
+const OTLP_CONFIG_FILE: &str = "otlp.json";
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OtlpConfig {
+    /// Collector traces endpoint, e.g. "http://localhost:4318/v1/traces"
+    pub endpoint: String,
+    /// Extra request headers (e.g. collector auth)
+    #[serde(default)]
+    pub headers: BTreeMap<String, String>,
+}
+
+/// What an export run shipped
+#[derive(Debug, Clone, Serialize)]
+pub struct ExportReport {
+    pub traces: usize,
+    pub spans: usize,
+}
+
+/// One log row that participates in a trace
+struct TraceRow {
+    id: String,
+    ts: i64,
+    request_id: String,
+    execution_id: Option<String>,
+    function_path: Option<String>,
+    duration_ms: Option<i64>,
+    success: Option<i64>,
+}
+
+fn config_path() -> Result<PathBuf, String> {
+    let home = std::env::var("HOME")
+        .or_else(|_| std::env::var("USERPROFILE"))
+        .map_err(|_| "Failed to get home directory")?;
+
+    let app_data = PathBuf::from(home).join(".convex-panel");
+    std::fs::create_dir_all(&app_data)
+        .map_err(|e| format!("Failed to create app data directory: {}", e))?;
+
+    Ok(app_data.join(OTLP_CONFIG_FILE))
+}
+
+fn load_config() -> OtlpConfig {
+    config_path()
+        .ok()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Stable 16-byte trace id from the request id
+fn trace_id(request_id: &str) -> String {
+    hex::encode(&Sha256::digest(request_id.as_bytes())[..16])
+}
+
+/// Stable 8-byte span id from any unique string
+fn span_id(seed: &str) -> String {
+    hex::encode(&Sha256::digest(seed.as_bytes())[..8])
+}
+
+fn string_attr(key: &str, value: &str) -> serde_json::Value {
+    serde_json::json!({ "key": key, "value": { "stringValue": value } })
+}
+
+/// Build the OTLP span for one function execution
+fn child_span(trace: &str, parent: &str, row: &TraceRow) -> serde_json::Value {
+    let start_ns = row.ts as i128 * 1_000_000;
+    let end_ns = start_ns + row.duration_ms.unwrap_or(0) as i128 * 1_000_000;
+    let failed = row.success == Some(0);
+
+    serde_json::json!({
+        "traceId": trace,
+        "spanId": span_id(row.execution_id.as_deref().unwrap_or(&row.id)),
+        "parentSpanId": parent,
+        "name": row.function_path.clone().unwrap_or_else(|| "execution".to_string()),
+        "kind": 1,
+        "startTimeUnixNano": start_ns.to_string(),
+        "endTimeUnixNano": end_ns.to_string(),
+        "attributes": [
+            string_attr("convex.log_id", &row.id),
+            string_attr(
+                "convex.execution_id",
+                row.execution_id.as_deref().unwrap_or(""),
+            ),
+        ],
+        "status": { "code": if failed { 2 } else { 1 } },
+    })
+}
+
+/// Reconstruct traces for a window and POST them to the collector. Returns
+/// how many traces and spans were shipped.
+#[tauri::command]
+pub async fn export_otlp_traces(
+    db: State<'_, DbConnection>,
+    deployment: String,
+    start_ts: i64,
+    end_ts: i64,
+) -> Result<ExportReport, String> {
+    let config = load_config();
+    if config.endpoint.is_empty() {
+        return Err("No OTLP collector endpoint is configured".to_string());
+    }
+
+    let rows: Vec<TraceRow> = {
+        let conn = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+        let mut stmt = conn
+            .prepare(
+                "SELECT id, ts, request_id, execution_id, function_path, duration_ms, success
+                 FROM logs
+                 WHERE deployment = ?1 AND ts >= ?2 AND ts <= ?3 AND request_id IS NOT NULL
+                 ORDER BY request_id, ts ASC",
+            )
+            .map_err(|e| format!("Query error: {}", e))?;
+
+        let rows = stmt
+            .query_map(params![deployment, start_ts, end_ts], |row| {
+                Ok(TraceRow {
+                    id: row.get(0)?,
+                    ts: row.get(1)?,
+                    request_id: row.get(2)?,
+                    execution_id: row.get(3)?,
+                    function_path: row.get(4)?,
+                    duration_ms: row.get(5)?,
+                    success: row.get(6)?,
+                })
+            })
+            .map_err(|e| format!("Query error: {}", e))?;
+        rows.collect::<Result<_, _>>()
+            .map_err(|e| format!("Query error: {}", e))?
+    };
+
+    // Group by request; BTreeMap keeps trace output deterministic
+    let mut requests: BTreeMap<String, Vec<TraceRow>> = BTreeMap::new();
+    for row in rows {
+        requests.entry(row.request_id.clone()).or_default().push(row);
+    }
+    if requests.is_empty() {
+        return Ok(ExportReport {
+            traces: 0,
+            spans: 0,
+        });
+    }
+
+    let mut spans: Vec<serde_json::Value> = Vec::new();
+    let traces = requests.len();
+    for (request_id, rows) in &requests {
+        let trace = trace_id(request_id);
+        let root = span_id(request_id);
+
+        let start_ns = rows.iter().map(|r| r.ts).min().unwrap_or(0) as i128 * 1_000_000;
+        let end_ns = rows
+            .iter()
+            .map(|r| r.ts as i128 * 1_000_000 + r.duration_ms.unwrap_or(0) as i128 * 1_000_000)
+            .max()
+            .unwrap_or(start_ns);
+        let failed = rows.iter().any(|r| r.success == Some(0));
+
+        // Root span covers the whole request
+        spans.push(serde_json::json!({
+            "traceId": trace,
+            "spanId": root,
+            "name": rows[0]
+                .function_path
+                .clone()
+                .unwrap_or_else(|| "request".to_string()),
+            "kind": 2,
+            "startTimeUnixNano": start_ns.to_string(),
+            "endTimeUnixNano": end_ns.to_string(),
+            "attributes": [string_attr("convex.request_id", request_id)],
+            "status": { "code": if failed { 2 } else { 1 } },
+        }));
+
+        for row in rows {
+            spans.push(child_span(&trace, &root, row));
+        }
+    }
+    let span_count = spans.len();
+
+    let payload = serde_json::json!({
+        "resourceSpans": [{
+            "resource": {
+                "attributes": [
+                    string_attr("service.name", "convex"),
+                    string_attr("convex.deployment", &deployment),
+                ],
+            },
+            "scopeSpans": [{
+                "scope": { "name": "convex-panel" },
+                "spans": spans,
+            }],
+        }],
+    });
+
+    let mut request = reqwest::Client::new()
+        .post(&config.endpoint)
+        .header("Content-Type", "application/json")
+        .json(&payload);
+    for (name, value) in &config.headers {
+        request = request.header(name, value);
+    }
+
+    let response = request
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach OTLP collector: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!(
+            "OTLP collector returned HTTP {}",
+            response.status()
+        ));
+    }
+
+    Ok(ExportReport {
+        traces,
+        spans: span_count,
+    })
+}
+
+#[tauri::command]
+pub fn get_otlp_config() -> OtlpConfig {
+    load_config()
+}
+
+#[tauri::command]
+pub fn set_otlp_config(config: OtlpConfig) -> Result<(), String> {
+    let path = config_path()?;
+    let json = serde_json::to_string_pretty(&config)
+        .map_err(|e| format!("Failed to serialize OTLP config: {}", e))?;
+    std::fs::write(&path, json).map_err(|e| format!("Failed to write OTLP config: {}", e))
+}
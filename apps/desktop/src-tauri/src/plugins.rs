@@ -0,0 +1,241 @@
+//! Plugin system
+//!
+//! Plugins are subprocesses speaking newline-delimited JSON over stdio,
+//! discovered from ~/.convex-panel/plugins/<name>/plugin.json. A plugin
+//! declares capabilities — extra MCP tools, a log sink, or a notification
+//! channel — and enabled plugins are kept running for the life of the app.
+//!
+//! Protocol: the panel writes one JSON object per line to the plugin's
+//! stdin ({"type": "initialize"}, {"type": "logs", ...},
+//! {"type": "notification", ...}); the plugin answers "initialize" with
+//! {"type": "capabilities", "tools": [...]}.
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process::{Child, Command, Stdio};
+use std::sync::Mutex;
+
+const STATE_FILE: &str = "state.json";
+
+/// Running plugin subprocesses by name
+static RUNNING: Lazy<Mutex<HashMap<String, Child>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// Manifest a plugin ships as plugin.json
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PluginManifest {
+    pub name: String,
+    pub version: String,
+    /// Executable to spawn, relative to the plugin directory or absolute
+    pub command: String,
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Any of "tools", "log-sink", "notifications"
+    #[serde(default)]
+    pub capabilities: Vec<String>,
+}
+
+/// Plugin as reported to the frontend
+#[derive(Debug, Clone, Serialize)]
+pub struct PluginInfo {
+    pub name: String,
+    pub version: String,
+    pub path: String,
+    pub capabilities: Vec<String>,
+    pub enabled: bool,
+    pub running: bool,
+}
+
+fn plugins_dir() -> Result<PathBuf, String> {
+    let home = std::env::var("HOME")
+        .or_else(|_| std::env::var("USERPROFILE"))
+        .map_err(|_| "Failed to get home directory")?;
+
+    let dir = PathBuf::from(home).join(".convex-panel").join("plugins");
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create plugins directory: {}", e))?;
+
+    Ok(dir)
+}
+
+/// Enabled flags per plugin name
+fn load_state() -> HashMap<String, bool> {
+    plugins_dir()
+        .ok()
+        .and_then(|dir| std::fs::read_to_string(dir.join(STATE_FILE)).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_state(state: &HashMap<String, bool>) -> Result<(), String> {
+    let path = plugins_dir()?.join(STATE_FILE);
+    let json = serde_json::to_string_pretty(state)
+        .map_err(|e| format!("Failed to serialize plugin state: {}", e))?;
+    std::fs::write(&path, json).map_err(|e| format!("Failed to write plugin state: {}", e))
+}
+
+/// Discovered manifests with their plugin directories
+fn discover() -> Vec<(PathBuf, PluginManifest)> {
+    let dir = match plugins_dir() {
+        Ok(dir) => dir,
+        Err(_) => return Vec::new(),
+    };
+
+    let entries = match std::fs::read_dir(&dir) {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+
+    let mut plugins: Vec<(PathBuf, PluginManifest)> = entries
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            if !path.is_dir() {
+                return None;
+            }
+            let manifest: PluginManifest =
+                serde_json::from_str(&std::fs::read_to_string(path.join("plugin.json")).ok()?)
+                    .ok()?;
+            Some((path, manifest))
+        })
+        .collect();
+
+    plugins.sort_by(|a, b| a.1.name.cmp(&b.1.name));
+    plugins
+}
+
+fn spawn_plugin(dir: &std::path::Path, manifest: &PluginManifest) -> Result<(), String> {
+    let mut running = RUNNING.lock().unwrap();
+    if running.contains_key(&manifest.name) {
+        return Ok(());
+    }
+
+    let command = dir.join(&manifest.command);
+    let command = if command.exists() {
+        command
+    } else {
+        PathBuf::from(&manifest.command)
+    };
+
+    let mut child = Command::new(&command)
+        .args(&manifest.args)
+        .current_dir(dir)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .map_err(|e| format!("Failed to start plugin {}: {}", manifest.name, e))?;
+
+    if let Some(stdin) = child.stdin.as_mut() {
+        let _ = writeln!(stdin, "{}", serde_json::json!({ "type": "initialize" }));
+    }
+
+    running.insert(manifest.name.clone(), child);
+    Ok(())
+}
+
+fn stop_plugin(name: &str) -> bool {
+    let mut running = RUNNING.lock().unwrap();
+    match running.remove(name) {
+        Some(mut child) => {
+            // Closing stdin asks the plugin to exit; kill if it lingers
+            drop(child.stdin.take());
+            std::thread::sleep(std::time::Duration::from_millis(200));
+            if matches!(child.try_wait(), Ok(None)) {
+                let _ = child.kill();
+            }
+            let _ = child.wait();
+            true
+        }
+        None => false,
+    }
+}
+
+/// Forward an event to every running plugin with the given capability
+/// ("log-sink" receives "logs" events, "notifications" receives
+/// "notification" events)
+pub fn forward_event(capability: &str, payload: serde_json::Value) {
+    let targets: Vec<String> = discover()
+        .iter()
+        .filter(|(_, manifest)| manifest.capabilities.iter().any(|c| c == capability))
+        .map(|(_, manifest)| manifest.name.clone())
+        .collect();
+
+    let mut running = RUNNING.lock().unwrap();
+    for name in targets {
+        if let Some(child) = running.get_mut(&name) {
+            if let Some(stdin) = child.stdin.as_mut() {
+                let _ = writeln!(stdin, "{}", payload);
+            }
+        }
+    }
+}
+
+/// Start every enabled plugin; called once on startup
+pub fn start_enabled_plugins() {
+    let state = load_state();
+    for (dir, manifest) in discover() {
+        if state.get(&manifest.name).copied().unwrap_or(false) {
+            if let Err(e) = spawn_plugin(&dir, &manifest) {
+                eprintln!("[plugins] {}", e);
+            }
+        }
+    }
+}
+
+/// Discovered plugins with their enabled/running state
+#[tauri::command]
+pub fn list_plugins() -> Vec<PluginInfo> {
+    let state = load_state();
+    let running = RUNNING.lock().unwrap();
+
+    discover()
+        .into_iter()
+        .map(|(path, manifest)| PluginInfo {
+            enabled: state.get(&manifest.name).copied().unwrap_or(false),
+            running: running.contains_key(&manifest.name),
+            name: manifest.name,
+            version: manifest.version,
+            path: path.to_string_lossy().to_string(),
+            capabilities: manifest.capabilities,
+        })
+        .collect()
+}
+
+/// Enable a plugin and start it
+#[tauri::command]
+pub fn enable_plugin(name: String) -> Result<(), String> {
+    let (dir, manifest) = discover()
+        .into_iter()
+        .find(|(_, manifest)| manifest.name == name)
+        .ok_or_else(|| format!("Plugin {} not found", name))?;
+
+    spawn_plugin(&dir, &manifest)?;
+
+    let mut state = load_state();
+    state.insert(name, true);
+    save_state(&state)
+}
+
+/// Disable a plugin and stop its process
+#[tauri::command]
+pub fn disable_plugin(name: String) -> Result<(), String> {
+    stop_plugin(&name);
+
+    let mut state = load_state();
+    state.insert(name, false);
+    save_state(&state)
+}
+
+/// Restart enabled plugins, picking up new or changed manifests
+#[tauri::command]
+pub fn reload_plugins() -> Vec<PluginInfo> {
+    let names: Vec<String> = RUNNING.lock().unwrap().keys().cloned().collect();
+    for name in names {
+        stop_plugin(&name);
+    }
+    start_enabled_plugins();
+    list_plugins()
+}
@@ -0,0 +1,184 @@
+//! Power and sleep awareness
+//!
+//! Watches for system sleep/wake (detected as wall-clock jumps across the
+//! poll interval — no OS hooks needed) and battery discharge, and suspends
+//! the background pollers through the job supervisor so the panel doesn't
+//! drain a sleeping or unplugged laptop. "Reduce activity on battery" is a
+//! setting; sleep handling is always on.
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+
+const POWER_FILE: &str = "power.json";
+const POLL_INTERVAL_SECS: u64 = 30;
+
+/// A gap this much longer than the interval means the machine slept
+const SLEEP_JUMP_MS: i64 = 2 * POLL_INTERVAL_SECS as i64 * 1000;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PowerSettings {
+    /// Suspend background pollers while running on battery
+    pub reduce_on_battery: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PowerStatus {
+    pub on_battery: bool,
+    pub suspended: bool,
+    pub last_wake_ms: Option<i64>,
+}
+
+static LAST_WAKE_MS: Lazy<Mutex<Option<i64>>> = Lazy::new(|| Mutex::new(None));
+static ON_BATTERY: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+fn settings_path() -> Result<PathBuf, String> {
+    let home = std::env::var("HOME")
+        .or_else(|_| std::env::var("USERPROFILE"))
+        .map_err(|_| "Failed to get home directory")?;
+
+    let app_data = PathBuf::from(home).join(".convex-panel");
+    std::fs::create_dir_all(&app_data)
+        .map_err(|e| format!("Failed to create app data directory: {}", e))?;
+
+    Ok(app_data.join(POWER_FILE))
+}
+
+fn load_settings() -> PowerSettings {
+    settings_path()
+        .ok()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Whether the machine is currently discharging. None when there's no
+/// battery or the state can't be read (desktops).
+#[cfg(target_os = "linux")]
+fn on_battery_power() -> Option<bool> {
+    let supplies = std::fs::read_dir("/sys/class/power_supply").ok()?;
+    for supply in supplies.flatten() {
+        let kind = std::fs::read_to_string(supply.path().join("type")).ok()?;
+        if kind.trim() == "Battery" {
+            let status = std::fs::read_to_string(supply.path().join("status")).ok()?;
+            return Some(status.trim() == "Discharging");
+        }
+    }
+    None
+}
+
+#[cfg(target_os = "macos")]
+fn on_battery_power() -> Option<bool> {
+    let output = std::process::Command::new("pmset")
+        .args(["-g", "batt"])
+        .output()
+        .ok()?;
+    let text = String::from_utf8_lossy(&output.stdout);
+    if text.contains("Battery Power") {
+        Some(true)
+    } else if text.contains("AC Power") {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn on_battery_power() -> Option<bool> {
+    let output = std::process::Command::new("powershell")
+        .args([
+            "-NoProfile",
+            "-Command",
+            "(Get-CimInstance Win32_Battery).BatteryStatus",
+        ])
+        .output()
+        .ok()?;
+    let text = String::from_utf8_lossy(&output.stdout);
+    // BatteryStatus 1 means discharging
+    text.trim().parse::<u32>().ok().map(|status| status == 1)
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos", target_os = "windows")))]
+fn on_battery_power() -> Option<bool> {
+    None
+}
+
+fn emit_power_event(app: &AppHandle, event: &str, payload: serde_json::Value) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.emit(event, payload);
+    }
+}
+
+/// Start the power monitor loop
+pub fn start_power_monitor(app: AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        let mut last_tick = chrono::Utc::now().timestamp_millis();
+
+        loop {
+            tokio::time::sleep(std::time::Duration::from_secs(POLL_INTERVAL_SECS)).await;
+            let now = chrono::Utc::now().timestamp_millis();
+
+            // A big wall-clock jump means we slept through the interval
+            if now - last_tick > SLEEP_JUMP_MS {
+                *LAST_WAKE_MS.lock().unwrap() = Some(now);
+                emit_power_event(
+                    &app,
+                    "system-woke",
+                    serde_json::json!({ "sleptMs": now - last_tick }),
+                );
+            }
+            last_tick = now;
+
+            let on_battery = on_battery_power().unwrap_or(false);
+            let was_on_battery =
+                ON_BATTERY.swap(on_battery, std::sync::atomic::Ordering::Relaxed);
+            if on_battery != was_on_battery {
+                emit_power_event(
+                    &app,
+                    "power-source-changed",
+                    serde_json::json!({ "onBattery": on_battery }),
+                );
+            }
+
+            let suspend = on_battery && load_settings().reduce_on_battery;
+            if suspend != crate::background_jobs::suspended() {
+                crate::background_jobs::set_suspended(suspend);
+                emit_power_event(
+                    &app,
+                    "background-work-suspended",
+                    serde_json::json!({ "suspended": suspend }),
+                );
+            }
+        }
+    });
+}
+
+#[tauri::command]
+pub fn get_power_settings() -> PowerSettings {
+    load_settings()
+}
+
+#[tauri::command]
+pub fn set_power_settings(settings: PowerSettings) -> Result<(), String> {
+    let path = settings_path()?;
+    let json = serde_json::to_string_pretty(&settings)
+        .map_err(|e| format!("Failed to serialize power settings: {}", e))?;
+    std::fs::write(&path, json).map_err(|e| format!("Failed to write power settings: {}", e))?;
+
+    // Lifting the battery rule resumes immediately rather than next tick
+    if !settings.reduce_on_battery && crate::background_jobs::suspended() {
+        crate::background_jobs::set_suspended(false);
+    }
+    Ok(())
+}
+
+#[tauri::command]
+pub fn get_power_status() -> PowerStatus {
+    PowerStatus {
+        on_battery: ON_BATTERY.load(std::sync::atomic::Ordering::Relaxed),
+        suspended: crate::background_jobs::suspended(),
+        last_wake_ms: *LAST_WAKE_MS.lock().unwrap(),
+    }
+}
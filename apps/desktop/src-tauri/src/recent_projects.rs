@@ -0,0 +1,111 @@
+//! Recently opened projects registry
+//!
+//! Persists opened project paths with last-opened timestamps and the
+//! deployment detected from the project's env files, so the welcome screen
+//! and tray can offer quick project switching.
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use crate::env_file;
+
+const RECENT_PROJECTS_FILE: &str = "recent-projects.json";
+const MAX_RECENT_PROJECTS: usize = 15;
+
+/// One remembered project
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecentProject {
+    pub path: String,
+    pub last_opened_ms: i64,
+    /// Deployment name from the project's env files, when detected
+    pub deployment: Option<String>,
+}
+
+fn registry_path() -> Result<PathBuf, String> {
+    let home = std::env::var("HOME")
+        .or_else(|_| std::env::var("USERPROFILE"))
+        .map_err(|_| "Failed to get home directory")?;
+
+    let app_data = PathBuf::from(home).join(".convex-panel");
+    std::fs::create_dir_all(&app_data)
+        .map_err(|e| format!("Failed to create app data directory: {}", e))?;
+
+    Ok(app_data.join(RECENT_PROJECTS_FILE))
+}
+
+fn load_registry() -> Vec<RecentProject> {
+    registry_path()
+        .ok()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_registry(projects: &[RecentProject]) -> Result<(), String> {
+    let path = registry_path()?;
+    let json = serde_json::to_string_pretty(projects)
+        .map_err(|e| format!("Failed to serialize recent projects: {}", e))?;
+    std::fs::write(&path, json).map_err(|e| format!("Failed to write recent projects: {}", e))
+}
+
+/// Deployment name from the project's env files (CONVEX_DEPLOYMENT)
+fn detect_deployment(project_path: &str) -> Option<String> {
+    for name in [".env.local", ".env"] {
+        let path = std::path::Path::new(project_path).join(name);
+        if let Ok(content) = std::fs::read_to_string(&path) {
+            if let Some(value) = env_file::get_value(&content, "CONVEX_DEPLOYMENT") {
+                // Values look like "dev:brave-cat-123"; keep just the name
+                let deployment = value.split(':').next_back().unwrap_or(&value).to_string();
+                if !deployment.is_empty() {
+                    return Some(deployment);
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Record that a project was opened, moving it to the top of the list
+#[tauri::command]
+pub fn record_recent_project(path: String) -> Result<Vec<RecentProject>, String> {
+    let mut projects = load_registry();
+    projects.retain(|p| p.path != path);
+
+    projects.insert(
+        0,
+        RecentProject {
+            deployment: detect_deployment(&path),
+            last_opened_ms: chrono::Utc::now().timestamp_millis(),
+            path,
+        },
+    );
+    projects.truncate(MAX_RECENT_PROJECTS);
+
+    save_registry(&projects)?;
+    Ok(projects)
+}
+
+/// Recently opened projects, most recent first. Entries whose directory no
+/// longer exists are dropped.
+#[tauri::command]
+pub fn list_recent_projects() -> Vec<RecentProject> {
+    let mut projects = load_registry();
+    let before = projects.len();
+
+    projects.retain(|p| std::path::Path::new(&p.path).exists());
+
+    if projects.len() != before {
+        let _ = save_registry(&projects);
+    }
+
+    projects
+}
+
+/// Remove one project from the registry
+#[tauri::command]
+pub fn remove_recent_project(path: String) -> Result<Vec<RecentProject>, String> {
+    let mut projects = load_registry();
+    projects.retain(|p| p.path != path);
+    save_registry(&projects)?;
+    Ok(projects)
+}
@@ -0,0 +1,369 @@
+//! Schema snapshot history and diffing
+//!
+//! Periodically fetches a deployment's schema through the Convex client and
+//! stores a new versioned snapshot whenever it changes, so schema drift on a
+//! dev deployment can be reviewed after the fact with `diff_schema`.
+
+use once_cell::sync::Lazy;
+use rusqlite::{params, OptionalExtension};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter, Manager, State};
+
+use crate::convex_client::ConvexClient;
+use crate::log_store::DbConnection;
+
+/// Active schema watch generations per deployment URL (same pattern as
+/// log_stream)
+static WATCHES: Lazy<Mutex<HashMap<String, u64>>> = Lazy::new(|| Mutex::new(HashMap::new()));
+
+const DEFAULT_WATCH_INTERVAL_SECS: u64 = 15 * 60;
+
+/// Snapshot metadata, without the (potentially large) schema body
+#[derive(Debug, Clone, Serialize)]
+pub struct SchemaSnapshotMeta {
+    pub id: i64,
+    pub ts: i64,
+    pub hash: String,
+    pub table_count: i64,
+}
+
+/// Field/index changes for one table present in both snapshots
+#[derive(Debug, Clone, Serialize)]
+pub struct TableChange {
+    pub table: String,
+    pub added_fields: Vec<String>,
+    pub removed_fields: Vec<String>,
+    pub added_indexes: Vec<String>,
+    pub removed_indexes: Vec<String>,
+}
+
+/// Difference between two schema snapshots
+#[derive(Debug, Clone, Serialize)]
+pub struct SchemaDiff {
+    pub added_tables: Vec<String>,
+    pub removed_tables: Vec<String>,
+    pub changed_tables: Vec<TableChange>,
+}
+
+fn schema_hash(schema: &serde_json::Value) -> String {
+    let canonical = serde_json::to_string(schema).unwrap_or_default();
+    hex::encode(Sha256::digest(canonical.as_bytes()))
+}
+
+/// Field names for a table entry. Shapes nest fields under an object; a
+/// dedicated `fields` key wins when present.
+fn table_fields(table: &serde_json::Value) -> Vec<String> {
+    let fields = table.get("fields").unwrap_or(table);
+    match fields {
+        serde_json::Value::Object(obj) => obj.keys().cloned().collect(),
+        serde_json::Value::Array(list) => list
+            .iter()
+            .filter_map(|f| {
+                f.get("fieldName")
+                    .or_else(|| f.get("name"))
+                    .and_then(|v| v.as_str())
+                    .map(String::from)
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Index names for a table entry, when the schema includes them
+fn table_indexes(table: &serde_json::Value) -> Vec<String> {
+    table
+        .get("indexes")
+        .and_then(|v| v.as_array())
+        .map(|list| {
+            list.iter()
+                .filter_map(|i| {
+                    i.get("indexDescriptor")
+                        .or_else(|| i.get("name"))
+                        .and_then(|v| v.as_str())
+                        .map(String::from)
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn missing_from<'a>(from: &'a [String], in_other: &[String]) -> Vec<String> {
+    let mut out: Vec<String> = from
+        .iter()
+        .filter(|item| !in_other.contains(item))
+        .cloned()
+        .collect();
+    out.sort();
+    out
+}
+
+/// Diff two schema documents into added/removed tables, fields, and indexes
+pub fn diff_schemas(a: &serde_json::Value, b: &serde_json::Value) -> SchemaDiff {
+    let empty = serde_json::Map::new();
+    let tables_a = a.as_object().unwrap_or(&empty);
+    let tables_b = b.as_object().unwrap_or(&empty);
+
+    let names_a: Vec<String> = tables_a.keys().cloned().collect();
+    let names_b: Vec<String> = tables_b.keys().cloned().collect();
+
+    let mut changed_tables = Vec::new();
+    for (name, table_a) in tables_a {
+        let table_b = match tables_b.get(name) {
+            Some(t) => t,
+            None => continue,
+        };
+        if table_a == table_b {
+            continue;
+        }
+
+        let fields_a = table_fields(table_a);
+        let fields_b = table_fields(table_b);
+        let indexes_a = table_indexes(table_a);
+        let indexes_b = table_indexes(table_b);
+
+        changed_tables.push(TableChange {
+            table: name.clone(),
+            added_fields: missing_from(&fields_b, &fields_a),
+            removed_fields: missing_from(&fields_a, &fields_b),
+            added_indexes: missing_from(&indexes_b, &indexes_a),
+            removed_indexes: missing_from(&indexes_a, &indexes_b),
+        });
+    }
+    changed_tables.sort_by(|a, b| a.table.cmp(&b.table));
+
+    SchemaDiff {
+        added_tables: missing_from(&names_b, &names_a),
+        removed_tables: missing_from(&names_a, &names_b),
+        changed_tables,
+    }
+}
+
+/// Fetch the current schema and store a snapshot if it differs from the
+/// latest one. Returns the new snapshot id, or None when unchanged.
+async fn snapshot_if_changed(
+    app: &AppHandle,
+    deployment_url: &str,
+    admin_key: Option<String>,
+) -> Result<Option<i64>, String> {
+    let client = ConvexClient::for_deployment(deployment_url, admin_key)?;
+    let schema = client.schema().await?;
+    let hash = schema_hash(&schema);
+
+    let db = app.state::<DbConnection>();
+    let conn = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+
+    let latest_hash: Option<String> = conn
+        .query_row(
+            "SELECT hash FROM schema_snapshots WHERE deployment = ?1 ORDER BY ts DESC LIMIT 1",
+            params![deployment_url],
+            |row| row.get(0),
+        )
+        .optional()
+        .map_err(|e| format!("Query error: {}", e))?;
+
+    if latest_hash.as_deref() == Some(&hash) {
+        return Ok(None);
+    }
+
+    conn.execute(
+        "INSERT INTO schema_snapshots (deployment, ts, hash, schema_json) VALUES (?1, ?2, ?3, ?4)",
+        params![
+            deployment_url,
+            chrono::Utc::now().timestamp_millis(),
+            hash,
+            serde_json::to_string(&schema).unwrap_or_default(),
+        ],
+    )
+    .map_err(|e| format!("Insert error: {}", e))?;
+
+    Ok(Some(conn.last_insert_rowid()))
+}
+
+/// Take a schema snapshot now. Returns the snapshot id when the schema
+/// changed since the last one.
+#[tauri::command]
+pub async fn snapshot_schema(
+    app: AppHandle,
+    deployment_url: String,
+    admin_key: Option<String>,
+) -> Result<Option<i64>, String> {
+    snapshot_if_changed(&app, &deployment_url, admin_key).await
+}
+
+/// Start polling a deployment's schema, snapshotting on change and emitting
+/// "schema-changed" to the main window
+#[tauri::command]
+pub fn watch_schema(
+    app: AppHandle,
+    deployment_url: String,
+    admin_key: Option<String>,
+    interval_secs: Option<u64>,
+) -> Result<(), String> {
+    // Resolve up front so a missing deploy key fails the command, not the task
+    let admin_key = crate::convex_client::resolve_admin_key(&deployment_url, admin_key)?;
+    let deployment_url = deployment_url.trim_end_matches('/').to_string();
+    let interval = Duration::from_secs(interval_secs.unwrap_or(DEFAULT_WATCH_INTERVAL_SECS).max(60));
+
+    let generation = {
+        let mut watches = WATCHES.lock().unwrap();
+        let generation = watches.get(&deployment_url).map(|g| g + 1).unwrap_or(1);
+        watches.insert(deployment_url.clone(), generation);
+        generation
+    };
+
+    tauri::async_runtime::spawn(async move {
+        loop {
+            let active = WATCHES
+                .lock()
+                .unwrap()
+                .get(&deployment_url)
+                .map(|g| *g == generation)
+                .unwrap_or(false);
+            if !active {
+                break;
+            }
+
+            match snapshot_if_changed(&app, &deployment_url, Some(admin_key.clone())).await {
+                Ok(Some(snapshot_id)) => {
+                    if let Some(window) = app.get_webview_window("main") {
+                        let _ = window.emit(
+                            "schema-changed",
+                            serde_json::json!({
+                                "deployment": deployment_url,
+                                "snapshotId": snapshot_id,
+                            }),
+                        );
+                    }
+                }
+                Ok(None) => {}
+                Err(e) => eprintln!("[schema_store] Snapshot failed for {}: {}", deployment_url, e),
+            }
+
+            tokio::time::sleep(interval).await;
+        }
+    });
+
+    Ok(())
+}
+
+/// Stop polling a deployment's schema
+#[tauri::command]
+pub fn unwatch_schema(deployment_url: String) -> Result<bool, String> {
+    let deployment_url = deployment_url.trim_end_matches('/').to_string();
+    Ok(WATCHES.lock().unwrap().remove(&deployment_url).is_some())
+}
+
+/// Snapshot history for a deployment, newest first
+#[tauri::command]
+pub async fn get_schema_history(
+    db: State<'_, DbConnection>,
+    deployment: String,
+    limit: Option<i64>,
+) -> Result<Vec<SchemaSnapshotMeta>, String> {
+    let conn = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+
+    let mut stmt = conn
+        .prepare(
+            "SELECT id, ts, hash, schema_json FROM schema_snapshots
+             WHERE deployment = ?1 ORDER BY ts DESC LIMIT ?2",
+        )
+        .map_err(|e| format!("Query error: {}", e))?;
+
+    let rows = stmt
+        .query_map(params![deployment, limit.unwrap_or(50).min(500)], |row| {
+            let id: i64 = row.get(0)?;
+            let ts: i64 = row.get(1)?;
+            let hash: String = row.get(2)?;
+            let schema_json: String = row.get(3)?;
+            Ok((id, ts, hash, schema_json))
+        })
+        .map_err(|e| format!("Query error: {}", e))?;
+
+    rows.map(|row| {
+        let (id, ts, hash, schema_json) = row.map_err(|e| format!("Query error: {}", e))?;
+        let table_count = serde_json::from_str::<serde_json::Value>(&schema_json)
+            .ok()
+            .and_then(|schema| schema.as_object().map(|obj| obj.len() as i64))
+            .unwrap_or(0);
+        Ok(SchemaSnapshotMeta {
+            id,
+            ts,
+            hash,
+            table_count,
+        })
+    })
+    .collect()
+}
+
+/// Full schema body of one snapshot
+#[tauri::command]
+pub async fn get_schema_snapshot(
+    db: State<'_, DbConnection>,
+    snapshot_id: i64,
+) -> Result<serde_json::Value, String> {
+    let conn = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+
+    let schema_json: String = conn
+        .query_row(
+            "SELECT schema_json FROM schema_snapshots WHERE id = ?1",
+            params![snapshot_id],
+            |row| row.get(0),
+        )
+        .map_err(|_| format!("Snapshot {} not found", snapshot_id))?;
+
+    serde_json::from_str(&schema_json).map_err(|e| format!("Failed to parse snapshot: {}", e))
+}
+
+/// Diff two snapshots (a -> b) into added/removed tables, fields, and indexes
+#[tauri::command]
+pub async fn diff_schema(
+    db: State<'_, DbConnection>,
+    a: i64,
+    b: i64,
+) -> Result<SchemaDiff, String> {
+    let load = |conn: &rusqlite::Connection, id: i64| -> Result<serde_json::Value, String> {
+        let schema_json: String = conn
+            .query_row(
+                "SELECT schema_json FROM schema_snapshots WHERE id = ?1",
+                params![id],
+                |row| row.get(0),
+            )
+            .map_err(|_| format!("Snapshot {} not found", id))?;
+        serde_json::from_str(&schema_json).map_err(|e| format!("Failed to parse snapshot: {}", e))
+    };
+
+    let conn = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+    let schema_a = load(&conn, a)?;
+    let schema_b = load(&conn, b)?;
+
+    Ok(diff_schemas(&schema_a, &schema_b))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_schemas_tables_and_fields() {
+        let a = serde_json::json!({
+            "users": { "fields": { "name": {}, "email": {} } },
+            "old": { "fields": {} },
+        });
+        let b = serde_json::json!({
+            "users": { "fields": { "name": {}, "avatarUrl": {} } },
+            "messages": { "fields": {} },
+        });
+
+        let diff = diff_schemas(&a, &b);
+        assert_eq!(diff.added_tables, vec!["messages"]);
+        assert_eq!(diff.removed_tables, vec!["old"]);
+        assert_eq!(diff.changed_tables.len(), 1);
+        assert_eq!(diff.changed_tables[0].table, "users");
+        assert_eq!(diff.changed_tables[0].added_fields, vec!["avatarUrl"]);
+        assert_eq!(diff.changed_tables[0].removed_fields, vec!["email"]);
+    }
+}
@@ -101,34 +101,43 @@ fn save_store(store: &SecureStore) -> Result<(), String> {
         .map_err(|e| format!("Failed to write secrets file: {}", e))
 }
 
+/// Synchronous secret write for use from other Rust modules
+pub(crate) fn store_secret(key: &str, value: &str) -> Result<(), String> {
+    let mut store = load_store()?;
+    store.secrets.insert(key.to_string(), value.to_string());
+    save_store(&store)
+}
+
+/// Synchronous secret read for use from other Rust modules
+pub(crate) fn read_secret(key: &str) -> Result<Option<String>, String> {
+    let store = load_store()?;
+    Ok(store.secrets.get(key).cloned())
+}
+
+/// Synchronous secret removal for use from other Rust modules
+pub(crate) fn remove_secret(key: &str) -> Result<(), String> {
+    let mut store = load_store()?;
+    store.secrets.remove(key);
+    save_store(&store)
+}
+
 #[tauri::command]
 pub async fn set_secret(key: String, value: String) -> Result<(), String> {
-    tauri::async_runtime::spawn_blocking(move || {
-        let mut store = load_store()?;
-        store.secrets.insert(key, value);
-        save_store(&store)
-    })
-    .await
-    .map_err(|e| format!("Task failed: {}", e))?
+    tauri::async_runtime::spawn_blocking(move || store_secret(&key, &value))
+        .await
+        .map_err(|e| format!("Task failed: {}", e))?
 }
 
 #[tauri::command]
 pub async fn get_secret(key: String) -> Result<Option<String>, String> {
-    tauri::async_runtime::spawn_blocking(move || {
-        let store = load_store()?;
-        Ok(store.secrets.get(&key).cloned())
-    })
-    .await
-    .map_err(|e| format!("Task failed: {}", e))?
+    tauri::async_runtime::spawn_blocking(move || read_secret(&key))
+        .await
+        .map_err(|e| format!("Task failed: {}", e))?
 }
 
 #[tauri::command]
 pub async fn delete_secret(key: String) -> Result<(), String> {
-    tauri::async_runtime::spawn_blocking(move || {
-        let mut store = load_store()?;
-        store.secrets.remove(&key);
-        save_store(&store)
-    })
-    .await
-    .map_err(|e| format!("Task failed: {}", e))?
+    tauri::async_runtime::spawn_blocking(move || remove_secret(&key))
+        .await
+        .map_err(|e| format!("Task failed: {}", e))?
 }
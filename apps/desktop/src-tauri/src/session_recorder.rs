@@ -0,0 +1,288 @@
+//! Debug session recording bundles
+//!
+//! Records a time window of debugging context — logs, terminal output,
+//! network test results, and the current schema snapshot — into a single
+//! shareable zip, so a teammate can load the bundle into their own panel
+//! and see exactly what the recorder saw.
+
+use once_cell::sync::Lazy;
+use rusqlite::params;
+use serde::Serialize;
+use std::io::{Read, Write};
+use std::sync::Mutex;
+use tauri::{AppHandle, Manager, State};
+
+use crate::log_store::DbConnection;
+
+/// An in-progress recording. Terminal output and other frontend-captured
+/// context accumulate here until the recording stops.
+struct Recording {
+    started_ms: i64,
+    deployment: Option<String>,
+    terminal: String,
+}
+
+static RECORDING: Lazy<Mutex<Option<Recording>>> = Lazy::new(|| Mutex::new(None));
+
+/// What `stop_session_recording` wrote
+#[derive(Debug, Clone, Serialize)]
+pub struct RecordingBundle {
+    pub path: String,
+    pub started_ms: i64,
+    pub ended_ms: i64,
+    pub log_count: usize,
+    pub network_samples: usize,
+}
+
+/// Summary of a loaded bundle
+#[derive(Debug, Clone, Serialize)]
+pub struct LoadedRecording {
+    pub manifest: serde_json::Value,
+    pub logs_ingested: usize,
+    pub terminal: String,
+    pub network: serde_json::Value,
+    pub schema: Option<serde_json::Value>,
+}
+
+/// Begin recording. Only one recording runs at a time; starting again
+/// restarts the window.
+#[tauri::command]
+pub fn start_session_recording(deployment: Option<String>) -> i64 {
+    let started_ms = chrono::Utc::now().timestamp_millis();
+    *RECORDING.lock().unwrap() = Some(Recording {
+        started_ms,
+        deployment: deployment.map(|d| d.trim_end_matches('/').to_string()),
+        terminal: String::new(),
+    });
+    started_ms
+}
+
+/// Append captured terminal output to the active recording. The frontend
+/// feeds PTY output here while a recording is running.
+#[tauri::command]
+pub fn record_session_terminal(data: String) {
+    if let Some(recording) = RECORDING.lock().unwrap().as_mut() {
+        recording.terminal.push_str(&data);
+    }
+}
+
+fn window_logs(
+    conn: &rusqlite::Connection,
+    deployment: Option<&str>,
+    start_ms: i64,
+    end_ms: i64,
+) -> Result<Vec<String>, String> {
+    let mut sql = String::from("SELECT json_blob FROM logs WHERE ts >= ?1 AND ts <= ?2");
+    if deployment.is_some() {
+        sql.push_str(" AND deployment = ?3");
+    }
+    sql.push_str(" ORDER BY ts ASC");
+
+    let mut stmt = conn
+        .prepare(&sql)
+        .map_err(|e| format!("Query error: {}", e))?;
+    let rows = match deployment {
+        Some(deployment) => stmt.query_map(params![start_ms, end_ms, deployment], |row| {
+            row.get::<_, String>(0)
+        }),
+        None => stmt.query_map(params![start_ms, end_ms], |row| row.get::<_, String>(0)),
+    }
+    .map_err(|e| format!("Query error: {}", e))?;
+
+    rows.collect::<Result<_, _>>()
+        .map_err(|e| format!("Query error: {}", e))
+}
+
+fn window_network(
+    conn: &rusqlite::Connection,
+    start_ms: i64,
+    end_ms: i64,
+) -> Result<Vec<serde_json::Value>, String> {
+    let mut stmt = conn
+        .prepare(
+            "SELECT ts, check_name, success, latency_ms FROM network_history
+             WHERE ts >= ?1 AND ts <= ?2 ORDER BY ts ASC",
+        )
+        .map_err(|e| format!("Query error: {}", e))?;
+
+    let rows = stmt
+        .query_map(params![start_ms, end_ms], |row| {
+            Ok(serde_json::json!({
+                "ts": row.get::<_, i64>(0)?,
+                "checkName": row.get::<_, String>(1)?,
+                "success": row.get::<_, i64>(2)? != 0,
+                "latencyMs": row.get::<_, Option<f64>>(3)?,
+            }))
+        })
+        .map_err(|e| format!("Query error: {}", e))?;
+
+    rows.collect::<Result<_, _>>()
+        .map_err(|e| format!("Query error: {}", e))
+}
+
+fn latest_schema(
+    conn: &rusqlite::Connection,
+    deployment: Option<&str>,
+) -> Option<String> {
+    let deployment = deployment?;
+    conn.query_row(
+        "SELECT schema_json FROM schema_snapshots WHERE deployment = ?1
+         ORDER BY ts DESC LIMIT 1",
+        params![deployment],
+        |row| row.get::<_, String>(0),
+    )
+    .ok()
+}
+
+/// Stop the recording and write the bundle zip into the chosen directory
+/// (defaults to ~/.convex-panel/recordings)
+#[tauri::command]
+pub async fn stop_session_recording(
+    app: AppHandle,
+    output_dir: Option<String>,
+) -> Result<RecordingBundle, String> {
+    let recording = RECORDING
+        .lock()
+        .unwrap()
+        .take()
+        .ok_or("No session recording is running")?;
+    let ended_ms = chrono::Utc::now().timestamp_millis();
+
+    let dir = match output_dir {
+        Some(dir) => std::path::PathBuf::from(dir),
+        None => {
+            let home = std::env::var("HOME")
+                .or_else(|_| std::env::var("USERPROFILE"))
+                .map_err(|_| "Failed to get home directory")?;
+            std::path::PathBuf::from(home)
+                .join(".convex-panel")
+                .join("recordings")
+        }
+    };
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create recordings directory: {}", e))?;
+    let path = dir.join(format!("session-{}.zip", recording.started_ms));
+
+    let (logs, network, schema) = {
+        let db = app.state::<DbConnection>();
+        let conn = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+        (
+            window_logs(
+                &conn,
+                recording.deployment.as_deref(),
+                recording.started_ms,
+                ended_ms,
+            )?,
+            window_network(&conn, recording.started_ms, ended_ms)?,
+            latest_schema(&conn, recording.deployment.as_deref()),
+        )
+    };
+
+    let manifest = serde_json::json!({
+        "version": 1,
+        "startedMs": recording.started_ms,
+        "endedMs": ended_ms,
+        "deployment": recording.deployment,
+        "appVersion": app.package_info().version.to_string(),
+        "logCount": logs.len(),
+    });
+
+    let file =
+        std::fs::File::create(&path).map_err(|e| format!("Failed to create bundle: {}", e))?;
+    let mut zip = zip::ZipWriter::new(file);
+    let options = zip::write::SimpleFileOptions::default();
+    let write_entry = |zip: &mut zip::ZipWriter<std::fs::File>, name: &str, content: &str| {
+        zip.start_file(name, options)
+            .and_then(|_| zip.write_all(content.as_bytes()).map_err(Into::into))
+            .map_err(|e| format!("Failed to write bundle: {}", e))
+    };
+
+    write_entry(&mut zip, "manifest.json", &manifest.to_string())?;
+    write_entry(&mut zip, "logs.jsonl", &logs.join("\n"))?;
+    write_entry(&mut zip, "terminal.txt", &recording.terminal)?;
+    write_entry(
+        &mut zip,
+        "network.json",
+        &serde_json::to_string(&network).unwrap_or_else(|_| "[]".to_string()),
+    )?;
+    if let Some(schema) = &schema {
+        write_entry(&mut zip, "schema.json", schema)?;
+    }
+    zip.finish()
+        .map_err(|e| format!("Failed to write bundle: {}", e))?;
+
+    Ok(RecordingBundle {
+        path: path.to_string_lossy().to_string(),
+        started_ms: recording.started_ms,
+        ended_ms,
+        log_count: logs.len(),
+        network_samples: network.len(),
+    })
+}
+
+fn read_zip_entry(
+    archive: &mut zip::ZipArchive<std::fs::File>,
+    name: &str,
+) -> Result<Option<String>, String> {
+    match archive.by_name(name) {
+        Ok(mut entry) => {
+            let mut content = String::new();
+            entry
+                .read_to_string(&mut content)
+                .map_err(|e| format!("Failed to read bundle entry {}: {}", name, e))?;
+            Ok(Some(content))
+        }
+        Err(zip::result::ZipError::FileNotFound) => Ok(None),
+        Err(e) => Err(format!("Failed to read bundle entry {}: {}", name, e)),
+    }
+}
+
+/// Load a recording bundle: its logs are ingested into the local log store
+/// under the bundle's deployment so the regular log views can replay them,
+/// and the rest comes back for display.
+#[tauri::command]
+pub async fn load_session_recording(
+    db: State<'_, DbConnection>,
+    path: String,
+) -> Result<LoadedRecording, String> {
+    let file = std::fs::File::open(&path).map_err(|e| format!("Failed to open bundle: {}", e))?;
+    let mut archive =
+        zip::ZipArchive::new(file).map_err(|e| format!("Failed to open bundle: {}", e))?;
+
+    let manifest: serde_json::Value = read_zip_entry(&mut archive, "manifest.json")?
+        .ok_or("Bundle has no manifest")?
+        .parse::<serde_json::Value>()
+        .map_err(|e| format!("Invalid bundle manifest: {}", e))?;
+    let deployment = manifest
+        .get("deployment")
+        .and_then(|v| v.as_str())
+        .unwrap_or("recording")
+        .to_string();
+
+    let logs = read_zip_entry(&mut archive, "logs.jsonl")?.unwrap_or_default();
+    let entries: Vec<crate::log_store::IngestLogEntry> = logs
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect();
+
+    let logs_ingested = {
+        let conn = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+        crate::log_store::ingest_batch(&conn, entries, &deployment).inserted
+    };
+
+    let terminal = read_zip_entry(&mut archive, "terminal.txt")?.unwrap_or_default();
+    let network = read_zip_entry(&mut archive, "network.json")?
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or(serde_json::json!([]));
+    let schema = read_zip_entry(&mut archive, "schema.json")?
+        .and_then(|content| serde_json::from_str(&content).ok());
+
+    Ok(LoadedRecording {
+        manifest,
+        logs_ingested,
+        terminal,
+        network,
+        schema,
+    })
+}
@@ -0,0 +1,300 @@
+//! Encrypted settings sync between machines
+//!
+//! Opt-in: bundles the shareable configuration files — saved searches,
+//! alert rules, the deployment registry (with secret references stripped),
+//! and workspace profiles — encrypts them with a user passphrase, and
+//! pushes/pulls the bundle to a user-provided location. A plain file path
+//! targets a synced folder (Dropbox, network share); an http(s) URL is
+//! PUT/GET as-is, which covers presigned S3 URLs and gist raw URLs.
+//! Secrets never leave the machine.
+
+use aes_gcm::{
+    aead::{rand_core::RngCore, Aead, KeyInit, OsRng},
+    Aes256Gcm, Nonce,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::PathBuf;
+
+const SYNC_CONFIG_FILE: &str = "sync.json";
+
+/// Files under ~/.convex-panel that sync covers. Everything else —
+/// secrets.enc above all — stays local.
+const SYNCED_FILES: &[&str] = &[
+    "deployments.json",
+    "profiles.json",
+    "saved-searches.json",
+    "alert-rules.json",
+];
+
+/// Iterations for the passphrase KDF
+const KDF_ROUNDS: u32 = 100_000;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SyncConfig {
+    pub enabled: bool,
+    /// File path or http(s) URL the encrypted bundle is pushed to
+    pub location: String,
+}
+
+/// The encrypted bundle as stored at the sync location
+#[derive(Serialize, Deserialize)]
+struct SyncBundle {
+    version: u32,
+    created_ms: i64,
+    salt: String,
+    nonce: String,
+    ciphertext: String,
+}
+
+/// What a push or pull touched
+#[derive(Debug, Clone, Serialize)]
+pub struct SyncReport {
+    pub files: Vec<String>,
+    pub location: String,
+}
+
+fn app_data_dir() -> Result<PathBuf, String> {
+    let home = std::env::var("HOME")
+        .or_else(|_| std::env::var("USERPROFILE"))
+        .map_err(|_| "Failed to get home directory")?;
+
+    let app_data = PathBuf::from(home).join(".convex-panel");
+    std::fs::create_dir_all(&app_data)
+        .map_err(|e| format!("Failed to create app data directory: {}", e))?;
+
+    Ok(app_data)
+}
+
+fn load_config() -> SyncConfig {
+    app_data_dir()
+        .ok()
+        .and_then(|dir| std::fs::read_to_string(dir.join(SYNC_CONFIG_FILE)).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Stretch the passphrase into an AES key with iterated salted SHA-256
+fn derive_key(passphrase: &str, salt: &[u8]) -> [u8; 32] {
+    let mut state = {
+        let mut hasher = Sha256::new();
+        hasher.update(b"convex-panel-sync-v1");
+        hasher.update(salt);
+        hasher.update(passphrase.as_bytes());
+        hasher.finalize()
+    };
+    for _ in 1..KDF_ROUNDS {
+        state = Sha256::digest(state);
+    }
+
+    let mut key = [0u8; 32];
+    key.copy_from_slice(&state);
+    key
+}
+
+/// The deployment registry without its secret references
+fn sanitize_deployments(content: &str) -> String {
+    let Ok(mut entries) = serde_json::from_str::<serde_json::Value>(content) else {
+        return content.to_string();
+    };
+    if let Some(list) = entries.as_array_mut() {
+        for entry in list {
+            if let Some(obj) = entry.as_object_mut() {
+                obj.remove("deploy_key_secret");
+            }
+        }
+    }
+    entries.to_string()
+}
+
+/// Gather the synced files into a name → content map
+fn collect_files() -> Result<HashMap<String, String>, String> {
+    let dir = app_data_dir()?;
+    let mut files = HashMap::new();
+
+    for name in SYNCED_FILES {
+        let Ok(content) = std::fs::read_to_string(dir.join(name)) else {
+            continue;
+        };
+        let content = if *name == "deployments.json" {
+            sanitize_deployments(&content)
+        } else {
+            content
+        };
+        files.insert(name.to_string(), content);
+    }
+
+    Ok(files)
+}
+
+async fn write_bundle(location: &str, bundle: &SyncBundle) -> Result<(), String> {
+    let json = serde_json::to_string(bundle)
+        .map_err(|e| format!("Failed to serialize sync bundle: {}", e))?;
+
+    if location.starts_with("http://") || location.starts_with("https://") {
+        let response = reqwest::Client::new()
+            .put(location)
+            .header("Content-Type", "application/json")
+            .body(json)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to upload sync bundle: {}", e))?;
+        if !response.status().is_success() {
+            return Err(format!(
+                "Sync location rejected the upload: HTTP {}",
+                response.status()
+            ));
+        }
+        return Ok(());
+    }
+
+    std::fs::write(location, json).map_err(|e| format!("Failed to write sync bundle: {}", e))
+}
+
+async fn read_bundle(location: &str) -> Result<SyncBundle, String> {
+    let json = if location.starts_with("http://") || location.starts_with("https://") {
+        let response = reqwest::get(location)
+            .await
+            .map_err(|e| format!("Failed to download sync bundle: {}", e))?;
+        if !response.status().is_success() {
+            return Err(format!(
+                "Sync location returned HTTP {}",
+                response.status()
+            ));
+        }
+        response
+            .text()
+            .await
+            .map_err(|e| format!("Failed to download sync bundle: {}", e))?
+    } else {
+        std::fs::read_to_string(location).map_err(|e| format!("Failed to read sync bundle: {}", e))?
+    };
+
+    serde_json::from_str(&json).map_err(|e| format!("Invalid sync bundle: {}", e))
+}
+
+#[tauri::command]
+pub fn get_sync_config() -> SyncConfig {
+    load_config()
+}
+
+#[tauri::command]
+pub fn set_sync_config(config: SyncConfig) -> Result<(), String> {
+    let path = app_data_dir()?.join(SYNC_CONFIG_FILE);
+    let json = serde_json::to_string_pretty(&config)
+        .map_err(|e| format!("Failed to serialize sync config: {}", e))?;
+    std::fs::write(&path, json).map_err(|e| format!("Failed to write sync config: {}", e))
+}
+
+/// Encrypt the synced settings with the passphrase and push the bundle to
+/// the configured location
+#[tauri::command]
+pub async fn push_settings_sync(passphrase: String) -> Result<SyncReport, String> {
+    let config = load_config();
+    if !config.enabled {
+        return Err("Settings sync is not enabled".to_string());
+    }
+    if passphrase.is_empty() {
+        return Err("A sync passphrase is required".to_string());
+    }
+
+    let files = collect_files()?;
+    let plaintext = serde_json::to_vec(&files)
+        .map_err(|e| format!("Failed to serialize settings: {}", e))?;
+
+    let mut salt = [0u8; 16];
+    OsRng.fill_bytes(&mut salt);
+    let mut nonce = [0u8; 12];
+    OsRng.fill_bytes(&mut nonce);
+
+    let key = derive_key(&passphrase, &salt);
+    let cipher = Aes256Gcm::new(&key.into());
+    let ciphertext = cipher
+        .encrypt(Nonce::from_slice(&nonce), plaintext.as_slice())
+        .map_err(|e| format!("Encryption failed: {}", e))?;
+
+    let bundle = SyncBundle {
+        version: 1,
+        created_ms: chrono::Utc::now().timestamp_millis(),
+        salt: hex::encode(salt),
+        nonce: hex::encode(nonce),
+        ciphertext: hex::encode(ciphertext),
+    };
+    write_bundle(&config.location, &bundle).await?;
+
+    Ok(SyncReport {
+        files: files.into_keys().collect(),
+        location: config.location,
+    })
+}
+
+/// Pull the bundle from the configured location, decrypt it with the
+/// passphrase, and write the contained settings files. Existing local files
+/// are overwritten; secrets and everything outside the synced set are
+/// untouched.
+#[tauri::command]
+pub async fn pull_settings_sync(passphrase: String) -> Result<SyncReport, String> {
+    let config = load_config();
+    if !config.enabled {
+        return Err("Settings sync is not enabled".to_string());
+    }
+
+    let bundle = read_bundle(&config.location).await?;
+    if bundle.version != 1 {
+        return Err(format!("Unsupported sync bundle version {}", bundle.version));
+    }
+
+    let salt = hex::decode(&bundle.salt).map_err(|_| "Invalid sync bundle salt")?;
+    let nonce = hex::decode(&bundle.nonce).map_err(|_| "Invalid sync bundle nonce")?;
+    let ciphertext =
+        hex::decode(&bundle.ciphertext).map_err(|_| "Invalid sync bundle ciphertext")?;
+
+    let key = derive_key(&passphrase, &salt);
+    let cipher = Aes256Gcm::new(&key.into());
+    let plaintext = cipher
+        .decrypt(Nonce::from_slice(&nonce), ciphertext.as_slice())
+        .map_err(|_| "Decryption failed — wrong passphrase?".to_string())?;
+
+    let files: HashMap<String, String> = serde_json::from_slice(&plaintext)
+        .map_err(|e| format!("Invalid sync bundle contents: {}", e))?;
+
+    let dir = app_data_dir()?;
+    let mut written = Vec::new();
+    for (name, content) in &files {
+        // Only the known set may be written, whatever the bundle claims
+        if !SYNCED_FILES.contains(&name.as_str()) {
+            continue;
+        }
+        std::fs::write(dir.join(name), content)
+            .map_err(|e| format!("Failed to write {}: {}", name, e))?;
+        written.push(name.clone());
+    }
+
+    Ok(SyncReport {
+        files: written,
+        location: config.location,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_derive_key_is_deterministic_and_salted() {
+        let a = derive_key("passphrase", b"salt-one********");
+        let b = derive_key("passphrase", b"salt-one********");
+        let c = derive_key("passphrase", b"salt-two********");
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    fn test_sanitize_deployments_strips_secret_refs() {
+        let raw = r#"[{"name":"dev","url":"https://x.convex.cloud","deploy_key_secret":"deploy-key:x"}]"#;
+        let sanitized = sanitize_deployments(raw);
+        assert!(!sanitized.contains("deploy_key_secret"));
+        assert!(sanitized.contains("https://x.convex.cloud"));
+    }
+}
@@ -0,0 +1,198 @@
+//! User-configurable keyboard shortcuts
+//!
+//! The native menus are built from a persisted action -> accelerator map
+//! instead of hardcoded accelerators. Bindings can be changed at runtime
+//! (live menu items are re-accelerated in place) with conflict validation.
+
+use once_cell::sync::Lazy;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::menu::MenuItem;
+
+const SHORTCUTS_FILE: &str = "shortcuts.json";
+
+/// Default bindings for every action that supports a shortcut. An empty
+/// string means "no shortcut assigned".
+const DEFAULT_SHORTCUTS: &[(&str, &str)] = &[
+    ("settings", "CmdOrCtrl+,"),
+    ("check_updates", ""),
+    ("about", ""),
+    ("always_on_top", "CmdOrCtrl+Shift+T"),
+    ("zoom_in", "CmdOrCtrl+="),
+    ("zoom_out", "CmdOrCtrl+-"),
+    ("zoom_reset", "CmdOrCtrl+0"),
+];
+
+/// Live menu items keyed by action, so binding changes apply immediately
+static LIVE_ITEMS: Lazy<Mutex<HashMap<String, MenuItem<tauri::Wry>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+fn shortcuts_path() -> Result<PathBuf, String> {
+    let home = std::env::var("HOME")
+        .or_else(|_| std::env::var("USERPROFILE"))
+        .map_err(|_| "Failed to get home directory")?;
+
+    let app_data = PathBuf::from(home).join(".convex-panel");
+    std::fs::create_dir_all(&app_data)
+        .map_err(|e| format!("Failed to create app data directory: {}", e))?;
+
+    Ok(app_data.join(SHORTCUTS_FILE))
+}
+
+/// Current bindings: defaults overlaid with the user's saved overrides
+pub fn load_shortcuts() -> HashMap<String, String> {
+    let mut shortcuts: HashMap<String, String> = DEFAULT_SHORTCUTS
+        .iter()
+        .map(|(action, accel)| (action.to_string(), accel.to_string()))
+        .collect();
+
+    if let Ok(path) = shortcuts_path() {
+        if let Some(saved) = std::fs::read_to_string(&path)
+            .ok()
+            .and_then(|content| serde_json::from_str::<HashMap<String, String>>(&content).ok())
+        {
+            for (action, accel) in saved {
+                // Ignore saved actions that no longer exist
+                if shortcuts.contains_key(&action) {
+                    shortcuts.insert(action, accel);
+                }
+            }
+        }
+    }
+
+    shortcuts
+}
+
+fn save_shortcuts(shortcuts: &HashMap<String, String>) -> Result<(), String> {
+    let path = shortcuts_path()?;
+    let json = serde_json::to_string_pretty(shortcuts)
+        .map_err(|e| format!("Failed to serialize shortcuts: {}", e))?;
+    std::fs::write(&path, json).map_err(|e| format!("Failed to write shortcuts: {}", e))
+}
+
+/// Accelerator for an action when building its menu item (None if unbound)
+pub fn accelerator_for(action: &str) -> Option<String> {
+    load_shortcuts().remove(action).filter(|a| !a.is_empty())
+}
+
+/// Register a live menu item so `set_keyboard_shortcut` can update it in place
+pub fn register_item(action: &str, item: &MenuItem<tauri::Wry>) {
+    let mut items = LIVE_ITEMS.lock().unwrap();
+    items.insert(action.to_string(), item.clone());
+}
+
+/// Basic sanity check of an accelerator string ("CmdOrCtrl+Shift+K" etc.)
+fn validate_accelerator(accelerator: &str) -> Result<(), String> {
+    let parts: Vec<&str> = accelerator.split('+').collect();
+
+    if parts.iter().any(|p| p.is_empty()) {
+        return Err(format!("Malformed accelerator: {}", accelerator));
+    }
+
+    let (modifiers, key) = parts.split_at(parts.len() - 1);
+    for modifier in modifiers {
+        match *modifier {
+            "Cmd" | "Ctrl" | "CmdOrCtrl" | "Alt" | "Option" | "Shift" | "Super" | "Meta" => {}
+            other => return Err(format!("Unknown modifier: {}", other)),
+        }
+    }
+
+    if key[0].is_empty() {
+        return Err(format!("Accelerator is missing a key: {}", accelerator));
+    }
+
+    Ok(())
+}
+
+/// Current action -> accelerator map for the settings UI
+#[tauri::command]
+pub fn get_keyboard_shortcuts() -> HashMap<String, String> {
+    load_shortcuts()
+}
+
+/// Rebind an action. Pass an empty accelerator to unbind. Fails on unknown
+/// actions, malformed accelerators, and conflicts with other actions.
+#[tauri::command]
+pub fn set_keyboard_shortcut(action: String, accelerator: String) -> Result<(), String> {
+    let mut shortcuts = load_shortcuts();
+
+    if !shortcuts.contains_key(&action) {
+        return Err(format!("Unknown shortcut action: {}", action));
+    }
+
+    if !accelerator.is_empty() {
+        validate_accelerator(&accelerator)?;
+
+        // Conflict validation against every other action
+        if let Some((taken_by, _)) = shortcuts
+            .iter()
+            .find(|(a, accel)| **a != action && **accel == accelerator)
+        {
+            return Err(format!(
+                "Shortcut {} is already bound to '{}'",
+                accelerator, taken_by
+            ));
+        }
+    }
+
+    shortcuts.insert(action.clone(), accelerator.clone());
+    save_shortcuts(&shortcuts)?;
+
+    // Apply to the live menu item if it has been registered
+    if let Some(item) = LIVE_ITEMS.lock().unwrap().get(&action) {
+        let accel = if accelerator.is_empty() {
+            None
+        } else {
+            Some(accelerator.as_str())
+        };
+        item.set_accelerator(accel)
+            .map_err(|e| format!("Failed to update menu accelerator: {}", e))?;
+    }
+
+    Ok(())
+}
+
+/// Restore all bindings to their defaults
+#[tauri::command]
+pub fn reset_keyboard_shortcuts() -> Result<HashMap<String, String>, String> {
+    let defaults: HashMap<String, String> = DEFAULT_SHORTCUTS
+        .iter()
+        .map(|(action, accel)| (action.to_string(), accel.to_string()))
+        .collect();
+
+    save_shortcuts(&defaults)?;
+
+    let items = LIVE_ITEMS.lock().unwrap();
+    for (action, accel) in &defaults {
+        if let Some(item) = items.get(action) {
+            let accel = if accel.is_empty() {
+                None
+            } else {
+                Some(accel.as_str())
+            };
+            let _ = item.set_accelerator(accel);
+        }
+    }
+
+    Ok(defaults)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_accelerator() {
+        assert!(validate_accelerator("CmdOrCtrl+,").is_ok());
+        assert!(validate_accelerator("CmdOrCtrl+Shift+K").is_ok());
+        assert!(validate_accelerator("K").is_ok());
+    }
+
+    #[test]
+    fn test_validate_accelerator_rejects_malformed() {
+        assert!(validate_accelerator("CmdOrCtrl+").is_err());
+        assert!(validate_accelerator("Bogus+K").is_err());
+        assert!(validate_accelerator("+K").is_err());
+    }
+}
@@ -0,0 +1,198 @@
+//! Local convex/ source indexer
+//!
+//! A lightweight parse of the project's `convex/` TypeScript files mapping
+//! exported function names to file and line, so a log entry's function path
+//! can jump straight to its definition via `open_in_editor`. The directory
+//! watcher keeps the index fresh as files change.
+
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Mutex;
+
+/// One exported function found in the source
+#[derive(Debug, Clone, Serialize)]
+pub struct IndexedFunction {
+    /// Convex-style path, e.g. "messages:send" or "lib/util:helper"
+    pub function_path: String,
+    pub export_name: String,
+    pub file: String,
+    /// 1-based line of the export
+    pub line: u32,
+}
+
+/// Indexed functions per project root
+static INDEXES: Lazy<Mutex<HashMap<String, Vec<IndexedFunction>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+fn is_source_file(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|e| e.to_str()),
+        Some("ts") | Some("tsx") | Some("js") | Some("jsx")
+    )
+}
+
+/// Module part of the function path: the file's path relative to convex/
+/// without its extension
+fn module_path(project_root: &str, file: &Path) -> Option<String> {
+    let convex_dir = Path::new(project_root).join("convex");
+    let relative = file.strip_prefix(&convex_dir).ok()?;
+    let without_ext = relative.with_extension("");
+    Some(without_ext.to_string_lossy().replace('\\', "/"))
+}
+
+/// Export name on a line, if it declares one. Good enough for the common
+/// declaration shapes; a full parser isn't worth the dependency.
+fn export_name(line: &str) -> Option<String> {
+    let trimmed = line.trim_start();
+    let rest = trimmed.strip_prefix("export ")?;
+
+    if rest.starts_with("default ") || rest == "default" {
+        return Some("default".to_string());
+    }
+
+    let rest = rest
+        .strip_prefix("const ")
+        .or_else(|| rest.strip_prefix("let "))
+        .or_else(|| rest.strip_prefix("var "))
+        .or_else(|| rest.strip_prefix("async function "))
+        .or_else(|| rest.strip_prefix("function "))?;
+
+    let name: String = rest
+        .chars()
+        .take_while(|c| c.is_alphanumeric() || *c == '_' || *c == '$')
+        .collect();
+    (!name.is_empty()).then_some(name)
+}
+
+/// Index the exports of one file
+fn index_file(project_root: &str, file: &Path) -> Vec<IndexedFunction> {
+    let Some(module) = module_path(project_root, file) else {
+        return Vec::new();
+    };
+    let Ok(content) = std::fs::read_to_string(file) else {
+        return Vec::new();
+    };
+
+    content
+        .lines()
+        .enumerate()
+        .filter_map(|(i, line)| {
+            export_name(line).map(|name| IndexedFunction {
+                function_path: format!("{}:{}", module, name),
+                export_name: name,
+                file: file.display().to_string(),
+                line: (i + 1) as u32,
+            })
+        })
+        .collect()
+}
+
+/// Re-index one file after a watcher event. Deleted files drop out of the
+/// index; untracked projects are ignored.
+pub fn refresh_file(project_root: &str, path: &str, deleted: bool) {
+    let mut indexes = INDEXES.lock().unwrap();
+    let Some(functions) = indexes.get_mut(project_root) else {
+        return;
+    };
+
+    functions.retain(|f| f.file != path);
+    if !deleted {
+        let file = Path::new(path);
+        if is_source_file(file) {
+            functions.extend(index_file(project_root, file));
+        }
+    }
+}
+
+/// Build (or rebuild) the source index for a project's convex/ directory
+#[tauri::command]
+pub fn build_source_index(project_path: String) -> Result<usize, String> {
+    let convex_dir = Path::new(&project_path).join("convex");
+    if !convex_dir.exists() {
+        return Err(format!("No convex/ directory in {}", project_path));
+    }
+
+    let mut functions = Vec::new();
+    let walker = ignore::WalkBuilder::new(&convex_dir)
+        .filter_entry(|entry| {
+            entry
+                .file_name()
+                .to_str()
+                .map(|name| name != "_generated" && name != "node_modules")
+                .unwrap_or(true)
+        })
+        .build();
+
+    for entry in walker.flatten() {
+        let path = entry.path();
+        if path.is_file() && is_source_file(path) {
+            functions.extend(index_file(&project_path, path));
+        }
+    }
+
+    let count = functions.len();
+    INDEXES.lock().unwrap().insert(project_path, functions);
+    Ok(count)
+}
+
+/// The indexed functions of a project, for browsing
+#[tauri::command]
+pub fn list_indexed_functions(project_path: String) -> Vec<IndexedFunction> {
+    INDEXES
+        .lock()
+        .unwrap()
+        .get(&project_path)
+        .cloned()
+        .unwrap_or_default()
+}
+
+/// Resolve a log entry's function path (e.g. "messages.js:send" or
+/// "messages:send") to its source location for `open_in_editor`
+#[tauri::command]
+pub fn resolve_function_source(
+    project_path: String,
+    function_path: String,
+) -> Option<IndexedFunction> {
+    // Runtime paths carry the bundled .js extension; the index doesn't
+    let normalized = match function_path.split_once(':') {
+        Some((module, export)) => format!(
+            "{}:{}",
+            module.trim_end_matches(".js").trim_end_matches(".ts"),
+            export
+        ),
+        None => format!("{}:default", function_path.trim_end_matches(".js")),
+    };
+
+    INDEXES
+        .lock()
+        .unwrap()
+        .get(&project_path)?
+        .iter()
+        .find(|f| f.function_path == normalized)
+        .cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_export_name() {
+        assert_eq!(
+            export_name("export const send = mutation({"),
+            Some("send".to_string())
+        );
+        assert_eq!(
+            export_name("export default query({"),
+            Some("default".to_string())
+        );
+        assert_eq!(
+            export_name("export async function helper() {"),
+            Some("helper".to_string())
+        );
+        assert_eq!(export_name("const internal = 1;"), None);
+        assert_eq!(export_name("export type Foo = string;"), None);
+    }
+}
@@ -0,0 +1,267 @@
+//! Stack-trace to source resolution
+//!
+//! Parses error stack traces out of ingested log entries and resolves
+//! bundled frames back to the local `convex/` sources — through a source
+//! map when one sits next to the bundled file, otherwise by matching the
+//! path on disk — so a trace line can jump to the editor.
+
+use rusqlite::params;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use tauri::State;
+
+use crate::log_store::DbConnection;
+
+/// One frame of a resolved stack
+#[derive(Debug, Clone, Serialize)]
+pub struct ResolvedFrame {
+    pub symbol: String,
+    /// The frame as it appeared in the trace
+    pub raw_file: String,
+    pub raw_line: u32,
+    /// Local file/line when resolution succeeded
+    pub file: Option<String>,
+    pub line: Option<u32>,
+    pub resolved: bool,
+}
+
+/// A frame as parsed from the trace text
+#[derive(Debug, Clone, PartialEq)]
+struct RawFrame {
+    symbol: String,
+    file: String,
+    line: u32,
+    column: u32,
+}
+
+/// Parse V8-style stack frames: "at symbol (file:line:col)" and
+/// "at file:line:col"
+fn parse_frames(text: &str) -> Vec<RawFrame> {
+    let mut frames = Vec::new();
+
+    for line in text.lines() {
+        let trimmed = line.trim_start();
+        let Some(rest) = trimmed.strip_prefix("at ") else {
+            continue;
+        };
+
+        let (symbol, location) = match (rest.find('('), rest.rfind(')')) {
+            (Some(open), Some(close)) if open < close => (
+                rest[..open].trim().to_string(),
+                &rest[open + 1..close],
+            ),
+            _ => (String::new(), rest.trim()),
+        };
+
+        // Split "file:line:col" from the right; the file part may contain ':'
+        let mut parts = location.rsplitn(3, ':');
+        let (Some(col), Some(ln), Some(file)) = (parts.next(), parts.next(), parts.next()) else {
+            continue;
+        };
+        let (Ok(column), Ok(line)) = (col.parse::<u32>(), ln.parse::<u32>()) else {
+            continue;
+        };
+
+        frames.push(RawFrame {
+            symbol,
+            file: file.to_string(),
+            line,
+            column,
+        });
+    }
+
+    frames
+}
+
+/// Decode one base64 VLQ value, advancing the iterator
+fn decode_vlq(chars: &mut std::iter::Peekable<std::str::Chars>) -> Option<i64> {
+    const BASE64: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut result: i64 = 0;
+    let mut shift = 0;
+    loop {
+        let digit = BASE64.find(chars.next()?)? as i64;
+        result |= (digit & 0x1f) << shift;
+        if digit & 0x20 == 0 {
+            break;
+        }
+        shift += 5;
+    }
+
+    let negative = result & 1 == 1;
+    let value = result >> 1;
+    Some(if negative { -value } else { value })
+}
+
+/// Look up a generated line/column in a source map's mappings, returning
+/// (source index, original line, original column), all 0-based
+fn lookup_mapping(mappings: &str, gen_line: u32, gen_col: u32) -> Option<(usize, u32, u32)> {
+    let mut source: i64 = 0;
+    let mut orig_line: i64 = 0;
+    let mut orig_col: i64 = 0;
+    let mut best: Option<(usize, u32, u32)> = None;
+
+    for (line_index, line) in mappings.split(';').enumerate() {
+        if line_index as u32 > gen_line {
+            break;
+        }
+        let mut col: i64 = 0;
+
+        for segment in line.split(',') {
+            if segment.is_empty() {
+                continue;
+            }
+            let mut chars = segment.chars().peekable();
+            col += decode_vlq(&mut chars)?;
+            if chars.peek().is_some() {
+                source += decode_vlq(&mut chars)?;
+                orig_line += decode_vlq(&mut chars)?;
+                orig_col += decode_vlq(&mut chars)?;
+            } else {
+                continue;
+            }
+
+            // The last segment at or before the target position wins
+            if line_index as u32 == gen_line && col as u32 > gen_col {
+                break;
+            }
+            if line_index as u32 == gen_line {
+                best = Some((source as usize, orig_line as u32, orig_col as u32));
+            }
+        }
+    }
+
+    best
+}
+
+/// Resolve a bundled frame through the `.map` file next to it, if any
+fn resolve_via_source_map(bundled: &Path, line: u32, column: u32) -> Option<(String, u32)> {
+    let map_path = PathBuf::from(format!("{}.map", bundled.display()));
+    let map: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(map_path).ok()?).ok()?;
+
+    let mappings = map.get("mappings")?.as_str()?;
+    // Trace lines/columns are 1-based, mappings are 0-based
+    let (source_index, orig_line, _) =
+        lookup_mapping(mappings, line.saturating_sub(1), column.saturating_sub(1))?;
+
+    let source = map.get("sources")?.as_array()?.get(source_index)?.as_str()?;
+    let resolved = bundled.parent()?.join(source);
+    Some((
+        resolved
+            .canonicalize()
+            .unwrap_or(resolved)
+            .display()
+            .to_string(),
+        orig_line + 1,
+    ))
+}
+
+/// Map a trace frame path onto the project's convex/ sources without a
+/// source map: match by file stem, preferring the TypeScript original
+fn resolve_by_path(project_root: &str, file: &str) -> Option<String> {
+    let stem = Path::new(file).file_stem()?.to_str()?;
+    let convex_dir = Path::new(project_root).join("convex");
+
+    for ext in ["ts", "tsx", "js", "jsx"] {
+        let candidate = convex_dir.join(format!("{}.{}", stem, ext));
+        if candidate.exists() {
+            return Some(candidate.display().to_string());
+        }
+    }
+    None
+}
+
+fn resolve_frame(project_root: &str, frame: &RawFrame) -> ResolvedFrame {
+    let bundled = Path::new(&frame.file);
+
+    if let Some((file, line)) = bundled
+        .exists()
+        .then(|| resolve_via_source_map(bundled, frame.line, frame.column))
+        .flatten()
+    {
+        return ResolvedFrame {
+            symbol: frame.symbol.clone(),
+            raw_file: frame.file.clone(),
+            raw_line: frame.line,
+            file: Some(file),
+            line: Some(line),
+            resolved: true,
+        };
+    }
+
+    let file = resolve_by_path(project_root, &frame.file);
+    ResolvedFrame {
+        symbol: frame.symbol.clone(),
+        raw_file: frame.file.clone(),
+        raw_line: frame.line,
+        resolved: file.is_some(),
+        // Without a source map the bundled line is the best guess
+        line: file.is_some().then_some(frame.line),
+        file,
+    }
+}
+
+/// Resolve the stack trace of a log entry to local source locations. Looks
+/// for trace text in the message and the usual error fields of the raw
+/// payload.
+#[tauri::command]
+pub async fn resolve_stack(
+    db: State<'_, DbConnection>,
+    log_id: String,
+    project_path: String,
+) -> Result<Vec<ResolvedFrame>, String> {
+    let (message, blob) = {
+        let conn = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+        conn.query_row(
+            "SELECT message, json_blob FROM logs WHERE id = ?1",
+            params![log_id],
+            |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)),
+        )
+        .map_err(|_| format!("Log {} not found", log_id))?
+    };
+
+    let blob: serde_json::Value = serde_json::from_str(&blob).unwrap_or_default();
+    let mut text = message;
+    for field in ["error", "errorMessage", "stack"] {
+        if let Some(extra) = blob.get(field).and_then(|v| v.as_str()) {
+            text.push('\n');
+            text.push_str(extra);
+        }
+    }
+
+    let frames = parse_frames(&text);
+    if frames.is_empty() {
+        return Err("No stack frames found in log entry".to_string());
+    }
+
+    Ok(frames
+        .iter()
+        .map(|frame| resolve_frame(&project_path, frame))
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_frames() {
+        let trace = "Uncaught Error: boom\n    at send (../convex/messages.js:12:5)\n    at ../convex/http.js:3:1";
+        let frames = parse_frames(trace);
+        assert_eq!(frames.len(), 2);
+        assert_eq!(frames[0].symbol, "send");
+        assert_eq!(frames[0].file, "../convex/messages.js");
+        assert_eq!(frames[0].line, 12);
+        assert_eq!(frames[1].symbol, "");
+        assert_eq!(frames[1].line, 3);
+    }
+
+    #[test]
+    fn test_decode_vlq() {
+        // "A" is 0, "C" is 1, "D" is -1
+        assert_eq!(decode_vlq(&mut "A".chars().peekable()), Some(0));
+        assert_eq!(decode_vlq(&mut "C".chars().peekable()), Some(1));
+        assert_eq!(decode_vlq(&mut "D".chars().peekable()), Some(-1));
+    }
+}
@@ -0,0 +1,341 @@
+//! Data import and seeding
+//!
+//! The counterpart to table snapshots: loads JSONL or CSV files into a
+//! deployment table through the system UDFs the dashboard already uses
+//! (`addDocument`, `replaceDocument`, `clearTablePage`), with batching,
+//! progress events, validation against the cached schema, and a dry-run
+//! mode.
+
+use serde::Serialize;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::convex_client::ConvexClient;
+use crate::log_store::DbConnection;
+
+/// System UDF batch limit is 4096; stay well under it
+const IMPORT_BATCH_SIZE: usize = 500;
+
+/// Report from `import_table_data`; on a dry run nothing was written
+#[derive(Debug, Clone, Serialize)]
+pub struct ImportReport {
+    pub table: String,
+    pub mode: String,
+    pub rows: usize,
+    pub inserted: usize,
+    pub replaced: usize,
+    pub dry_run: bool,
+    /// Fields in the data that the cached schema doesn't know about
+    pub unknown_fields: Vec<String>,
+    /// True when the cached schema has no entry for the table at all
+    pub new_table: bool,
+}
+
+/// Split one CSV record, honoring double-quoted fields with "" escapes
+fn split_csv_record(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if in_quotes => {
+                if chars.peek() == Some(&'"') {
+                    chars.next();
+                    current.push('"');
+                } else {
+                    in_quotes = false;
+                }
+            }
+            '"' if current.is_empty() => in_quotes = true,
+            ',' if !in_quotes => {
+                fields.push(std::mem::take(&mut current));
+            }
+            c => current.push(c),
+        }
+    }
+    fields.push(current);
+    fields
+}
+
+/// CSV cell to a JSON value: numbers, booleans, and null keep their type,
+/// everything else stays a string
+fn csv_value(cell: &str) -> serde_json::Value {
+    match cell {
+        "" | "null" => serde_json::Value::Null,
+        "true" => serde_json::Value::Bool(true),
+        "false" => serde_json::Value::Bool(false),
+        _ => match cell.parse::<i64>() {
+            Ok(n) => serde_json::json!(n),
+            Err(_) => match cell.parse::<f64>() {
+                Ok(f) => serde_json::json!(f),
+                Err(_) => serde_json::Value::String(cell.to_string()),
+            },
+        },
+    }
+}
+
+/// Parse an import file into documents. JSONL is one JSON object per line;
+/// CSV uses the first record as the header.
+fn parse_import_file(path: &str) -> Result<Vec<serde_json::Value>, String> {
+    let content =
+        std::fs::read_to_string(path).map_err(|e| format!("Failed to read file: {}", e))?;
+
+    let is_csv = std::path::Path::new(path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .map(|ext| ext.eq_ignore_ascii_case("csv"))
+        .unwrap_or(false);
+
+    if !is_csv {
+        return content
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(i, line)| {
+                serde_json::from_str(line)
+                    .map_err(|e| format!("Invalid JSON on line {}: {}", i + 1, e))
+            })
+            .collect();
+    }
+
+    let mut lines = content.lines().filter(|line| !line.trim().is_empty());
+    let header = match lines.next() {
+        Some(line) => split_csv_record(line),
+        None => return Ok(Vec::new()),
+    };
+
+    lines
+        .enumerate()
+        .map(|(i, line)| {
+            let cells = split_csv_record(line);
+            if cells.len() != header.len() {
+                return Err(format!(
+                    "Line {} has {} fields, expected {}",
+                    i + 2,
+                    cells.len(),
+                    header.len()
+                ));
+            }
+            Ok(serde_json::Value::Object(
+                header
+                    .iter()
+                    .cloned()
+                    .zip(cells.iter().map(|cell| csv_value(cell)))
+                    .collect(),
+            ))
+        })
+        .collect()
+}
+
+/// Compare document fields against the latest cached schema snapshot
+fn validate_against_schema(
+    app: &AppHandle,
+    deployment_url: &str,
+    table: &str,
+    documents: &[serde_json::Value],
+) -> (Vec<String>, bool) {
+    let db = app.state::<DbConnection>();
+    let schema: Option<serde_json::Value> = db
+        .lock()
+        .ok()
+        .and_then(|conn| {
+            conn.query_row(
+                "SELECT schema_json FROM schema_snapshots WHERE deployment = ?1
+                 ORDER BY ts DESC LIMIT 1",
+                rusqlite::params![deployment_url],
+                |row| row.get::<_, String>(0),
+            )
+            .ok()
+        })
+        .and_then(|json| serde_json::from_str(&json).ok());
+
+    let schema = match schema {
+        Some(schema) => schema,
+        // Without a snapshot there's nothing to validate against
+        None => return (Vec::new(), false),
+    };
+
+    let table_entry = match schema.get(table) {
+        Some(entry) => entry,
+        None => return (Vec::new(), true),
+    };
+
+    let known: Vec<String> = match table_entry.get("fields").unwrap_or(table_entry) {
+        serde_json::Value::Object(obj) => obj.keys().cloned().collect(),
+        serde_json::Value::Array(list) => list
+            .iter()
+            .filter_map(|f| {
+                f.get("fieldName")
+                    .or_else(|| f.get("name"))
+                    .and_then(|v| v.as_str())
+                    .map(String::from)
+            })
+            .collect(),
+        _ => return (Vec::new(), false),
+    };
+
+    let mut unknown = Vec::new();
+    for document in documents {
+        if let Some(obj) = document.as_object() {
+            for key in obj.keys() {
+                if !key.starts_with('_') && !known.contains(key) && !unknown.contains(key) {
+                    unknown.push(key.clone());
+                }
+            }
+        }
+    }
+    unknown.sort();
+    (unknown, false)
+}
+
+fn emit_progress(app: &AppHandle, table: &str, processed: usize, total: usize) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.emit(
+            "import-progress",
+            serde_json::json!({ "table": table, "processed": processed, "total": total }),
+        );
+    }
+}
+
+/// Delete every row in a table, page by page
+pub(crate) async fn clear_table(client: &ConvexClient, table: &str) -> Result<(), String> {
+    let mut cursor = serde_json::Value::Null;
+    loop {
+        let result = client
+            .run_function(
+                "mutation",
+                "_system/frontend/clearTablePage:default",
+                serde_json::json!({ "tableName": table, "cursor": cursor }),
+            )
+            .await?;
+
+        if !result.success {
+            return Err(result
+                .error_message
+                .unwrap_or_else(|| "Failed to clear table".to_string()));
+        }
+
+        let value = result.value.unwrap_or_default();
+        if !value.get("hasMore").and_then(|v| v.as_bool()).unwrap_or(false) {
+            return Ok(());
+        }
+        cursor = value.get("continueCursor").cloned().unwrap_or(serde_json::Value::Null);
+    }
+}
+
+/// Import documents from a JSONL/CSV file into a table. `mode` is "insert",
+/// "upsert" (documents with an `_id` replace the existing row), or "replace"
+/// (the table is cleared first). With `dry_run` only parsing and schema
+/// validation run.
+#[tauri::command]
+pub async fn import_table_data(
+    app: AppHandle,
+    deployment_url: String,
+    table: String,
+    path: String,
+    mode: String,
+    dry_run: Option<bool>,
+    admin_key: Option<String>,
+) -> Result<ImportReport, String> {
+    if !matches!(mode.as_str(), "insert" | "upsert" | "replace") {
+        return Err(format!("Unknown import mode: {}", mode));
+    }
+
+    let deployment_url = deployment_url.trim_end_matches('/').to_string();
+    let documents = parse_import_file(&path)?;
+    let (unknown_fields, new_table) =
+        validate_against_schema(&app, &deployment_url, &table, &documents);
+
+    let mut report = ImportReport {
+        table: table.clone(),
+        mode: mode.clone(),
+        rows: documents.len(),
+        inserted: 0,
+        replaced: 0,
+        dry_run: dry_run.unwrap_or(false),
+        unknown_fields,
+        new_table,
+    };
+
+    if report.dry_run || documents.is_empty() {
+        return Ok(report);
+    }
+
+    let client = ConvexClient::for_deployment(&deployment_url, admin_key)?;
+
+    if mode == "replace" {
+        clear_table(&client, &table).await?;
+    }
+
+    // In upsert mode documents carrying an _id replace their existing row
+    let (to_replace, to_insert): (Vec<_>, Vec<_>) = documents
+        .into_iter()
+        .partition(|d| mode == "upsert" && d.get("_id").is_some());
+
+    let total = to_replace.len() + to_insert.len();
+    let mut processed = 0;
+
+    for document in to_replace {
+        let id = document.get("_id").cloned().unwrap_or_default();
+        let result = client
+            .run_function(
+                "mutation",
+                "_system/frontend/replaceDocument:default",
+                serde_json::json!({ "id": id, "document": document }),
+            )
+            .await?;
+        if !result.success {
+            return Err(result
+                .error_message
+                .unwrap_or_else(|| "Failed to replace document".to_string()));
+        }
+        report.replaced += 1;
+        processed += 1;
+        emit_progress(&app, &table, processed, total);
+    }
+
+    for batch in to_insert.chunks(IMPORT_BATCH_SIZE) {
+        let result = client
+            .run_function(
+                "mutation",
+                "_system/frontend/addDocument:default",
+                serde_json::json!({ "table": table, "documents": batch }),
+            )
+            .await?;
+        if !result.success {
+            return Err(result
+                .error_message
+                .unwrap_or_else(|| "Failed to insert documents".to_string()));
+        }
+        report.inserted += batch.len();
+        processed += batch.len();
+        emit_progress(&app, &table, processed, total);
+    }
+
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_csv_record() {
+        assert_eq!(split_csv_record("a,b,c"), vec!["a", "b", "c"]);
+        assert_eq!(
+            split_csv_record(r#"plain,"has, comma","quoted ""q"""#),
+            vec!["plain", "has, comma", "quoted \"q\""]
+        );
+        assert_eq!(split_csv_record("trailing,"), vec!["trailing", ""]);
+    }
+
+    #[test]
+    fn test_csv_value_types() {
+        assert_eq!(csv_value("42"), serde_json::json!(42));
+        assert_eq!(csv_value("1.5"), serde_json::json!(1.5));
+        assert_eq!(csv_value("true"), serde_json::json!(true));
+        assert_eq!(csv_value(""), serde_json::Value::Null);
+        assert_eq!(csv_value("hello"), serde_json::json!("hello"));
+    }
+}
@@ -0,0 +1,419 @@
+//! Table backup/snapshot subsystem
+//!
+//! Exports Convex tables (paginated through the deployment API) to
+//! timestamped JSONL files under ~/.convex-panel/table-snapshots, with a
+//! catalog and integrity checksums, so the state of a dev deployment can be
+//! captured before risky changes.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::io::Write;
+use std::path::PathBuf;
+use tauri::{AppHandle, Emitter, Manager};
+
+use crate::convex_client::ConvexClient;
+
+const CATALOG_FILE: &str = "catalog.json";
+const PAGE_SIZE: usize = 500;
+
+/// One snapshot file in the catalog
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotEntry {
+    pub id: String,
+    pub deployment: String,
+    pub table: String,
+    pub ts: i64,
+    pub path: String,
+    pub rows: u64,
+    /// Hex SHA-256 of the snapshot file
+    pub checksum: String,
+}
+
+fn snapshots_dir() -> Result<PathBuf, String> {
+    let home = std::env::var("HOME")
+        .or_else(|_| std::env::var("USERPROFILE"))
+        .map_err(|_| "Failed to get home directory")?;
+
+    let dir = PathBuf::from(home)
+        .join(".convex-panel")
+        .join("table-snapshots");
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create snapshots directory: {}", e))?;
+
+    Ok(dir)
+}
+
+fn load_catalog() -> Vec<SnapshotEntry> {
+    snapshots_dir()
+        .ok()
+        .and_then(|dir| std::fs::read_to_string(dir.join(CATALOG_FILE)).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_catalog(entries: &[SnapshotEntry]) -> Result<(), String> {
+    let path = snapshots_dir()?.join(CATALOG_FILE);
+    let json = serde_json::to_string_pretty(entries)
+        .map_err(|e| format!("Failed to serialize catalog: {}", e))?;
+    std::fs::write(&path, json).map_err(|e| format!("Failed to write catalog: {}", e))
+}
+
+fn file_checksum(path: &std::path::Path) -> Result<String, String> {
+    let bytes = std::fs::read(path).map_err(|e| format!("Failed to read snapshot: {}", e))?;
+    Ok(hex::encode(Sha256::digest(&bytes)))
+}
+
+/// Short, filesystem-safe label for a deployment URL
+fn deployment_label(deployment_url: &str) -> String {
+    deployment_url
+        .trim_end_matches('/')
+        .trim_start_matches("https://")
+        .trim_start_matches("http://")
+        .replace(['/', ':', '.'], "-")
+}
+
+fn emit_progress(app: &AppHandle, table: &str, rows: u64, done: bool) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.emit(
+            "snapshot-progress",
+            serde_json::json!({ "table": table, "rows": rows, "done": done }),
+        );
+    }
+}
+
+/// Export one table to a JSONL snapshot file, paginating through the API.
+/// Tables exported in the same run share `ts`, which groups them into one
+/// restorable snapshot set.
+async fn export_table(
+    app: &AppHandle,
+    client: &ConvexClient,
+    deployment_url: &str,
+    table: &str,
+    ts: i64,
+) -> Result<SnapshotEntry, String> {
+    let path = snapshots_dir()?.join(format!(
+        "{}-{}-{}.jsonl",
+        deployment_label(deployment_url),
+        table,
+        ts
+    ));
+
+    let mut file = std::fs::File::create(&path)
+        .map_err(|e| format!("Failed to create snapshot file: {}", e))?;
+
+    let mut rows: u64 = 0;
+    let mut cursor: Option<String> = None;
+
+    loop {
+        let page = client
+            .list_documents(table, cursor.as_deref(), PAGE_SIZE)
+            .await?;
+
+        for document in &page.documents {
+            let line = serde_json::to_string(document)
+                .map_err(|e| format!("Failed to serialize document: {}", e))?;
+            writeln!(file, "{}", line).map_err(|e| format!("Failed to write snapshot: {}", e))?;
+            rows += 1;
+        }
+
+        emit_progress(app, table, rows, false);
+
+        if !page.has_more || page.documents.is_empty() {
+            break;
+        }
+        cursor = page.cursor;
+    }
+
+    file.flush().map_err(|e| format!("Failed to write snapshot: {}", e))?;
+    drop(file);
+
+    emit_progress(app, table, rows, true);
+
+    Ok(SnapshotEntry {
+        id: format!("{}-{}", table, ts),
+        deployment: deployment_url.trim_end_matches('/').to_string(),
+        table: table.to_string(),
+        ts,
+        path: path.to_string_lossy().to_string(),
+        rows,
+        checksum: file_checksum(&path)?,
+    })
+}
+
+/// Snapshot one table, or every table when `tables` is unset. Progress is
+/// emitted to the main window as "snapshot-progress".
+#[tauri::command]
+pub async fn export_table_snapshot(
+    app: AppHandle,
+    deployment_url: String,
+    tables: Option<Vec<String>>,
+    admin_key: Option<String>,
+) -> Result<Vec<SnapshotEntry>, String> {
+    let client = ConvexClient::for_deployment(&deployment_url, admin_key)?;
+
+    let tables = match tables {
+        Some(tables) if !tables.is_empty() => tables,
+        _ => client.list_tables().await?,
+    };
+
+    let ts = chrono::Utc::now().timestamp_millis();
+    let mut entries = Vec::with_capacity(tables.len());
+    for table in &tables {
+        entries.push(export_table(&app, &client, &deployment_url, table, ts).await?);
+    }
+
+    let mut catalog = load_catalog();
+    catalog.extend(entries.iter().cloned());
+    save_catalog(&catalog)?;
+
+    Ok(entries)
+}
+
+/// The snapshot catalog, newest first
+#[tauri::command]
+pub fn list_table_snapshots(deployment: Option<String>) -> Vec<SnapshotEntry> {
+    let mut catalog = load_catalog();
+    if let Some(deployment) = deployment {
+        let deployment = deployment.trim_end_matches('/').to_string();
+        catalog.retain(|entry| entry.deployment == deployment);
+    }
+    catalog.sort_by(|a, b| b.ts.cmp(&a.ts));
+    catalog
+}
+
+/// Recompute a snapshot file's checksum and compare with the catalog
+#[tauri::command]
+pub fn verify_table_snapshot(snapshot_id: String) -> Result<bool, String> {
+    let catalog = load_catalog();
+    let entry = catalog
+        .iter()
+        .find(|entry| entry.id == snapshot_id)
+        .ok_or_else(|| format!("Snapshot {} not found", snapshot_id))?;
+
+    Ok(file_checksum(std::path::Path::new(&entry.path))? == entry.checksum)
+}
+
+/// Outcome of restoring one table
+#[derive(Debug, Clone, Serialize)]
+pub struct RestoreReport {
+    pub table: String,
+    pub rows: u64,
+    pub inserted: u64,
+    pub replaced: u64,
+}
+
+/// Result of `restore_snapshot`: what was written plus the automatic
+/// pre-restore backup taken for rollback
+#[derive(Debug, Clone, Serialize)]
+pub struct RestoreResult {
+    pub reports: Vec<RestoreReport>,
+    pub backup: Vec<SnapshotEntry>,
+}
+
+fn emit_restore_progress(app: &AppHandle, table: &str, processed: u64, total: u64, phase: &str) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.emit(
+            "restore-progress",
+            serde_json::json!({
+                "table": table,
+                "processed": processed,
+                "total": total,
+                "phase": phase,
+            }),
+        );
+    }
+}
+
+/// Push one snapshot file back into a table. "replace" clears the table and
+/// re-inserts every document (stripping `_id`/`_creationTime`, so identities
+/// change); "merge" keeps existing rows and replaces only those whose `_id`
+/// appears in the snapshot.
+async fn restore_table(
+    app: &AppHandle,
+    client: &ConvexClient,
+    entry: &SnapshotEntry,
+    strategy: &str,
+) -> Result<RestoreReport, String> {
+    let content = std::fs::read_to_string(&entry.path)
+        .map_err(|e| format!("Failed to read snapshot: {}", e))?;
+    let documents: Vec<serde_json::Value> = content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .map(|line| {
+            serde_json::from_str(line).map_err(|e| format!("Invalid snapshot line: {}", e))
+        })
+        .collect::<Result<_, _>>()?;
+
+    let total = documents.len() as u64;
+    let mut report = RestoreReport {
+        table: entry.table.clone(),
+        rows: total,
+        inserted: 0,
+        replaced: 0,
+    };
+
+    if strategy == "replace" {
+        emit_restore_progress(app, &entry.table, 0, total, "clearing");
+        crate::table_import::clear_table(client, &entry.table).await?;
+    }
+
+    let (to_replace, to_insert): (Vec<_>, Vec<_>) = documents
+        .into_iter()
+        .partition(|d| strategy == "merge" && d.get("_id").is_some());
+
+    for document in to_replace {
+        let id = document.get("_id").cloned().unwrap_or_default();
+        let result = client
+            .run_function(
+                "mutation",
+                "_system/frontend/replaceDocument:default",
+                serde_json::json!({ "id": id, "document": document }),
+            )
+            .await?;
+        if !result.success {
+            return Err(result
+                .error_message
+                .unwrap_or_else(|| "Failed to replace document".to_string()));
+        }
+        report.replaced += 1;
+        emit_restore_progress(
+            app,
+            &entry.table,
+            report.replaced + report.inserted,
+            total,
+            "writing",
+        );
+    }
+
+    for batch in to_insert.chunks(PAGE_SIZE) {
+        // System fields can't be inserted; re-created rows get fresh ids
+        let batch: Vec<serde_json::Value> = batch
+            .iter()
+            .map(|document| match document.as_object() {
+                Some(obj) => serde_json::Value::Object(
+                    obj.iter()
+                        .filter(|(key, _)| !key.starts_with('_'))
+                        .map(|(key, value)| (key.clone(), value.clone()))
+                        .collect(),
+                ),
+                None => document.clone(),
+            })
+            .collect();
+
+        let result = client
+            .run_function(
+                "mutation",
+                "_system/frontend/addDocument:default",
+                serde_json::json!({ "table": entry.table, "documents": batch }),
+            )
+            .await?;
+        if !result.success {
+            return Err(result
+                .error_message
+                .unwrap_or_else(|| "Failed to insert documents".to_string()));
+        }
+        report.inserted += batch.len() as u64;
+        emit_restore_progress(
+            app,
+            &entry.table,
+            report.replaced + report.inserted,
+            total,
+            "writing",
+        );
+    }
+
+    emit_restore_progress(app, &entry.table, total, total, "done");
+    Ok(report)
+}
+
+/// Restore a snapshot set into a deployment. `snapshot_id` names any entry
+/// of the set; every table captured in the same export run is restored
+/// unless `tables` narrows the selection. `strategy` is "replace" (default)
+/// or "merge". Before anything is written, the affected tables are backed
+/// up to a fresh snapshot so the restore can be rolled back.
+#[tauri::command]
+pub async fn restore_snapshot(
+    app: AppHandle,
+    snapshot_id: String,
+    deployment_url: String,
+    tables: Option<Vec<String>>,
+    strategy: Option<String>,
+    admin_key: Option<String>,
+) -> Result<RestoreResult, String> {
+    let strategy = strategy.unwrap_or_else(|| "replace".to_string());
+    if !matches!(strategy.as_str(), "replace" | "merge") {
+        return Err(format!("Unknown restore strategy: {}", strategy));
+    }
+
+    let catalog = load_catalog();
+    let anchor = catalog
+        .iter()
+        .find(|entry| entry.id == snapshot_id)
+        .ok_or_else(|| format!("Snapshot {} not found", snapshot_id))?;
+
+    // All entries captured in the same run form the set being restored
+    let mut selected: Vec<SnapshotEntry> = catalog
+        .iter()
+        .filter(|entry| entry.deployment == anchor.deployment && entry.ts == anchor.ts)
+        .cloned()
+        .collect();
+    if let Some(tables) = &tables {
+        selected.retain(|entry| tables.contains(&entry.table));
+    }
+    if selected.is_empty() {
+        return Err("No snapshot tables match the selection".to_string());
+    }
+
+    // Refuse to restore from files that no longer match their checksum
+    for entry in &selected {
+        if file_checksum(std::path::Path::new(&entry.path))? != entry.checksum {
+            return Err(format!(
+                "Snapshot file for table {} failed its checksum; refusing to restore",
+                entry.table
+            ));
+        }
+    }
+
+    let deployment_url = deployment_url.trim_end_matches('/').to_string();
+    let client = ConvexClient::for_deployment(&deployment_url, admin_key)?;
+
+    // Automatic pre-restore backup of the affected tables for rollback
+    let backup_ts = chrono::Utc::now().timestamp_millis();
+    let mut backup = Vec::with_capacity(selected.len());
+    for entry in &selected {
+        backup.push(export_table(&app, &client, &deployment_url, &entry.table, backup_ts).await?);
+    }
+    {
+        let mut catalog = load_catalog();
+        catalog.extend(backup.iter().cloned());
+        save_catalog(&catalog)?;
+    }
+
+    let mut reports = Vec::with_capacity(selected.len());
+    for entry in &selected {
+        reports.push(restore_table(&app, &client, entry, &strategy).await?);
+    }
+
+    Ok(RestoreResult { reports, backup })
+}
+
+/// Delete a snapshot file and its catalog entry
+#[tauri::command]
+pub fn delete_table_snapshot(snapshot_id: String) -> Result<bool, String> {
+    let mut catalog = load_catalog();
+    let before = catalog.len();
+
+    catalog.retain(|entry| {
+        if entry.id != snapshot_id {
+            return true;
+        }
+        let _ = std::fs::remove_file(&entry.path);
+        false
+    });
+
+    if catalog.len() == before {
+        return Ok(false);
+    }
+    save_catalog(&catalog)?;
+    Ok(true)
+}
@@ -0,0 +1,142 @@
+//! Opt-in local usage telemetry
+//!
+//! Feature-usage counters (commands invoked, views opened) aggregated in a
+//! local JSON file. Counting is always local; nothing is uploaded unless
+//! the user has explicitly opted in, and `get_telemetry_report` shows
+//! exactly the payload an upload would send.
+
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+const TELEMETRY_FILE: &str = "telemetry.json";
+const TELEMETRY_ENDPOINT: &str = "https://telemetry.convexpanel.dev/v1/usage";
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct TelemetryState {
+    #[serde(default)]
+    opt_in: bool,
+    /// Event name -> count since the last upload
+    #[serde(default)]
+    counters: HashMap<String, u64>,
+    /// Start of the current counting window (ms since epoch)
+    #[serde(default)]
+    window_start_ms: i64,
+}
+
+/// State is kept in memory and flushed on every change; counts are low-rate
+static STATE: Lazy<Mutex<TelemetryState>> = Lazy::new(|| Mutex::new(load_state()));
+
+fn state_path() -> Result<PathBuf, String> {
+    let home = std::env::var("HOME")
+        .or_else(|_| std::env::var("USERPROFILE"))
+        .map_err(|_| "Failed to get home directory")?;
+
+    let app_data = PathBuf::from(home).join(".convex-panel");
+    std::fs::create_dir_all(&app_data)
+        .map_err(|e| format!("Failed to create app data directory: {}", e))?;
+
+    Ok(app_data.join(TELEMETRY_FILE))
+}
+
+fn load_state() -> TelemetryState {
+    state_path()
+        .ok()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_else(|| TelemetryState {
+            window_start_ms: chrono::Utc::now().timestamp_millis(),
+            ..Default::default()
+        })
+}
+
+fn save_state(state: &TelemetryState) -> Result<(), String> {
+    let path = state_path()?;
+    let json = serde_json::to_string_pretty(state)
+        .map_err(|e| format!("Failed to serialize telemetry: {}", e))?;
+    std::fs::write(&path, json).map_err(|e| format!("Failed to write telemetry: {}", e))
+}
+
+/// Increment a usage counter. Always local — opt-in only gates uploads.
+pub fn count(event: &str) {
+    let mut state = STATE.lock().unwrap();
+    *state.counters.entry(event.to_string()).or_insert(0) += 1;
+    let _ = save_state(&state);
+}
+
+/// The exact payload an upload would send: aggregate counters plus coarse
+/// app/OS info. No identifiers, no timestamps finer than the window.
+fn build_report(state: &TelemetryState) -> serde_json::Value {
+    serde_json::json!({
+        "appVersion": env!("CARGO_PKG_VERSION"),
+        "os": std::env::consts::OS,
+        "windowStartMs": state.window_start_ms,
+        "counters": state.counters,
+    })
+}
+
+/// Count a usage event from the frontend (e.g. "view:logs")
+#[tauri::command]
+pub fn record_telemetry_event(event: String) {
+    count(&event);
+}
+
+/// Preview exactly what an upload would send
+#[tauri::command]
+pub fn get_telemetry_report() -> serde_json::Value {
+    let state = STATE.lock().unwrap();
+    build_report(&state)
+}
+
+/// Whether the user has opted into uploads
+#[tauri::command]
+pub fn get_telemetry_opt_in() -> bool {
+    STATE.lock().unwrap().opt_in
+}
+
+/// Set the upload opt-in. Opting out also clears accumulated counters.
+#[tauri::command]
+pub fn set_telemetry_opt_in(enabled: bool) -> Result<(), String> {
+    let mut state = STATE.lock().unwrap();
+    state.opt_in = enabled;
+    if !enabled {
+        state.counters.clear();
+        state.window_start_ms = chrono::Utc::now().timestamp_millis();
+    }
+    save_state(&state)
+}
+
+/// Upload the current report and reset the counting window. Refuses to send
+/// anything without opt-in.
+#[tauri::command]
+pub async fn upload_telemetry() -> Result<(), String> {
+    let report = {
+        let state = STATE.lock().unwrap();
+        if !state.opt_in {
+            return Err("Telemetry upload requires opt-in".to_string());
+        }
+        if state.counters.is_empty() {
+            return Ok(());
+        }
+        build_report(&state)
+    };
+
+    let client = reqwest::Client::new();
+    let response = client
+        .post(TELEMETRY_ENDPOINT)
+        .json(&report)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to upload telemetry: {}", e))?;
+
+    if !response.status().is_success() {
+        return Err(format!("Upload failed: {}", response.status()));
+    }
+
+    let mut state = STATE.lock().unwrap();
+    state.counters.clear();
+    state.window_start_ms = chrono::Utc::now().timestamp_millis();
+    save_state(&state)
+}
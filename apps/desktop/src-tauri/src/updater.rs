@@ -0,0 +1,107 @@
+//! Auto-update support with release channels
+//!
+//! Persists a stable/beta channel choice and exposes the check/install
+//! commands for the frontend, the "Check for Updates…" menu item, and the
+//! tray's pending-update indicator.
+//!
+//! Checking and installing are disabled until release signing exists: the
+//! updater verifies downloads against a minisign public key, and no keypair
+//! has been generated yet. Shipping an empty pubkey would mean every check
+//! fails after download at best and normalizes unverified binaries at
+//! worst, so the updater plugin stays out of tauri.conf.json and these
+//! commands refuse up front. Once a keypair exists, embed the public key in
+//! the config, re-enable `createUpdaterArtifacts`, and restore the
+//! tauri-plugin-updater flow here.
+
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use tauri::AppHandle;
+
+const CHANNEL_FILE: &str = "update-channel";
+const DEFAULT_CHANNEL: &str = "stable";
+
+const UPDATES_DISABLED: &str =
+    "Automatic updates are disabled in this build: no release signing key is configured";
+
+/// Version string of an update found by the last check, if any
+static PENDING_UPDATE: Lazy<Mutex<Option<String>>> = Lazy::new(|| Mutex::new(None));
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UpdateStatus {
+    pub available: bool,
+    pub version: Option<String>,
+    pub channel: String,
+    pub current_version: String,
+}
+
+fn channel_path() -> Result<PathBuf, String> {
+    let home = std::env::var("HOME")
+        .or_else(|_| std::env::var("USERPROFILE"))
+        .map_err(|_| "Failed to get home directory")?;
+
+    let app_data = PathBuf::from(home).join(".convex-panel");
+    std::fs::create_dir_all(&app_data)
+        .map_err(|e| format!("Failed to create app data directory: {}", e))?;
+
+    Ok(app_data.join(CHANNEL_FILE))
+}
+
+/// Currently selected release channel (stable unless the user opted into beta)
+pub fn current_channel() -> String {
+    channel_path()
+        .ok()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| DEFAULT_CHANNEL.to_string())
+}
+
+/// Switch between release channels ("stable" or "beta")
+#[tauri::command]
+pub fn set_update_channel(channel: String) -> Result<(), String> {
+    if channel != "stable" && channel != "beta" {
+        return Err(format!("Unknown release channel: {}", channel));
+    }
+
+    let path = channel_path()?;
+    std::fs::write(&path, &channel).map_err(|e| format!("Failed to save channel: {}", e))?;
+
+    // A channel switch invalidates any previously found update
+    *PENDING_UPDATE.lock().unwrap() = None;
+
+    Ok(())
+}
+
+/// Check the selected channel for a newer version. Refuses while updates
+/// are disabled; see the module docs.
+#[tauri::command]
+pub async fn check_for_updates(_app: AppHandle) -> Result<UpdateStatus, String> {
+    Err(UPDATES_DISABLED.to_string())
+}
+
+/// Download and install the pending update, then restart the app. Refuses
+/// while updates are disabled; see the module docs.
+#[tauri::command]
+pub async fn install_pending_update(_app: AppHandle) -> Result<(), String> {
+    Err(UPDATES_DISABLED.to_string())
+}
+
+/// Latest check result without hitting the network
+#[tauri::command]
+pub fn get_update_status(app: AppHandle) -> UpdateStatus {
+    let pending = PENDING_UPDATE.lock().unwrap().clone();
+
+    UpdateStatus {
+        available: pending.is_some(),
+        version: pending,
+        channel: current_channel(),
+        current_version: app.package_info().version.to_string(),
+    }
+}
+
+/// Whether an update is waiting to be installed (drives the tray indicator)
+pub fn update_pending() -> Option<String> {
+    PENDING_UPDATE.lock().unwrap().clone()
+}
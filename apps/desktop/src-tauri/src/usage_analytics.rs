@@ -0,0 +1,134 @@
+//! Function usage analytics by caller identity
+//!
+//! Answers "who actually calls this?" from the local log store before a
+//! refactor: attributes calls to the identity/client recorded in the raw
+//! log payload and surfaces top functions per user and functions that are
+//! new to the deployment.
+
+use rusqlite::params;
+use serde::Serialize;
+use tauri::State;
+
+use crate::log_store::DbConnection;
+
+/// The raw payload fields an identity can hide under, in preference order.
+/// Falls back to "anonymous" so unauthenticated traffic still aggregates.
+pub(crate) const IDENTITY_SQL: &str = "COALESCE(
+    json_extract(json_blob, '$.identity'),
+    json_extract(json_blob, '$.auth.subject'),
+    json_extract(json_blob, '$.userId'),
+    json_extract(json_blob, '$.clientId'),
+    'anonymous'
+)";
+
+/// Calls of one function attributed to one identity
+#[derive(Debug, Clone, Serialize)]
+pub struct IdentityFunctionUsage {
+    pub identity: String,
+    pub function_path: String,
+    pub calls: i64,
+    pub errors: i64,
+    pub last_call_ts: i64,
+}
+
+/// A function first seen within the queried window
+#[derive(Debug, Clone, Serialize)]
+pub struct NewFunction {
+    pub function_path: String,
+    pub first_call_ts: i64,
+    pub calls: i64,
+    pub identities: i64,
+}
+
+/// Top function/identity pairs by call count over a range. With `identity`
+/// set, only that caller's usage is returned.
+#[tauri::command]
+pub async fn get_top_functions_by_identity(
+    db: State<'_, DbConnection>,
+    deployment: String,
+    start_ts: i64,
+    end_ts: i64,
+    identity: Option<String>,
+    limit: Option<i64>,
+) -> Result<Vec<IdentityFunctionUsage>, String> {
+    let conn = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+
+    let mut sql = format!(
+        "SELECT {IDENTITY_SQL} AS caller, function_path,
+                COUNT(*),
+                SUM(CASE WHEN success = 0 THEN 1 ELSE 0 END),
+                MAX(ts)
+         FROM logs
+         WHERE deployment = ?1 AND ts >= ?2 AND ts <= ?3 AND function_path IS NOT NULL"
+    );
+    if identity.is_some() {
+        sql.push_str(" AND caller = ?5");
+    }
+    sql.push_str(" GROUP BY caller, function_path ORDER BY COUNT(*) DESC LIMIT ?4");
+
+    let mut stmt = conn
+        .prepare(&sql)
+        .map_err(|e| format!("Query error: {}", e))?;
+
+    let map_row = |row: &rusqlite::Row| {
+        Ok(IdentityFunctionUsage {
+            identity: row.get(0)?,
+            function_path: row.get(1)?,
+            calls: row.get(2)?,
+            errors: row.get(3)?,
+            last_call_ts: row.get(4)?,
+        })
+    };
+
+    let limit = limit.unwrap_or(50).min(500);
+    let rows = match &identity {
+        Some(identity) => stmt.query_map(
+            params![deployment, start_ts, end_ts, limit, identity],
+            map_row,
+        ),
+        None => stmt.query_map(params![deployment, start_ts, end_ts, limit], map_row),
+    }
+    .map_err(|e| format!("Query error: {}", e))?;
+
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Query error: {}", e))
+}
+
+/// Functions whose first recorded call falls after `since_ts` (e.g. the
+/// start of this week), with how many distinct identities already use them
+#[tauri::command]
+pub async fn get_new_functions(
+    db: State<'_, DbConnection>,
+    deployment: String,
+    since_ts: i64,
+) -> Result<Vec<NewFunction>, String> {
+    let conn = db.lock().map_err(|e| format!("Lock error: {}", e))?;
+
+    let sql = format!(
+        "SELECT function_path, MIN(ts), COUNT(*),
+                COUNT(DISTINCT {IDENTITY_SQL})
+         FROM logs
+         WHERE deployment = ?1 AND function_path IS NOT NULL
+         GROUP BY function_path
+         HAVING MIN(ts) >= ?2
+         ORDER BY MIN(ts) DESC"
+    );
+
+    let mut stmt = conn
+        .prepare(&sql)
+        .map_err(|e| format!("Query error: {}", e))?;
+
+    let rows = stmt
+        .query_map(params![deployment, since_ts], |row| {
+            Ok(NewFunction {
+                function_path: row.get(0)?,
+                first_call_ts: row.get(1)?,
+                calls: row.get(2)?,
+                identities: row.get(3)?,
+            })
+        })
+        .map_err(|e| format!("Query error: {}", e))?;
+
+    rows.collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Query error: {}", e))
+}
@@ -0,0 +1,213 @@
+//! Local webhook receiver
+//!
+//! A toggleable HTTP listener with a generated localhost URL that records
+//! every incoming request — method, headers, body, timing — so third-party
+//! webhooks or a project's own HTTP actions can be pointed at it during
+//! development and the captured requests replayed against a real endpoint.
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::Router;
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::sync::Mutex;
+use tauri::{AppHandle, Emitter, Manager};
+
+const MAX_CAPTURED_REQUESTS: usize = 500;
+const DEFAULT_PORT: u16 = 14320;
+
+/// Running receiver: the generated URL plus the shutdown handle
+static RECEIVER: Lazy<Mutex<Option<(String, tokio::sync::oneshot::Sender<()>)>>> =
+    Lazy::new(|| Mutex::new(None));
+
+/// Captured requests, newest last
+static CAPTURED: Lazy<Mutex<VecDeque<CapturedRequest>>> =
+    Lazy::new(|| Mutex::new(VecDeque::new()));
+
+static NEXT_ID: std::sync::atomic::AtomicU64 = std::sync::atomic::AtomicU64::new(1);
+
+/// One recorded incoming request
+#[derive(Debug, Clone, Serialize)]
+pub struct CapturedRequest {
+    pub id: u64,
+    pub ts: i64,
+    pub method: String,
+    pub path: String,
+    pub headers: HashMap<String, String>,
+    pub body: String,
+}
+
+async fn capture_handler(
+    State(app): State<AppHandle>,
+    request: axum::extract::Request,
+) -> StatusCode {
+    let ts = chrono::Utc::now().timestamp_millis();
+    let method = request.method().to_string();
+    let path = request.uri().path().to_string();
+
+    let headers: HashMap<String, String> = request
+        .headers()
+        .iter()
+        .filter_map(|(name, value)| {
+            value
+                .to_str()
+                .ok()
+                .map(|value| (name.to_string(), value.to_string()))
+        })
+        .collect();
+
+    let body = axum::body::to_bytes(request.into_body(), 1024 * 1024)
+        .await
+        .map(|bytes| String::from_utf8_lossy(&bytes).to_string())
+        .unwrap_or_default();
+
+    let captured = CapturedRequest {
+        id: NEXT_ID.fetch_add(1, std::sync::atomic::Ordering::Relaxed),
+        ts,
+        method,
+        path,
+        headers,
+        body,
+    };
+
+    {
+        let mut requests = CAPTURED.lock().unwrap();
+        if requests.len() >= MAX_CAPTURED_REQUESTS {
+            requests.pop_front();
+        }
+        requests.push_back(captured.clone());
+    }
+
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.emit("webhook-received", &captured);
+    }
+
+    StatusCode::OK
+}
+
+/// Start the receiver and return its generated URL. The random path segment
+/// keeps other local processes from guessing the endpoint.
+#[tauri::command]
+pub async fn start_webhook_receiver(app: AppHandle, port: Option<u16>) -> Result<String, String> {
+    {
+        let receiver = RECEIVER.lock().unwrap();
+        if let Some((url, _)) = receiver.as_ref() {
+            return Ok(url.clone());
+        }
+    }
+
+    // Random path segment from the OS RNG
+    use aes_gcm::aead::{rand_core::RngCore, OsRng};
+    let mut bytes = [0u8; 12];
+    OsRng.fill_bytes(&mut bytes);
+    let secret_path = hex::encode(bytes);
+
+    let port = port.unwrap_or(DEFAULT_PORT);
+    let listener = tokio::net::TcpListener::bind(("127.0.0.1", port))
+        .await
+        .map_err(|e| format!("Failed to bind webhook receiver: {}", e))?;
+    let bound_port = listener
+        .local_addr()
+        .map_err(|e| format!("Failed to bind webhook receiver: {}", e))?
+        .port();
+
+    let url = format!("http://127.0.0.1:{}/hooks/{}", bound_port, secret_path);
+
+    let router = Router::new()
+        .route(
+            &format!("/hooks/{}", secret_path),
+            axum::routing::any(capture_handler),
+        )
+        .route(
+            &format!("/hooks/{}/*rest", secret_path),
+            axum::routing::any(capture_handler),
+        )
+        .with_state(app);
+
+    let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel();
+    *RECEIVER.lock().unwrap() = Some((url.clone(), shutdown_tx));
+
+    tauri::async_runtime::spawn(async move {
+        let serve = axum::serve(listener, router).with_graceful_shutdown(async {
+            let _ = shutdown_rx.await;
+        });
+        if let Err(e) = serve.await {
+            eprintln!("[webhook_receiver] Server error: {}", e);
+        }
+        RECEIVER.lock().unwrap().take();
+    });
+
+    Ok(url)
+}
+
+/// Stop the receiver. Captured requests are kept for inspection.
+#[tauri::command]
+pub fn stop_webhook_receiver() -> Result<bool, String> {
+    match RECEIVER.lock().unwrap().take() {
+        Some((_, shutdown_tx)) => {
+            let _ = shutdown_tx.send(());
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+/// The receiver's URL when it is running
+#[tauri::command]
+pub fn get_webhook_receiver_status() -> Option<String> {
+    RECEIVER.lock().unwrap().as_ref().map(|(url, _)| url.clone())
+}
+
+/// Captured requests, newest first
+#[tauri::command]
+pub fn list_webhook_requests() -> Vec<CapturedRequest> {
+    let requests = CAPTURED.lock().unwrap();
+    requests.iter().rev().cloned().collect()
+}
+
+/// Drop all captured requests
+#[tauri::command]
+pub fn clear_webhook_requests() {
+    CAPTURED.lock().unwrap().clear();
+}
+
+/// Re-send a captured request to a target URL (e.g. the project's real HTTP
+/// action) and return the response status
+#[tauri::command]
+pub async fn replay_webhook_request(request_id: u64, target_url: String) -> Result<u16, String> {
+    let captured = {
+        let requests = CAPTURED.lock().unwrap();
+        requests
+            .iter()
+            .find(|r| r.id == request_id)
+            .cloned()
+            .ok_or_else(|| format!("Captured request {} not found", request_id))?
+    };
+
+    let client = reqwest::Client::new();
+    let method: reqwest::Method = captured
+        .method
+        .parse()
+        .map_err(|_| format!("Invalid method: {}", captured.method))?;
+
+    let mut request = client.request(method, &target_url).body(captured.body);
+    for (name, value) in &captured.headers {
+        // Hop-by-hop headers don't transfer to the new connection
+        if matches!(
+            name.to_ascii_lowercase().as_str(),
+            "host" | "content-length" | "connection"
+        ) {
+            continue;
+        }
+        request = request.header(name, value);
+    }
+
+    let response = request
+        .send()
+        .await
+        .map_err(|e| format!("Replay failed: {}", e))?;
+
+    Ok(response.status().as_u16())
+}
@@ -0,0 +1,227 @@
+//! Window geometry persistence
+//!
+//! Saves window size, position, and monitor on move/resize/close and restores
+//! them at startup, with sanity checks so a window saved on a since-
+//! disconnected monitor doesn't come back off-screen.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::PathBuf;
+use tauri::{Manager, PhysicalPosition, PhysicalSize, WebviewWindow, WindowEvent};
+
+const STATE_FILE: &str = "window-state.json";
+
+/// Saved geometry for one window, in physical pixels
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WindowGeometry {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+    pub maximized: bool,
+    /// Name of the monitor the window was on, if known
+    pub monitor: Option<String>,
+    /// Whether the window floats above other applications
+    #[serde(default)]
+    pub always_on_top: bool,
+    /// Webview zoom factor (1.0 = 100%)
+    #[serde(default = "default_zoom")]
+    pub zoom: f64,
+}
+
+fn default_zoom() -> f64 {
+    1.0
+}
+
+fn state_path() -> Result<PathBuf, String> {
+    let home = std::env::var("HOME")
+        .or_else(|_| std::env::var("USERPROFILE"))
+        .map_err(|_| "Failed to get home directory")?;
+
+    let app_data = PathBuf::from(home).join(".convex-panel");
+    fs::create_dir_all(&app_data)
+        .map_err(|e| format!("Failed to create app data directory: {}", e))?;
+
+    Ok(app_data.join(STATE_FILE))
+}
+
+fn load_states() -> HashMap<String, WindowGeometry> {
+    let path = match state_path() {
+        Ok(p) => p,
+        Err(_) => return HashMap::new(),
+    };
+
+    fs::read_to_string(&path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_states(states: &HashMap<String, WindowGeometry>) -> Result<(), String> {
+    let path = state_path()?;
+    let json = serde_json::to_string_pretty(states)
+        .map_err(|e| format!("Failed to serialize window state: {}", e))?;
+    fs::write(&path, json).map_err(|e| format!("Failed to write window state: {}", e))
+}
+
+/// Capture the window's current geometry and persist it
+pub fn persist(window: &WebviewWindow) {
+    let geometry = match capture(window) {
+        Some(g) => g,
+        None => return,
+    };
+
+    let mut states = load_states();
+    states.insert(window.label().to_string(), geometry);
+
+    if let Err(e) = save_states(&states) {
+        eprintln!("[window_state] Failed to save window state: {}", e);
+    }
+}
+
+fn capture(window: &WebviewWindow) -> Option<WindowGeometry> {
+    // Don't record the transient geometry of a maximized window; keep the
+    // last normal bounds and just remember the maximized flag
+    let maximized = window.is_maximized().unwrap_or(false);
+
+    if maximized {
+        let mut states = load_states();
+        if let Some(existing) = states.get_mut(window.label()) {
+            existing.maximized = true;
+            let _ = save_states(&states);
+        }
+        return None;
+    }
+
+    let position = window.outer_position().ok()?;
+    let size = window.outer_size().ok()?;
+    let monitor = window
+        .current_monitor()
+        .ok()
+        .flatten()
+        .and_then(|m| m.name().cloned());
+
+    // Geometry events don't know about always-on-top or zoom; carry them over
+    let saved = load_states();
+    let existing = saved.get(window.label());
+    let always_on_top = existing.map(|s| s.always_on_top).unwrap_or(false);
+    let zoom = existing.map(|s| s.zoom).unwrap_or_else(default_zoom);
+
+    Some(WindowGeometry {
+        x: position.x,
+        y: position.y,
+        width: size.width,
+        height: size.height,
+        maximized: false,
+        monitor,
+        always_on_top,
+        zoom,
+    })
+}
+
+/// Record the always-on-top preference for a window so it survives restarts
+pub fn set_always_on_top_state(label: &str, always_on_top: bool) {
+    let mut states = load_states();
+
+    if let Some(state) = states.get_mut(label) {
+        state.always_on_top = always_on_top;
+        if let Err(e) = save_states(&states) {
+            eprintln!("[window_state] Failed to save always-on-top state: {}", e);
+        }
+    }
+}
+
+/// Saved always-on-top preference for a window
+pub fn always_on_top_state(label: &str) -> bool {
+    load_states()
+        .get(label)
+        .map(|s| s.always_on_top)
+        .unwrap_or(false)
+}
+
+/// Record the zoom factor for a window so it survives restarts
+pub fn set_zoom_state(label: &str, zoom: f64) {
+    let mut states = load_states();
+
+    if let Some(state) = states.get_mut(label) {
+        state.zoom = zoom;
+        if let Err(e) = save_states(&states) {
+            eprintln!("[window_state] Failed to save zoom state: {}", e);
+        }
+    }
+}
+
+/// Saved zoom factor for a window (1.0 when none saved)
+pub fn zoom_state(label: &str) -> f64 {
+    load_states()
+        .get(label)
+        .map(|s| s.zoom)
+        .unwrap_or_else(default_zoom)
+}
+
+/// True if the saved position is (partially) visible on a connected monitor
+fn is_on_screen(window: &WebviewWindow, geometry: &WindowGeometry) -> bool {
+    let monitors = match window.available_monitors() {
+        Ok(m) => m,
+        Err(_) => return false,
+    };
+
+    monitors.iter().any(|monitor| {
+        let pos = monitor.position();
+        let size = monitor.size();
+
+        let overlaps_x = geometry.x + geometry.width as i32 > pos.x
+            && geometry.x < pos.x + size.width as i32;
+        let overlaps_y = geometry.y + geometry.height as i32 > pos.y
+            && geometry.y < pos.y + size.height as i32;
+
+        overlaps_x && overlaps_y
+    })
+}
+
+/// Restore saved geometry for this window. Returns false when there is no
+/// saved state (first launch) or the state fails the monitor sanity check.
+pub fn restore(window: &WebviewWindow) -> bool {
+    let states = load_states();
+    let geometry = match states.get(window.label()) {
+        Some(g) => g,
+        None => return false,
+    };
+
+    if !is_on_screen(window, geometry) {
+        println!(
+            "[window_state] Saved position for '{}' is off-screen, ignoring",
+            window.label()
+        );
+        return false;
+    }
+
+    let _ = window.set_size(PhysicalSize::new(geometry.width, geometry.height));
+    let _ = window.set_position(PhysicalPosition::new(geometry.x, geometry.y));
+
+    if geometry.maximized {
+        let _ = window.maximize();
+    }
+
+    if geometry.always_on_top {
+        let _ = window.set_always_on_top(true);
+    }
+
+    if (geometry.zoom - 1.0).abs() > f64::EPSILON {
+        let _ = window.set_zoom(geometry.zoom);
+    }
+
+    true
+}
+
+/// Persist geometry whenever the window is moved, resized, or closed
+pub fn attach_listeners(window: &WebviewWindow) {
+    let window_clone = window.clone();
+    window.on_window_event(move |event| match event {
+        WindowEvent::Moved(_) | WindowEvent::Resized(_) | WindowEvent::CloseRequested { .. } => {
+            persist(&window_clone);
+        }
+        _ => {}
+    });
+}
@@ -0,0 +1,141 @@
+//! Workspace profiles
+//!
+//! Named combinations of project path, deployment, env profile, saved log
+//! filters, and terminal layout. Switching a profile retargets native log
+//! sync to the profile's deployment and emits a context-switch event so the
+//! frontend (and the MCP server it manages) reconfigures in one step.
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use tauri::{AppHandle, Emitter, Manager};
+
+const PROFILES_FILE: &str = "profiles.json";
+
+/// One saved workspace profile. The filter and layout payloads are opaque
+/// frontend state; the backend only stores and replays them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkspaceProfile {
+    pub name: String,
+    pub project_path: Option<String>,
+    pub deployment_url: Option<String>,
+    /// Which env file variant the project uses (e.g. ".env.local")
+    pub env_profile: Option<String>,
+    #[serde(default)]
+    pub log_filters: serde_json::Value,
+    #[serde(default)]
+    pub terminal_layout: serde_json::Value,
+}
+
+/// Result of a switch: what was reconfigured and what wasn't
+#[derive(Debug, Clone, Serialize)]
+pub struct SwitchReport {
+    pub profile: WorkspaceProfile,
+    pub log_stream_started: bool,
+    pub log_stream_error: Option<String>,
+}
+
+fn profiles_path() -> Result<PathBuf, String> {
+    let home = std::env::var("HOME")
+        .or_else(|_| std::env::var("USERPROFILE"))
+        .map_err(|_| "Failed to get home directory")?;
+
+    let app_data = PathBuf::from(home).join(".convex-panel");
+    std::fs::create_dir_all(&app_data)
+        .map_err(|e| format!("Failed to create app data directory: {}", e))?;
+
+    Ok(app_data.join(PROFILES_FILE))
+}
+
+fn load_profiles() -> Vec<WorkspaceProfile> {
+    profiles_path()
+        .ok()
+        .and_then(|path| std::fs::read_to_string(path).ok())
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_profiles(profiles: &[WorkspaceProfile]) -> Result<(), String> {
+    let path = profiles_path()?;
+    let json = serde_json::to_string_pretty(profiles)
+        .map_err(|e| format!("Failed to serialize profiles: {}", e))?;
+    std::fs::write(&path, json).map_err(|e| format!("Failed to write profiles: {}", e))
+}
+
+/// All saved profiles, sorted by name
+#[tauri::command]
+pub fn list_workspace_profiles() -> Vec<WorkspaceProfile> {
+    let mut profiles = load_profiles();
+    profiles.sort_by(|a, b| a.name.cmp(&b.name));
+    profiles
+}
+
+/// Create or update a profile (matched by name)
+#[tauri::command]
+pub fn save_workspace_profile(profile: WorkspaceProfile) -> Result<(), String> {
+    if profile.name.trim().is_empty() {
+        return Err("Profile name cannot be empty".to_string());
+    }
+
+    let mut profiles = load_profiles();
+    profiles.retain(|existing| existing.name != profile.name);
+    profiles.push(profile);
+    save_profiles(&profiles)
+}
+
+/// Delete a profile by name
+#[tauri::command]
+pub fn delete_workspace_profile(name: String) -> Result<bool, String> {
+    let mut profiles = load_profiles();
+    let before = profiles.len();
+    profiles.retain(|profile| profile.name != name);
+
+    if profiles.len() == before {
+        return Ok(false);
+    }
+    save_profiles(&profiles)?;
+    Ok(true)
+}
+
+/// Switch the workspace to a named profile: stop log streams for other
+/// deployments, start one for the profile's deployment (when a deploy key
+/// is stored), record the project as recently opened, and hand the full
+/// profile to the frontend via "profile-switched" so it can re-point log
+/// filters, the terminal layout, and the MCP server.
+#[tauri::command]
+pub fn switch_profile(app: AppHandle, name: String) -> Result<SwitchReport, String> {
+    let profile = load_profiles()
+        .into_iter()
+        .find(|profile| profile.name == name)
+        .ok_or_else(|| format!("Profile {} not found", name))?;
+
+    let mut report = SwitchReport {
+        profile: profile.clone(),
+        log_stream_started: false,
+        log_stream_error: None,
+    };
+
+    // Retarget native log sync to the profile's deployment
+    for url in crate::log_stream::list_log_streams() {
+        if Some(&url) != profile.deployment_url.as_ref() {
+            let _ = crate::log_stream::stop_log_stream(url);
+        }
+    }
+    if let Some(url) = &profile.deployment_url {
+        match crate::log_stream::start_log_stream(app.clone(), url.clone(), None) {
+            Ok(()) => report.log_stream_started = true,
+            // Typically no stored deploy key; the switch still succeeds
+            Err(e) => report.log_stream_error = Some(e),
+        }
+        crate::deployments::touch_deployment(url);
+    }
+
+    if let Some(path) = &profile.project_path {
+        let _ = crate::recent_projects::record_recent_project(path.clone());
+    }
+
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.emit("profile-switched", &profile);
+    }
+
+    Ok(report)
+}